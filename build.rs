@@ -0,0 +1,53 @@
+use std::env;
+use std::path::Path;
+
+/// Returns the value of the `name` environment variable for the target being built: the
+/// `<name>_<target>` variable (like `GEOS_LIB_DIR_aarch64_linux_android`) if set, `name`
+/// otherwise.
+fn target_var(name: &str) -> Option<String> {
+    let target = env::var("TARGET").unwrap_or_default().replace('-', "_");
+    let target_name = format!("{}_{}", name, target);
+    println!("cargo:rerun-if-env-changed={}", target_name);
+    println!("cargo:rerun-if-env-changed={}", name);
+    env::var(&target_name).or_else(|_| env::var(name)).ok()
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    // `geos-sys` only looks for `geos_c` with pkg-config (which refuses to cross-compile unless
+    // configured for it), so prebuilt libraries for the other targets are added here.
+    let lib_dir = target_var("GEOS_LIB_DIR");
+    if let Some(ref lib_dir) = lib_dir {
+        println!("cargo:rustc-link-search=native={}", lib_dir);
+    }
+    if !matches!(target_var("GEOS_STATIC").as_deref(), None | Some("0")) {
+        // The `static` feature makes `geos-sys` build and link its own GEOS.
+        if env::var_os("CARGO_FEATURE_STATIC").is_some() {
+            panic!("GEOS_STATIC can't be used with the `static` feature, which builds GEOS");
+        }
+        // Without pkg-config, `geos-sys` still asks for a `geos_c` dylib, which the linker only
+        // resolves to the static library if there's no shared one next to it.
+        if let Some(ref lib_dir) = lib_dir {
+            for shared in &["libgeos_c.so", "libgeos_c.dylib"] {
+                if Path::new(lib_dir).join(shared).exists() {
+                    panic!(
+                        "GEOS_STATIC is set but {} contains {}, which would be linked too",
+                        lib_dir, shared
+                    );
+                }
+            }
+        }
+        // The static `geos_c` needs the C++ part of GEOS and a C++ standard library.
+        println!("cargo:rustc-link-lib=static=geos_c");
+        println!("cargo:rustc-link-lib=static=geos");
+        let cxx = target_var("GEOS_CXX_LIB").unwrap_or_else(|| {
+            match env::var("CARGO_CFG_TARGET_OS").unwrap_or_default().as_str() {
+                "android" => "c++_shared".to_owned(),
+                "ios" | "macos" | "freebsd" => "c++".to_owned(),
+                _ => "stdc++".to_owned(),
+            }
+        });
+        println!("cargo:rustc-link-lib=dylib={}", cxx);
+    }
+}
@@ -0,0 +1,183 @@
+//! Spatial analysis helpers built on top of the GEOS operations and spatial index.
+
+use crate::{Geom, STRtree, SpatialIndex};
+use error::GResult;
+
+/// Returns the `(i, j, area)` triples of all the pairs of `geoms` (with `i < j`) whose
+/// intersection has a non-zero area, sorted by `(i, j)`.
+///
+/// Candidate pairs are found using a [`STRtree`] and filtered with a prepared geometry, so only
+/// actually intersecting pairs go through the intersection computation. It's typically used to
+/// find double-digitized parcels.
+///
+/// # Example
+///
+/// ```
+/// use geos::Geometry;
+/// use geos::analysis::overlap_areas;
+///
+/// let geoms = vec![
+///     Geometry::new_from_wkt("POLYGON((0 0, 2 0, 2 2, 0 2, 0 0))").expect("Invalid geometry"),
+///     Geometry::new_from_wkt("POLYGON((1 1, 3 1, 3 3, 1 3, 1 1))").expect("Invalid geometry"),
+///     // Only touching the first one.
+///     Geometry::new_from_wkt("POLYGON((-1 0, 0 0, 0 1, -1 1, -1 0))").expect("Invalid geometry"),
+/// ];
+///
+/// assert_eq!(overlap_areas(&geoms), Ok(vec![(0, 1, 1.)]));
+/// ```
+pub fn overlap_areas<'a, G: Geom<'a>>(geoms: &[G]) -> GResult<Vec<(usize, usize, f64)>> {
+    let mut tree = STRtree::with_capacity(10)?;
+    for (i, geom) in geoms.iter().enumerate() {
+        if !geom.is_empty()? {
+            tree.insert(geom, i);
+        }
+    }
+
+    let mut res = Vec::new();
+    for (i, geom) in geoms.iter().enumerate() {
+        if geom.is_empty()? {
+            continue;
+        }
+        let mut candidates = Vec::new();
+        tree.query(geom, |j: &usize| {
+            if *j > i {
+                candidates.push(*j);
+            }
+        });
+        candidates.sort_unstable();
+
+        let prepared = geom.to_prepared_geom()?;
+        for j in candidates {
+            if !prepared.intersects(&geoms[j])? {
+                continue;
+            }
+            let area = geom.intersection(&geoms[j])?.area()?;
+            if area > 0. {
+                res.push((i, j, area));
+            }
+        }
+    }
+    Ok(res)
+}
+
+/// Redistributes the values attached to the `sources` polygons onto the `targets` polygons,
+/// proportionally to the area of their intersections (area-weighted interpolation of an
+/// extensive value, like a population count).
+///
+/// Each returned value is the sum, over all the sources, of `value * area(source ∩ target) /
+/// area(source)`. Sources with a zero area (like empty geometries) and targets intersecting no
+/// source get nothing. If the targets don't cover the sources, the part of the values falling
+/// outside of them is lost; if they overlap, it is counted several times.
+///
+/// # Example
+///
+/// ```
+/// use geos::Geometry;
+/// use geos::analysis::areal_interpolation;
+///
+/// let sources = vec![
+///     (Geometry::new_from_wkt("POLYGON((0 0, 2 0, 2 2, 0 2, 0 0))").expect("Invalid geometry"),
+///      100.),
+///     (Geometry::new_from_wkt("POLYGON EMPTY").expect("Invalid geometry"), 12.),
+/// ];
+/// let targets = vec![
+///     Geometry::new_from_wkt("POLYGON((0 0, 1 0, 1 2, 0 2, 0 0))").expect("Invalid geometry"),
+///     Geometry::new_from_wkt("POLYGON((1 0, 4 0, 4 2, 1 2, 1 0))").expect("Invalid geometry"),
+///     Geometry::new_from_wkt("POLYGON((5 5, 6 5, 6 6, 5 6, 5 5))").expect("Invalid geometry"),
+/// ];
+///
+/// assert_eq!(areal_interpolation(&sources, &targets), Ok(vec![50., 50., 0.]));
+/// ```
+pub fn areal_interpolation<'a, 'b, S: Geom<'a>, T: Geom<'b>>(
+    sources: &[(S, f64)],
+    targets: &[T],
+) -> GResult<Vec<f64>> {
+    let mut tree = STRtree::with_capacity(10)?;
+    for (i, target) in targets.iter().enumerate() {
+        if !target.is_empty()? {
+            tree.insert(target, i);
+        }
+    }
+
+    let mut res = vec![0.; targets.len()];
+    for (source, value) in sources {
+        if source.is_empty()? {
+            continue;
+        }
+        let source_area = source.area()?;
+        if source_area <= 0. {
+            continue;
+        }
+        let mut candidates = Vec::new();
+        tree.query(source, |i: &usize| candidates.push(*i));
+        candidates.sort_unstable();
+
+        let prepared = source.to_prepared_geom()?;
+        for i in candidates {
+            if !prepared.intersects(&targets[i])? {
+                continue;
+            }
+            let area = source.intersection(&targets[i])?.area()?;
+            res[i] += value * area / source_area;
+        }
+    }
+    Ok(res)
+}
+
+/// Returns, for each polygon of `polygons`, the sorted indices of the `points` it contains,
+/// which is the first step of most zonal statistics.
+///
+/// The points are indexed in a [`STRtree`] and each polygon is prepared, so each polygon is only
+/// tested against the points of its envelope. A point on the boundary of a polygon is considered
+/// inside of it: a point on a boundary shared by two polygons is given to both. Empty geometries
+/// never match.
+///
+/// # Example
+///
+/// ```
+/// use geos::Geometry;
+/// use geos::analysis::points_in_polygons;
+///
+/// let points = ["POINT(1 1)", "POINT(3 1)", "POINT(2 1)", "POINT(9 9)", "POINT EMPTY"]
+///     .iter()
+///     .map(|wkt| Geometry::new_from_wkt(wkt).expect("Invalid geometry"))
+///     .collect::<Vec<_>>();
+/// let polygons = vec![
+///     Geometry::new_from_wkt("POLYGON((0 0, 2 0, 2 2, 0 2, 0 0))").expect("Invalid geometry"),
+///     Geometry::new_from_wkt("POLYGON((2 0, 4 0, 4 2, 2 2, 2 0))").expect("Invalid geometry"),
+///     Geometry::new_from_wkt("POLYGON((5 5, 6 5, 6 6, 5 6, 5 5))").expect("Invalid geometry"),
+/// ];
+///
+/// assert_eq!(points_in_polygons(&points, &polygons),
+///            Ok(vec![vec![0, 2], vec![1, 2], vec![]]));
+/// ```
+pub fn points_in_polygons<'a, 'b, P: Geom<'a>, G: Geom<'b>>(
+    points: &[P],
+    polygons: &[G],
+) -> GResult<Vec<Vec<usize>>> {
+    let mut tree = STRtree::with_capacity(10)?;
+    for (i, point) in points.iter().enumerate() {
+        if !point.is_empty()? {
+            tree.insert(point, i);
+        }
+    }
+
+    let mut res = Vec::with_capacity(polygons.len());
+    for polygon in polygons {
+        let mut inside = Vec::new();
+        if !polygon.is_empty()? {
+            let mut candidates = Vec::new();
+            tree.query(polygon, |i: &usize| candidates.push(*i));
+            candidates.sort_unstable();
+
+            let prepared = polygon.to_prepared_geom()?;
+            for i in candidates {
+                if prepared.intersects(&points[i])? {
+                    inside.push(i);
+                }
+            }
+        }
+        res.push(inside);
+    }
+    Ok(res)
+}
@@ -0,0 +1,134 @@
+use crate::{ContextHandle, GResult, Geometry};
+use std::cell::RefCell;
+use std::sync::Arc;
+
+/// Keeps short-lived geometries alive until the arena is dropped (or [`clear`](#method.clear)ed),
+/// handing out references to them.
+///
+/// GEOS has no bulk deallocation, so each geometry is still destroyed on its own, but all of them
+/// are destroyed in one go at the end of the scope instead of being interleaved with the
+/// computations. The geometries created with the arena's [`context`](#method.context) also share
+/// it, instead of each creating (and finishing) its own context.
+///
+/// # Example
+///
+/// ```
+/// use geos::{Geom, GeomArena, Geometry};
+///
+/// let arena = GeomArena::new().expect("Failed to create GeomArena");
+///
+/// let mut area = 0.;
+/// for wkt in &["POINT (0 0)", "POINT (10 10)"] {
+///     let point = arena.alloc(Geometry::new_from_wkt_with_context(wkt, arena.context())
+///                                      .expect("Invalid geometry"));
+///     let buffer = arena.alloc(point.buffer(1., 8).expect("buffer failed"));
+///     let envelope = arena.alloc(buffer.envelope().expect("envelope failed"));
+///     area += envelope.area().expect("area failed");
+/// }
+/// assert_eq!(area, 8.);
+/// assert_eq!(arena.len(), 6);
+/// ```
+pub struct GeomArena<'a> {
+    context: Arc<ContextHandle<'a>>,
+    geoms: Arena<Geometry<'a>>,
+}
+
+impl<'a> GeomArena<'a> {
+    /// Creates an empty `GeomArena`.
+    pub fn new() -> GResult<GeomArena<'a>> {
+        Ok(GeomArena {
+            context: ContextHandle::init_default("GeomArena::new")?,
+            geoms: Arena::new(),
+        })
+    }
+
+    /// Returns the context shared by the arena, to be given to the `*_with_context`
+    /// constructors.
+    pub fn context(&self) -> Arc<ContextHandle<'a>> {
+        Arc::clone(&self.context)
+    }
+
+    /// Moves `geom` into the arena and returns a reference to it, valid as long as the arena.
+    pub fn alloc(&self, geom: Geometry<'a>) -> &Geometry<'a> {
+        self.geoms.alloc(geom)
+    }
+
+    /// Returns the number of geometries held by the arena.
+    pub fn len(&self) -> usize {
+        self.geoms.len()
+    }
+
+    /// Returns `true` if the arena holds no geometry.
+    pub fn is_empty(&self) -> bool {
+        self.geoms.len() == 0
+    }
+
+    /// Drops all the geometries held by the arena, so it can be reused.
+    pub fn clear(&mut self) {
+        self.geoms.clear();
+    }
+}
+
+/// The storage of `GeomArena`, generic so that it can be tested without GEOS (under Miri).
+///
+/// The values are leaked boxes: a raw pointer doesn't assert unique access like a `Box` does,
+/// so the references handed out stay valid while the vector holding the pointers grows.
+struct Arena<T> {
+    values: RefCell<Vec<*mut T>>,
+}
+
+// The arena owns its values, like a `Vec<Box<T>>` would.
+unsafe impl<T: Send> Send for Arena<T> {}
+
+impl<T> Arena<T> {
+    fn new() -> Arena<T> {
+        Arena {
+            values: RefCell::new(Vec::new()),
+        }
+    }
+
+    fn alloc(&self, value: T) -> &T {
+        let ptr = Box::into_raw(Box::new(value));
+        self.values.borrow_mut().push(ptr);
+        // The value is only freed by `clear` (which needs `&mut self`) or when the arena itself
+        // is dropped, so the reference can live as long as `&self`.
+        unsafe { &*ptr }
+    }
+
+    fn len(&self) -> usize {
+        self.values.borrow().len()
+    }
+
+    fn clear(&mut self) {
+        for ptr in self.values.get_mut().drain(..) {
+            drop(unsafe { Box::from_raw(ptr) });
+        }
+    }
+}
+
+impl<T> Drop for Arena<T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Arena;
+
+    // Doesn't call GEOS, so it can be run with `cargo miri test arena`.
+    #[test]
+    fn arena_references_stay_valid() {
+        let mut arena = Arena::new();
+        let first = arena.alloc(String::from("first"));
+        let others = (0..100)
+            .map(|i| arena.alloc(i.to_string()))
+            .collect::<Vec<_>>();
+        assert_eq!(first, "first");
+        assert_eq!(others[99], "99");
+        assert_eq!(arena.len(), 101);
+        arena.clear();
+        assert_eq!(arena.len(), 0);
+        assert_eq!(arena.alloc(String::from("again")), "again");
+    }
+}
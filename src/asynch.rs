@@ -0,0 +1,240 @@
+//! Helpers to run GEOS operations from async code without blocking the executor.
+//!
+//! GEOS calls are synchronous and some of them (unions and buffers of large geometries, for
+//! example) can take seconds. [`run`](fn.run.html) moves such an operation onto a pool of
+//! threads dedicated to blocking work and returns a future resolving to its result, which works
+//! with any executor. [`Blocking`](struct.Blocking.html) wraps a value (typically a
+//! `Geometry<'static>`, see [`Geometry::into_static`]) so that it can only be used through
+//! `run`, which prevents calling a long operation on it from the executor by mistake.
+//!
+//! [`Geometry::into_static`]: ../struct.Geometry.html#method.into_static
+//!
+//! Available using the `asynch` feature.
+
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::task::{Context, Poll, Waker};
+use std::thread;
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// Returns the sender of the jobs to the pool, starting its threads (one per available core) on
+/// the first call.
+fn pool() -> &'static Mutex<Sender<Job>> {
+    static POOL: OnceLock<Mutex<Sender<Job>>> = OnceLock::new();
+    POOL.get_or_init(|| {
+        let (sender, receiver) = mpsc::channel::<Job>();
+        let receiver = Arc::new(Mutex::new(receiver));
+        let nb_threads = thread::available_parallelism().map_or(4, |n| n.get());
+        for i in 0..nb_threads {
+            let receiver: Arc<Mutex<Receiver<Job>>> = Arc::clone(&receiver);
+            thread::Builder::new()
+                .name(format!("geos-blocking-{}", i))
+                .spawn(move || loop {
+                    // The lock is only held while waiting for a job, not while running it.
+                    let job = match receiver.lock() {
+                        Ok(receiver) => receiver.recv(),
+                        Err(_) => return,
+                    };
+                    match job {
+                        // The operations' panics are caught by `run`, but the waker can panic
+                        // too, and the thread wouldn't be replaced.
+                        Ok(job) => {
+                            let _ = panic::catch_unwind(AssertUnwindSafe(job));
+                        }
+                        Err(_) => return,
+                    }
+                })
+                .expect("failed to spawn a GEOS blocking thread");
+        }
+        Mutex::new(sender)
+    })
+}
+
+struct State<T> {
+    result: Option<thread::Result<T>>,
+    waker: Option<Waker>,
+}
+
+/// The future of an operation run by [`run`](fn.run.html).
+///
+/// If the operation panics, the panic is resumed when polling the future.
+pub struct Task<T> {
+    state: Arc<Mutex<State<T>>>,
+}
+
+impl<T> Future for Task<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        match state.result.take() {
+            Some(Ok(result)) => Poll::Ready(result),
+            Some(Err(payload)) => panic::resume_unwind(payload),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// Runs `op` on the pool of blocking threads of the crate, and returns a future resolving to
+/// its result.
+///
+/// Geometries are `Send`, so they can be moved into `op` once made `'static` with
+/// [`Geometry::into_static`](../struct.Geometry.html#method.into_static).
+///
+/// # Example
+///
+/// ```
+/// use geos::{Geom, Geometry};
+/// use geos::asynch::run;
+/// # use std::future::Future;
+/// # use std::sync::Arc;
+/// # use std::task::{Context, Poll, Wake};
+/// #
+/// # fn block_on<F: Future>(future: F) -> F::Output {
+/// #     struct Unpark(std::thread::Thread);
+/// #     impl Wake for Unpark {
+/// #         fn wake(self: Arc<Self>) {
+/// #             self.0.unpark();
+/// #         }
+/// #     }
+/// #     let waker = Arc::new(Unpark(std::thread::current())).into();
+/// #     let mut cx = Context::from_waker(&waker);
+/// #     let mut future = Box::pin(future);
+/// #     loop {
+/// #         match future.as_mut().poll(&mut cx) {
+/// #             Poll::Ready(output) => return output,
+/// #             Poll::Pending => std::thread::park(),
+/// #         }
+/// #     }
+/// # }
+///
+/// let geom = Geometry::new_from_wkt("POINT(0 0)").expect("Invalid geometry");
+/// // Typically `.await`ed in an async function.
+/// let area = block_on(run(move || geom.buffer(10., 8).and_then(|b| b.area())))
+///     .expect("buffer failed");
+/// assert!(area > 300.);
+/// ```
+pub fn run<T, F>(op: F) -> Task<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    let state = Arc::new(Mutex::new(State {
+        result: None,
+        waker: None,
+    }));
+    let task_state = Arc::clone(&state);
+    let job: Job = Box::new(move || {
+        let result = panic::catch_unwind(AssertUnwindSafe(op));
+        let waker = {
+            let mut state = task_state.lock().unwrap_or_else(|e| e.into_inner());
+            state.result = Some(result);
+            state.waker.take()
+        };
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    });
+    pool()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .send(job)
+        .expect("the GEOS blocking threads are gone");
+    Task { state }
+}
+
+/// A value which can only be used on the blocking threads, through [`run`](#method.run).
+///
+/// It's cheap to clone (the value is shared), so a `Blocking<Geometry<'static>>` can be kept in
+/// the state of an async service and used by all its requests.
+///
+/// A GEOS context must not be used by two threads at once, so the operations run on the same
+/// value (or its clones) are serialized. Wrap a copy of the value per worker to run them in
+/// parallel.
+///
+/// # Example
+///
+/// ```
+/// use geos::{Geom, Geometry};
+/// use geos::asynch::Blocking;
+/// # use std::future::Future;
+/// # use std::sync::Arc;
+/// # use std::task::{Context, Poll, Wake};
+/// #
+/// # fn block_on<F: Future>(future: F) -> F::Output {
+/// #     struct Unpark(std::thread::Thread);
+/// #     impl Wake for Unpark {
+/// #         fn wake(self: Arc<Self>) {
+/// #             self.0.unpark();
+/// #         }
+/// #     }
+/// #     let waker = Arc::new(Unpark(std::thread::current())).into();
+/// #     let mut cx = Context::from_waker(&waker);
+/// #     let mut future = Box::pin(future);
+/// #     loop {
+/// #         match future.as_mut().poll(&mut cx) {
+/// #             Poll::Ready(output) => return output,
+/// #             Poll::Pending => std::thread::park(),
+/// #         }
+/// #     }
+/// # }
+///
+/// let zones = Geometry::new_from_wkt("POLYGON((0 0, 10 0, 10 10, 0 10, 0 0))")
+///                      .expect("Invalid geometry");
+/// let zones = Blocking::new(zones);
+///
+/// let covered = block_on(zones.run(|zones| {
+///     let point = Geometry::new_from_wkt("POINT(5 5)")?;
+///     zones.contains(&point)
+/// }));
+/// assert_eq!(covered, Ok(true));
+/// ```
+pub struct Blocking<T> {
+    value: Arc<Mutex<T>>,
+}
+
+impl<T> Clone for Blocking<T> {
+    fn clone(&self) -> Self {
+        Blocking {
+            value: Arc::clone(&self.value),
+        }
+    }
+}
+
+impl<T: Send + 'static> Blocking<T> {
+    /// Wraps `value`.
+    pub fn new(value: T) -> Blocking<T> {
+        Blocking {
+            value: Arc::new(Mutex::new(value)),
+        }
+    }
+
+    /// Runs `op` with the value on the blocking threads (see [`run`](fn.run.html)).
+    pub fn run<R, F>(&self, op: F) -> Task<R>
+    where
+        R: Send + 'static,
+        F: FnOnce(&T) -> R + Send + 'static,
+    {
+        let value = Arc::clone(&self.value);
+        run(move || {
+            // A panicking operation can't leave a GEOS value half modified, since `op` only gets
+            // a shared reference.
+            let value = value.lock().unwrap_or_else(|e| e.into_inner());
+            op(&value)
+        })
+    }
+
+    /// Returns the value, or `self` if it's still shared with clones or running operations.
+    pub fn into_inner(self) -> Result<T, Blocking<T>> {
+        Arc::try_unwrap(self.value)
+            .map(|value| value.into_inner().unwrap_or_else(|e| e.into_inner()))
+            .map_err(|value| Blocking { value })
+    }
+}
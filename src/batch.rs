@@ -0,0 +1,96 @@
+//! Batch operations which don't stop at the first failing geometry.
+
+use crate::{ContextHandle, GResult, Geom, Geometry, GeometryTypes};
+use error::Error;
+use std::sync::Arc;
+
+/// What to do with the geometries for which the operation failed. In both cases, the error is
+/// reported in [`BatchResult::errors`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorPolicy {
+    /// The geometry is left out of the results.
+    Skip,
+    /// The geometry is replaced by an empty `GeometryCollection` in the results, so they stay
+    /// aligned with the inputs (unless creating the empty geometry fails too).
+    Empty,
+}
+
+/// Output of [`try_map`].
+pub struct BatchResult<'a> {
+    /// The `(index in the input, result)` pairs, in the input order.
+    pub geometries: Vec<(usize, Geometry<'a>)>,
+    /// The `(index in the input, error)` pairs of the failed operations, in the input order.
+    pub errors: Vec<(usize, Error)>,
+}
+
+impl<'a> BatchResult<'a> {
+    /// Returns the resulting geometries, without their index.
+    pub fn into_geometries(self) -> Vec<Geometry<'a>> {
+        self.geometries.into_iter().map(|(_, g)| g).collect()
+    }
+}
+
+/// Applies `f` to each geometry of `geoms`, collecting the results and the errors instead of
+/// stopping at the first error.
+///
+/// # Example
+///
+/// ```
+/// use geos::{Geom, Geometry};
+/// use geos::batch::{try_map, ErrorPolicy};
+///
+/// let geoms = vec![
+///     Geometry::new_from_wkt("LINESTRING(0 0, 1 1)").expect("Invalid geometry"),
+///     Geometry::new_from_wkt("POINT(0 0)").expect("Invalid geometry"),
+///     Geometry::new_from_wkt("LINESTRING(0 0, 2 0)").expect("Invalid geometry"),
+/// ];
+///
+/// // `get_start_point` fails on the point.
+/// let res = try_map(&geoms, ErrorPolicy::Skip, |g| g.get_start_point());
+/// assert_eq!(res.geometries.iter().map(|(i, _)| *i).collect::<Vec<_>>(), vec![0, 2]);
+/// assert_eq!(res.errors.len(), 1);
+/// assert_eq!(res.errors[0].0, 1);
+///
+/// let res = try_map(&geoms, ErrorPolicy::Empty, |g| g.get_start_point());
+/// let starts = res.into_geometries();
+/// assert_eq!(starts.len(), 3);
+/// assert_eq!(starts[1].is_empty(), Ok(true));
+/// ```
+pub fn try_map<'a, 'b, G, F>(geoms: &[G], policy: ErrorPolicy, mut f: F) -> BatchResult<'b>
+where
+    G: Geom<'a>,
+    F: FnMut(&G) -> GResult<Geometry<'b>>,
+{
+    let mut res = BatchResult {
+        geometries: Vec::with_capacity(geoms.len()),
+        errors: Vec::new(),
+    };
+    // Shared by all the substituted empty geometries.
+    let mut context: Option<Arc<ContextHandle<'b>>> = None;
+    for (i, geom) in geoms.iter().enumerate() {
+        match f(geom) {
+            Ok(geom) => res.geometries.push((i, geom)),
+            Err(e) => {
+                res.errors.push((i, e));
+                if policy == ErrorPolicy::Empty {
+                    if let Ok(empty) = create_empty(&mut context) {
+                        res.geometries.push((i, empty));
+                    }
+                }
+            }
+        }
+    }
+    res
+}
+
+fn create_empty<'b>(context: &mut Option<Arc<ContextHandle<'b>>>) -> GResult<Geometry<'b>> {
+    let context = match context {
+        Some(context) => Arc::clone(context),
+        None => {
+            let new_context = ContextHandle::init_default("batch::try_map")?;
+            *context = Some(Arc::clone(&new_context));
+            new_context
+        }
+    };
+    Geometry::create_empty_collection_with_context(GeometryTypes::GeometryCollection, context)
+}
@@ -0,0 +1,225 @@
+//! Helpers to work with `(xmin, ymin, xmax, ymax)` bounding boxes.
+
+use crate::{CoordSeq, GResult, Geom, Geometry};
+use functions::{create_rectangle, get_bounds};
+
+/// A `(xmin, ymin, xmax, ymax)` bounding box.
+pub type BBox = (f64, f64, f64, f64);
+
+/// Returns the smallest bounding box containing all the given ones, or `None` if `boxes` is
+/// empty.
+///
+/// # Example
+///
+/// ```
+/// use geos::bbox;
+///
+/// assert_eq!(bbox::union(&[(0., 0., 1., 1.), (-1., 0.5, 0.5, 3.)]), Some((-1., 0., 1., 3.)));
+/// assert_eq!(bbox::union(&[]), None);
+/// ```
+pub fn union(boxes: &[BBox]) -> Option<BBox> {
+    let mut iter = boxes.iter();
+    let first = *iter.next()?;
+    Some(iter.fold(first, |acc, b| {
+        (
+            acc.0.min(b.0),
+            acc.1.min(b.1),
+            acc.2.max(b.2),
+            acc.3.max(b.3),
+        )
+    }))
+}
+
+/// Grows `bbox` by `margin` on every side (or shrinks it if `margin` is negative). Returns
+/// `None` if the result is collapsed.
+///
+/// # Example
+///
+/// ```
+/// use geos::bbox;
+///
+/// assert_eq!(bbox::expand((0., 0., 4., 2.), 1.), Some((-1., -1., 5., 3.)));
+/// assert_eq!(bbox::expand((0., 0., 4., 2.), -1.), Some((1., 1., 3., 1.)));
+/// assert_eq!(bbox::expand((0., 0., 4., 2.), -2.), None);
+/// ```
+pub fn expand(bbox: BBox, margin: f64) -> Option<BBox> {
+    let res = (
+        bbox.0 - margin,
+        bbox.1 - margin,
+        bbox.2 + margin,
+        bbox.3 + margin,
+    );
+    if res.0 <= res.2 && res.1 <= res.3 {
+        Some(res)
+    } else {
+        None
+    }
+}
+
+/// Returns `true` if the two bounding boxes share at least one point.
+///
+/// # Example
+///
+/// ```
+/// use geos::bbox;
+///
+/// assert!(bbox::intersects((0., 0., 1., 1.), (1., 1., 2., 2.)));
+/// assert!(!bbox::intersects((0., 0., 1., 1.), (1.5, 0., 2., 1.)));
+/// ```
+pub fn intersects(a: BBox, b: BBox) -> bool {
+    a.0 <= b.2 && b.0 <= a.2 && a.1 <= b.3 && b.1 <= a.3
+}
+
+/// An axis-aligned rectangle, handled in pure Rust.
+///
+/// It's much cheaper than a GEOS geometry when filtering on envelopes only; use
+/// [`from_geom`](#method.from_geom) and [`to_geometry`](#method.to_geometry) to go back and
+/// forth.
+///
+/// # Example
+///
+/// ```
+/// use geos::{Geom, Geometry};
+/// use geos::bbox::Rect;
+///
+/// let geom = Geometry::new_from_wkt("LINESTRING(0 0, 4 2)").expect("Invalid geometry");
+/// let rect = Rect::from_geom(&geom).expect("from_geom failed").expect("empty geometry");
+/// assert_eq!(rect, Rect::new(0., 0., 4., 2.));
+///
+/// assert!(rect.intersects(&Rect::new(3., 1., 5., 5.)));
+/// assert!(rect.contains(&Rect::new(1., 1., 2., 2.)));
+/// assert!(!rect.contains_point(5., 1.));
+///
+/// let polygon = rect.to_geometry().expect("to_geometry failed");
+/// assert_eq!(polygon.to_wkt_precision(0).unwrap(), "POLYGON ((0 0, 4 0, 4 2, 0 2, 0 0))");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rect {
+    pub xmin: f64,
+    pub ymin: f64,
+    pub xmax: f64,
+    pub ymax: f64,
+}
+
+impl Rect {
+    /// Creates a `Rect` from its bounds, swapping them if they are given in the wrong order.
+    pub fn new(xmin: f64, ymin: f64, xmax: f64, ymax: f64) -> Rect {
+        Rect {
+            xmin: xmin.min(xmax),
+            ymin: ymin.min(ymax),
+            xmax: xmin.max(xmax),
+            ymax: ymin.max(ymax),
+        }
+    }
+
+    /// Returns the envelope of `geom`, or `None` if it is empty.
+    pub fn from_geom<'a, G: Geom<'a>>(geom: &G) -> GResult<Option<Rect>> {
+        Ok(get_bounds(geom)?.map(Rect::from))
+    }
+
+    /// Creates the geometry covering the rectangle, like [`Geom::envelope`] does: a `Point` if
+    /// it's collapsed to a point, a `LineString` if it has no width or no height, a `Polygon`
+    /// otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::Geom;
+    /// use geos::bbox::Rect;
+    ///
+    /// let point = Rect::new(1., 2., 1., 2.).to_geometry().expect("to_geometry failed");
+    /// assert_eq!(point.to_wkt_precision(0).unwrap(), "POINT (1 2)");
+    /// let line = Rect::new(1., 2., 3., 2.).to_geometry().expect("to_geometry failed");
+    /// assert_eq!(line.to_wkt_precision(0).unwrap(), "LINESTRING (1 2, 3 2)");
+    /// ```
+    pub fn to_geometry<'a>(&self) -> GResult<Geometry<'a>> {
+        if self.xmin == self.xmax && self.ymin == self.ymax {
+            Geometry::create_point(CoordSeq::new_from_vec(&[[self.xmin, self.ymin]])?)
+        } else if self.xmin == self.xmax || self.ymin == self.ymax {
+            Geometry::create_line_string(CoordSeq::new_from_vec(&[
+                [self.xmin, self.ymin],
+                [self.xmax, self.ymax],
+            ])?)
+        } else {
+            create_rectangle(self.xmin, self.ymin, self.xmax, self.ymax)
+        }
+    }
+
+    /// Returns the width of the rectangle.
+    pub fn width(&self) -> f64 {
+        self.xmax - self.xmin
+    }
+
+    /// Returns the height of the rectangle.
+    pub fn height(&self) -> f64 {
+        self.ymax - self.ymin
+    }
+
+    /// Returns the area of the rectangle.
+    pub fn area(&self) -> f64 {
+        self.width() * self.height()
+    }
+
+    /// Returns `true` if the two rectangles share at least one point.
+    pub fn intersects(&self, other: &Rect) -> bool {
+        intersects((*self).into(), (*other).into())
+    }
+
+    /// Returns `true` if `other` lies in `self`, boundary included.
+    pub fn contains(&self, other: &Rect) -> bool {
+        self.xmin <= other.xmin
+            && other.xmax <= self.xmax
+            && self.ymin <= other.ymin
+            && other.ymax <= self.ymax
+    }
+
+    /// Returns `true` if the `(x, y)` point lies in the rectangle, boundary included.
+    pub fn contains_point(&self, x: f64, y: f64) -> bool {
+        self.xmin <= x && x <= self.xmax && self.ymin <= y && y <= self.ymax
+    }
+
+    /// Returns the intersection of the two rectangles, or `None` if they don't intersect.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::bbox::Rect;
+    ///
+    /// let a = Rect::new(0., 0., 2., 2.);
+    /// assert_eq!(a.intersection(&Rect::new(1., 1., 3., 3.)), Some(Rect::new(1., 1., 2., 2.)));
+    /// assert_eq!(a.intersection(&Rect::new(5., 5., 6., 6.)), None);
+    /// ```
+    pub fn intersection(&self, other: &Rect) -> Option<Rect> {
+        if !self.intersects(other) {
+            return None;
+        }
+        Some(Rect {
+            xmin: self.xmin.max(other.xmin),
+            ymin: self.ymin.max(other.ymin),
+            xmax: self.xmax.min(other.xmax),
+            ymax: self.ymax.min(other.ymax),
+        })
+    }
+
+    /// Returns the smallest rectangle containing both rectangles.
+    pub fn union(&self, other: &Rect) -> Rect {
+        Rect {
+            xmin: self.xmin.min(other.xmin),
+            ymin: self.ymin.min(other.ymin),
+            xmax: self.xmax.max(other.xmax),
+            ymax: self.ymax.max(other.ymax),
+        }
+    }
+}
+
+impl From<BBox> for Rect {
+    fn from(bbox: BBox) -> Rect {
+        Rect::new(bbox.0, bbox.1, bbox.2, bbox.3)
+    }
+}
+
+impl From<Rect> for BBox {
+    fn from(rect: Rect) -> BBox {
+        (rect.xmin, rect.ymin, rect.xmax, rect.ymax)
+    }
+}
@@ -0,0 +1,205 @@
+//! Clustering of geometries, similar to the `ST_Cluster*` functions of PostGIS.
+
+use crate::{Geom, Geometry, STRtree, SpatialIndex};
+use error::GResult;
+
+/// Returns the indices of the geometries within `eps` of `geoms[i]` (including `i`).
+fn neighbors<'a, G: Geom<'a>>(
+    geoms: &[G],
+    tree: &STRtree<usize>,
+    windows: &[Option<Geometry>],
+    i: usize,
+    eps: f64,
+) -> GResult<Vec<usize>> {
+    let window = match windows[i] {
+        Some(ref window) => window,
+        None => return Ok(Vec::new()),
+    };
+    let mut candidates = Vec::new();
+    tree.query(window, |j: &usize| candidates.push(*j));
+    candidates.sort_unstable();
+
+    let mut res = Vec::with_capacity(candidates.len());
+    for j in candidates {
+        if geoms[i].distance(&geoms[j])? <= eps {
+            res.push(j);
+        }
+    }
+    Ok(res)
+}
+
+/// Clusters `geoms` with the DBSCAN algorithm, like `ST_ClusterDBSCAN` does in PostGIS.
+///
+/// A geometry is a core geometry if at least `min_points` geometries (itself included) are
+/// within `eps` of it. Clusters are made of the core geometries within `eps` of each other and
+/// of the geometries within `eps` of those. Returns the cluster index of each geometry, or
+/// `None` for noise (including empty geometries).
+///
+/// # Example
+///
+/// ```
+/// use geos::Geometry;
+/// use geos::cluster::dbscan;
+///
+/// let points = ["POINT (0 0)", "POINT (0 1)", "POINT (1 0)", "POINT (10 10)", "POINT (10 11)",
+///               "POINT (50 50)"]
+///     .iter()
+///     .map(|wkt| Geometry::new_from_wkt(wkt).expect("Invalid geometry"))
+///     .collect::<Vec<_>>();
+///
+/// assert_eq!(dbscan(&points, 1.5, 2),
+///            Ok(vec![Some(0), Some(0), Some(0), Some(1), Some(1), None]));
+/// ```
+pub fn dbscan<'a, G: Geom<'a>>(
+    geoms: &[G],
+    eps: f64,
+    min_points: usize,
+) -> GResult<Vec<Option<usize>>> {
+    let mut tree = STRtree::with_capacity(10)?;
+    let mut windows = Vec::with_capacity(geoms.len());
+    for (i, geom) in geoms.iter().enumerate() {
+        if geom.is_empty()? {
+            windows.push(None);
+        } else {
+            tree.insert(geom, i);
+            windows.push(Some(geom.envelope_expanded(eps)?));
+        }
+    }
+
+    let mut labels = vec![None; geoms.len()];
+    let mut visited = vec![false; geoms.len()];
+    let mut nb_clusters = 0;
+    for i in 0..geoms.len() {
+        if visited[i] {
+            continue;
+        }
+        visited[i] = true;
+        let mut queue = neighbors(geoms, &tree, &windows, i, eps)?;
+        if queue.is_empty() || queue.len() < min_points {
+            continue;
+        }
+        labels[i] = Some(nb_clusters);
+        while let Some(j) = queue.pop() {
+            if labels[j].is_none() {
+                labels[j] = Some(nb_clusters);
+            }
+            if visited[j] {
+                continue;
+            }
+            visited[j] = true;
+            let j_neighbors = neighbors(geoms, &tree, &windows, j, eps)?;
+            if j_neighbors.len() >= min_points {
+                queue.extend(j_neighbors);
+            }
+        }
+        nb_clusters += 1;
+    }
+    Ok(labels)
+}
+
+/// Connectivity rule used by [`connected_components`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Predicate {
+    /// The geometries intersect (touching is enough).
+    Intersects,
+    /// The geometries are within the given distance of each other.
+    DWithin(f64),
+}
+
+fn find_root(parents: &mut [usize], mut i: usize) -> usize {
+    while parents[i] != i {
+        parents[i] = parents[parents[i]];
+        i = parents[i];
+    }
+    i
+}
+
+/// Groups `geoms` into sets of transitively connected geometries, like `ST_ClusterIntersecting`
+/// (or `ST_ClusterWithin`) does in PostGIS.
+///
+/// Returns the indices of the geometries of each group, sorted, the groups being sorted by their
+/// first index. Empty geometries end up alone in their group.
+///
+/// # Example
+///
+/// ```
+/// use geos::Geometry;
+/// use geos::cluster::{connected_components, Predicate};
+///
+/// let geoms = ["POLYGON((0 0, 1 0, 1 1, 0 1, 0 0))",
+///              "POLYGON((5 5, 6 5, 6 6, 5 6, 5 5))",
+///              // Touches the first one.
+///              "POLYGON((1 0, 2 0, 2 1, 1 1, 1 0))",
+///              "POLYGON((2.5 0, 3 0, 3 1, 2.5 1, 2.5 0))"]
+///     .iter()
+///     .map(|wkt| Geometry::new_from_wkt(wkt).expect("Invalid geometry"))
+///     .collect::<Vec<_>>();
+///
+/// assert_eq!(connected_components(&geoms, Predicate::Intersects),
+///            Ok(vec![vec![0, 2], vec![1], vec![3]]));
+/// assert_eq!(connected_components(&geoms, Predicate::DWithin(0.5)),
+///            Ok(vec![vec![0, 2, 3], vec![1]]));
+/// ```
+pub fn connected_components<'a, G: Geom<'a>>(
+    geoms: &[G],
+    predicate: Predicate,
+) -> GResult<Vec<Vec<usize>>> {
+    let mut tree = STRtree::with_capacity(10)?;
+    for (i, geom) in geoms.iter().enumerate() {
+        if !geom.is_empty()? {
+            tree.insert(geom, i);
+        }
+    }
+
+    let mut parents = (0..geoms.len()).collect::<Vec<_>>();
+    for (i, geom) in geoms.iter().enumerate() {
+        if geom.is_empty()? {
+            continue;
+        }
+        let mut candidates = Vec::new();
+        let mut visitor = |j: &usize| {
+            if *j > i {
+                candidates.push(*j)
+            }
+        };
+        match predicate {
+            Predicate::Intersects => tree.query(geom, &mut visitor),
+            Predicate::DWithin(distance) => {
+                tree.query(&geom.envelope_expanded(distance)?, &mut visitor)
+            }
+        }
+
+        let prepared = match predicate {
+            Predicate::Intersects => Some(geom.to_prepared_geom()?),
+            Predicate::DWithin(_) => None,
+        };
+        for j in candidates {
+            let (root_i, root_j) = (find_root(&mut parents, i), find_root(&mut parents, j));
+            if root_i == root_j {
+                continue;
+            }
+            let connected = match (predicate, &prepared) {
+                (Predicate::DWithin(distance), _) => geom.distance(&geoms[j])? <= distance,
+                (_, Some(prepared)) => prepared.intersects(&geoms[j])?,
+                (_, None) => geom.intersects(&geoms[j])?,
+            };
+            if connected {
+                parents[root_j.max(root_i)] = root_j.min(root_i);
+            }
+        }
+    }
+
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    let mut group_of_root: Vec<Option<usize>> = vec![None; geoms.len()];
+    for i in 0..geoms.len() {
+        let root = find_root(&mut parents, i);
+        match group_of_root[root] {
+            Some(group) => groups[group].push(i),
+            None => {
+                group_of_root[root] = Some(groups.len());
+                groups.push(vec![i]);
+            }
+        }
+    }
+    Ok(groups)
+}
@@ -0,0 +1,81 @@
+use crate::{GResult, Geometry, GeometryTypes};
+use error::Error;
+
+/// Incrementally builds a multi geometry or a geometry collection.
+///
+/// The pushed geometries are owned by the builder until [`finish`](#method.finish) hands them
+/// over to the created collection. If it fails or if the builder is dropped before, they are
+/// destroyed like any other [`Geometry`].
+///
+/// # Example
+///
+/// ```
+/// use geos::{CollectionBuilder, Geom, Geometry, GeometryTypes};
+///
+/// let mut builder = CollectionBuilder::new();
+/// for wkt in &["POINT (1 2)", "POINT (3 4)"] {
+///     builder.push(Geometry::new_from_wkt(wkt).expect("Invalid geometry"));
+/// }
+/// let geom = builder.finish(GeometryTypes::MultiPoint).expect("finish failed");
+/// assert_eq!(geom.to_wkt_precision(0).unwrap(), "MULTIPOINT (1 2, 3 4)");
+/// ```
+#[derive(Default)]
+pub struct CollectionBuilder<'a> {
+    geoms: Vec<Geometry<'a>>,
+}
+
+impl<'a> CollectionBuilder<'a> {
+    /// Creates an empty `CollectionBuilder`.
+    pub fn new() -> CollectionBuilder<'a> {
+        CollectionBuilder { geoms: Vec::new() }
+    }
+
+    /// Creates an empty `CollectionBuilder` with room for `capacity` geometries.
+    pub fn with_capacity(capacity: usize) -> CollectionBuilder<'a> {
+        CollectionBuilder {
+            geoms: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Adds a geometry to the collection.
+    pub fn push(&mut self, geom: Geometry<'a>) {
+        self.geoms.push(geom);
+    }
+
+    /// Returns the number of geometries pushed so far.
+    pub fn len(&self) -> usize {
+        self.geoms.len()
+    }
+
+    /// Returns `true` if no geometry has been pushed yet.
+    pub fn is_empty(&self) -> bool {
+        self.geoms.is_empty()
+    }
+
+    /// Creates the collection of the given type from the pushed geometries. `type_` must be one
+    /// of the multi geometry types or `GeometryCollection`, and the pushed geometries must match
+    /// it (only polygons in a `MultiPolygon` for example).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{CollectionBuilder, Geometry, GeometryTypes};
+    ///
+    /// let mut builder = CollectionBuilder::with_capacity(1);
+    /// builder.push(Geometry::new_from_wkt("POINT (1 2)").expect("Invalid geometry"));
+    /// // A point can't be part of a MultiLineString.
+    /// assert!(builder.finish(GeometryTypes::MultiLineString).is_err());
+    /// ```
+    pub fn finish(self, type_: GeometryTypes) -> GResult<Geometry<'a>> {
+        match type_ {
+            GeometryTypes::MultiPoint => Geometry::create_multipoint(self.geoms),
+            GeometryTypes::MultiLineString => Geometry::create_multiline_string(self.geoms),
+            GeometryTypes::MultiPolygon => Geometry::create_multipolygon(self.geoms),
+            GeometryTypes::GeometryCollection => Geometry::create_geometry_collection(self.geoms),
+            _ => Err(Error::GenericError(format!(
+                "{:?} is not a collection type",
+                type_
+            ))),
+        }
+    }
+}
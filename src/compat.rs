@@ -0,0 +1,326 @@
+//! Functions named after their [PostGIS](https://postgis.net/docs/reference.html) equivalents,
+//! to ease porting SQL queries and comparing the results with a PostGIS database.
+//!
+//! They are thin aliases of the [`Geom`] methods, only adapted where PostGIS differs: indexes are
+//! 1-based in [`st_geometryn`], [`st_geometrytype`] returns `ST_`-prefixed names and
+//! [`st_buffer`] uses 8 segments per quarter circle like `ST_Buffer`. GEOS also measures a `0`
+//! distance to an empty geometry, where PostGIS returns `NULL`: [`st_distance`] returns an error
+//! and [`st_dwithin`] returns `false` instead.
+//!
+//! Available using the `compat` feature.
+//!
+//! # Example
+//!
+//! ```
+//! use geos::compat::*;
+//!
+//! let a = st_geomfromtext("POLYGON((0 0, 2 0, 2 2, 0 2, 0 0))").expect("Invalid geometry");
+//! let b = st_geomfromtext("POINT(3 1)").expect("Invalid geometry");
+//!
+//! assert_eq!(st_dwithin(&a, &b, 1.), Ok(true));
+//! assert_eq!(st_area(&st_intersection(&a, &st_buffer(&b, 1.).unwrap()).unwrap()), Ok(0.));
+//! assert_eq!(st_geometrytype(&a), Ok("ST_Polygon".to_owned()));
+//! ```
+
+use crate::{ConstGeometry, Geom, Geometry};
+use error::{Error, GResult};
+
+/// `ST_GeomFromText`: see [`Geometry::new_from_wkt`].
+pub fn st_geomfromtext<'a>(wkt: &str) -> GResult<Geometry<'a>> {
+    Geometry::new_from_wkt(wkt)
+}
+
+/// `ST_AsText`: see [`Geom::to_wkt`].
+pub fn st_astext<'a, G: Geom<'a>>(geom: &G) -> GResult<String> {
+    geom.to_wkt()
+}
+
+/// `ST_GeometryType`: the type name prefixed by `ST_`, like `ST_MultiPolygon`.
+pub fn st_geometrytype<'a, G: Geom<'a>>(geom: &G) -> GResult<String> {
+    Ok(format!("ST_{}", geom.get_type()?))
+}
+
+/// `ST_SRID`: see [`Geom::get_srid`].
+pub fn st_srid<'a, G: Geom<'a>>(geom: &G) -> GResult<usize> {
+    geom.get_srid()
+}
+
+/// `ST_SetSRID`: returns a copy of `geom` with the given SRID.
+pub fn st_setsrid<'a, G: Geom<'a>>(geom: &G, srid: usize) -> Geometry<'a> {
+    let mut geom = Geom::clone(geom);
+    geom.set_srid(srid);
+    geom
+}
+
+/// `ST_X`: see [`Geom::get_x`].
+pub fn st_x<'a, G: Geom<'a>>(geom: &G) -> GResult<f64> {
+    geom.get_x()
+}
+
+/// `ST_Y`: see [`Geom::get_y`].
+pub fn st_y<'a, G: Geom<'a>>(geom: &G) -> GResult<f64> {
+    geom.get_y()
+}
+
+/// `ST_IsEmpty`: see [`Geom::is_empty`].
+pub fn st_isempty<'a, G: Geom<'a>>(geom: &G) -> GResult<bool> {
+    geom.is_empty()
+}
+
+/// `ST_IsValid`: see [`Geom::is_valid`].
+pub fn st_isvalid<'a, G: Geom<'a>>(geom: &G) -> bool {
+    geom.is_valid()
+}
+
+/// `ST_IsSimple`: see [`Geom::is_simple`].
+pub fn st_issimple<'a, G: Geom<'a>>(geom: &G) -> GResult<bool> {
+    geom.is_simple()
+}
+
+/// `ST_NPoints`: see [`Geom::get_num_coordinates`].
+pub fn st_npoints<'a, G: Geom<'a>>(geom: &G) -> GResult<usize> {
+    geom.get_num_coordinates()
+}
+
+/// `ST_NumGeometries`: see [`Geom::get_num_geometries`].
+pub fn st_numgeometries<'a, G: Geom<'a>>(geom: &G) -> GResult<usize> {
+    geom.get_num_geometries()
+}
+
+/// `ST_GeometryN`: the `n`-th geometry, starting from 1.
+pub fn st_geometryn<'a, 'c, G: Geom<'a>>(geom: &'c G, n: usize) -> GResult<ConstGeometry<'a, 'c>> {
+    match n.checked_sub(1) {
+        Some(index) => geom.get_geometry_n(index),
+        None => Err(Error::InvalidInput(
+            "ST_GeometryN indexes start from 1".to_owned(),
+        )),
+    }
+}
+
+/// `ST_Area`: see [`Geom::area`].
+pub fn st_area<'a, G: Geom<'a>>(geom: &G) -> GResult<f64> {
+    geom.area()
+}
+
+/// `ST_Length`: see [`Geom::length`].
+pub fn st_length<'a, G: Geom<'a>>(geom: &G) -> GResult<f64> {
+    geom.length()
+}
+
+/// `ST_Distance`: see [`Geom::distance`]. `ST_Distance` returns `NULL` when `a` or `b` is
+/// empty, so [`Error::EmptyGeometry`] is returned in that case.
+pub fn st_distance<'a, 'b, G1: Geom<'a>, G2: Geom<'b>>(a: &G1, b: &G2) -> GResult<f64> {
+    if a.is_empty()? || b.is_empty()? {
+        return Err(Error::EmptyGeometry("ST_Distance".to_owned()));
+    }
+    a.distance(b)
+}
+
+/// `ST_DWithin`: whether the distance between `a` and `b` is at most `distance`, `false` if `a`
+/// or `b` is empty.
+pub fn st_dwithin<'a, 'b, G1: Geom<'a>, G2: Geom<'b>>(
+    a: &G1,
+    b: &G2,
+    distance: f64,
+) -> GResult<bool> {
+    if a.is_empty()? || b.is_empty()? {
+        return Ok(false);
+    }
+    Ok(a.distance(b)? <= distance)
+}
+
+/// `ST_HausdorffDistance`: see [`Geom::hausdorff_distance`].
+pub fn st_hausdorffdistance<'a, 'b, G1: Geom<'a>, G2: Geom<'b>>(a: &G1, b: &G2) -> GResult<f64> {
+    a.hausdorff_distance(b)
+}
+
+/// `ST_Intersects`: see [`Geom::intersects`].
+pub fn st_intersects<'a, 'b, G1: Geom<'a>, G2: Geom<'b>>(a: &G1, b: &G2) -> GResult<bool> {
+    a.intersects(b)
+}
+
+/// `ST_Disjoint`: see [`Geom::disjoint`].
+pub fn st_disjoint<'a, 'b, G1: Geom<'a>, G2: Geom<'b>>(a: &G1, b: &G2) -> GResult<bool> {
+    a.disjoint(b)
+}
+
+/// `ST_Contains`: see [`Geom::contains`].
+pub fn st_contains<'a, 'b, G1: Geom<'a>, G2: Geom<'b>>(a: &G1, b: &G2) -> GResult<bool> {
+    a.contains(b)
+}
+
+/// `ST_Within`: see [`Geom::within`].
+pub fn st_within<'a, 'b, G1: Geom<'a>, G2: Geom<'b>>(a: &G1, b: &G2) -> GResult<bool> {
+    a.within(b)
+}
+
+/// `ST_Covers`: see [`Geom::covers`].
+pub fn st_covers<'a, 'b, G1: Geom<'a>, G2: Geom<'b>>(a: &G1, b: &G2) -> GResult<bool> {
+    a.covers(b)
+}
+
+/// `ST_CoveredBy`: see [`Geom::covered_by`].
+pub fn st_coveredby<'a, 'b, G1: Geom<'a>, G2: Geom<'b>>(a: &G1, b: &G2) -> GResult<bool> {
+    a.covered_by(b)
+}
+
+/// `ST_Touches`: see [`Geom::touches`].
+pub fn st_touches<'a, 'b, G1: Geom<'a>, G2: Geom<'b>>(a: &G1, b: &G2) -> GResult<bool> {
+    a.touches(b)
+}
+
+/// `ST_Crosses`: see [`Geom::crosses`].
+pub fn st_crosses<'a, 'b, G1: Geom<'a>, G2: Geom<'b>>(a: &G1, b: &G2) -> GResult<bool> {
+    a.crosses(b)
+}
+
+/// `ST_Overlaps`: see [`Geom::overlaps`].
+pub fn st_overlaps<'a, 'b, G1: Geom<'a>, G2: Geom<'b>>(a: &G1, b: &G2) -> GResult<bool> {
+    a.overlaps(b)
+}
+
+/// `ST_Equals`: see [`Geom::equals`].
+pub fn st_equals<'a, 'b, G1: Geom<'a>, G2: Geom<'b>>(a: &G1, b: &G2) -> GResult<bool> {
+    a.equals(b)
+}
+
+/// `ST_Intersection`: see [`Geom::intersection`].
+pub fn st_intersection<'a, 'b, G1: Geom<'a>, G2: Geom<'b>>(
+    a: &G1,
+    b: &G2,
+) -> GResult<Geometry<'a>> {
+    a.intersection(b)
+}
+
+/// `ST_Union`: see [`Geom::union`].
+pub fn st_union<'a, 'b, G1: Geom<'a>, G2: Geom<'b>>(a: &G1, b: &G2) -> GResult<Geometry<'a>> {
+    a.union(b)
+}
+
+/// `ST_Difference`: see [`Geom::difference`].
+pub fn st_difference<'a, 'b, G1: Geom<'a>, G2: Geom<'b>>(a: &G1, b: &G2) -> GResult<Geometry<'a>> {
+    a.difference(b)
+}
+
+/// `ST_SymDifference`: see [`Geom::sym_difference`].
+pub fn st_symdifference<'a, 'b, G1: Geom<'a>, G2: Geom<'b>>(
+    a: &G1,
+    b: &G2,
+) -> GResult<Geometry<'a>> {
+    a.sym_difference(b)
+}
+
+/// `ST_UnaryUnion`: see [`Geom::unary_union`].
+pub fn st_unaryunion<'a, G: Geom<'a>>(geom: &G) -> GResult<Geometry<'a>> {
+    geom.unary_union()
+}
+
+/// `ST_Buffer` (without style parameters): see [`Geom::buffer`].
+pub fn st_buffer<'a, G: Geom<'a>>(geom: &G, radius: f64) -> GResult<Geometry<'a>> {
+    geom.buffer(radius, 8)
+}
+
+/// `ST_Envelope`: see [`Geom::envelope`].
+pub fn st_envelope<'a, G: Geom<'a>>(geom: &G) -> GResult<Geometry<'a>> {
+    geom.envelope()
+}
+
+/// `ST_Centroid`: see [`Geom::get_centroid`].
+pub fn st_centroid<'a, G: Geom<'a>>(geom: &G) -> GResult<Geometry<'a>> {
+    geom.get_centroid()
+}
+
+/// `ST_PointOnSurface`: see [`Geom::point_on_surface`].
+pub fn st_pointonsurface<'a, G: Geom<'a>>(geom: &G) -> GResult<Geometry<'a>> {
+    geom.point_on_surface()
+}
+
+/// `ST_ConvexHull`: see [`Geom::convex_hull`].
+pub fn st_convexhull<'a, G: Geom<'a>>(geom: &G) -> GResult<Geometry<'a>> {
+    geom.convex_hull()
+}
+
+/// `ST_Boundary`: see [`Geom::boundary`].
+pub fn st_boundary<'a, G: Geom<'a>>(geom: &G) -> GResult<Geometry<'a>> {
+    geom.boundary()
+}
+
+/// `ST_Simplify`: see [`Geometry::simplify`].
+pub fn st_simplify<'a>(geom: &Geometry<'a>, tolerance: f64) -> GResult<Geometry<'a>> {
+    geom.simplify(tolerance)
+}
+
+/// `ST_SimplifyPreserveTopology`: see [`Geometry::topology_preserve_simplify`].
+pub fn st_simplifypreservetopology<'a>(
+    geom: &Geometry<'a>,
+    tolerance: f64,
+) -> GResult<Geometry<'a>> {
+    geom.topology_preserve_simplify(tolerance)
+}
+
+/// `ST_MakeValid`: see [`Geom::make_valid`].
+///
+/// Available using the `v3_8_0` feature.
+#[cfg(any(feature = "v3_8_0", feature = "dox"))]
+pub fn st_makevalid<'a, G: Geom<'a>>(geom: &G) -> GResult<Geometry<'a>> {
+    geom.make_valid()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // The expected values are the results of the same queries run by PostGIS.
+
+    #[test]
+    fn test_st_geometryn() {
+        let geom = st_geomfromtext("MULTIPOINT((1 2), (3 4))").unwrap();
+        assert_eq!(
+            st_astext(&st_geometryn(&geom, 1).unwrap()).unwrap(),
+            "POINT (1 2)"
+        );
+        assert_eq!(
+            st_astext(&st_geometryn(&geom, 2).unwrap()).unwrap(),
+            "POINT (3 4)"
+        );
+        assert!(st_geometryn(&geom, 0).is_err());
+    }
+
+    #[test]
+    fn test_st_geometrytype() {
+        let types = [
+            ("POINT(0 0)", "ST_Point"),
+            ("LINESTRING(0 0, 1 1)", "ST_LineString"),
+            ("POLYGON((0 0, 1 0, 1 1, 0 0))", "ST_Polygon"),
+            ("MULTIPOLYGON(((0 0, 1 0, 1 1, 0 0)))", "ST_MultiPolygon"),
+            ("GEOMETRYCOLLECTION(POINT(0 0))", "ST_GeometryCollection"),
+        ];
+        for (wkt, expected) in types.iter() {
+            let geom = st_geomfromtext(wkt).unwrap();
+            assert_eq!(st_geometrytype(&geom).unwrap(), *expected);
+        }
+    }
+
+    #[test]
+    fn test_st_buffer() {
+        // ST_NPoints(ST_Buffer('POINT(0 0)', 1)) = 33
+        let point = st_geomfromtext("POINT(0 0)").unwrap();
+        assert_eq!(st_npoints(&st_buffer(&point, 1.).unwrap()), Ok(33));
+    }
+
+    #[test]
+    fn test_empty_distance() {
+        let point = st_geomfromtext("POINT(0 0)").unwrap();
+        let empty = st_geomfromtext("POINT EMPTY").unwrap();
+        // ST_DWithin('POINT(0 0)', 'POINT EMPTY', 0) = false
+        assert_eq!(st_dwithin(&point, &empty, 0.), Ok(false));
+        assert_eq!(st_dwithin(&empty, &point, 10.), Ok(false));
+        assert_eq!(st_dwithin(&point, &point, 0.), Ok(true));
+        // ST_Distance('POINT(0 0)', 'POINT EMPTY') IS NULL
+        assert_eq!(
+            st_distance(&point, &empty),
+            Err(Error::EmptyGeometry("ST_Distance".to_owned()))
+        );
+        assert_eq!(st_distance(&point, &point), Ok(0.));
+    }
+}
@@ -0,0 +1,85 @@
+use error::{Error, GResult};
+use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
+
+static DEFAULT_QUADSEGS: AtomicI32 = AtomicI32::new(8);
+static WKT_TRIM: AtomicBool = AtomicBool::new(true);
+
+/// Process-wide defaults, set with [`configure`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GlobalConfig {
+    /// Number of segments per quarter circle used by the buffers which don't take it as
+    /// parameter (like [`Geom::buffer_mitred`](crate::Geom::buffer_mitred) or
+    /// [`Geom::shrink`](crate::Geom::shrink)). Defaults to 8.
+    pub default_quadsegs: i32,
+    /// Whether [`Geom::to_wkt`](crate::Geom::to_wkt) (and so the `Display` implementation)
+    /// trims the trailing zeros of the coordinates. Defaults to `true`.
+    pub wkt_trim: bool,
+}
+
+impl Default for GlobalConfig {
+    fn default() -> GlobalConfig {
+        GlobalConfig {
+            default_quadsegs: 8,
+            wkt_trim: true,
+        }
+    }
+}
+
+/// Sets the process-wide defaults. It only affects the calls made afterwards.
+///
+/// There is no setting to reuse a context per thread in the constructors which don't take one:
+/// the geometries can be sent to other threads, and a GEOS context must not be used by two
+/// threads at once. To share a context, create it once and pass it to the `*_with_context`
+/// constructors.
+///
+/// # Example
+///
+/// ```
+/// use geos::{configure, global_config, Geom, Geometry, GlobalConfig};
+///
+/// configure(GlobalConfig {
+///     wkt_trim: false,
+///     ..GlobalConfig::default()
+/// }).expect("configure failed");
+/// assert!(!global_config().wkt_trim);
+///
+/// let geom = Geometry::new_from_wkt("POINT (1 2)").expect("Invalid geometry");
+/// assert_eq!(geom.to_wkt().unwrap(), "POINT (1.0000000000000000 2.0000000000000000)");
+///
+/// assert!(configure(GlobalConfig { default_quadsegs: 0, ..GlobalConfig::default() }).is_err());
+/// ```
+pub fn configure(config: GlobalConfig) -> GResult<()> {
+    if config.default_quadsegs <= 0 {
+        return Err(Error::InvalidInput(format!(
+            "default_quadsegs must be positive, got {}",
+            config.default_quadsegs
+        )));
+    }
+    DEFAULT_QUADSEGS.store(config.default_quadsegs, Ordering::Relaxed);
+    WKT_TRIM.store(config.wkt_trim, Ordering::Relaxed);
+    Ok(())
+}
+
+/// Returns the current process-wide defaults.
+///
+/// # Example
+///
+/// ```
+/// use geos::{global_config, GlobalConfig};
+///
+/// assert_eq!(global_config(), GlobalConfig::default());
+/// ```
+pub fn global_config() -> GlobalConfig {
+    GlobalConfig {
+        default_quadsegs: default_quadsegs(),
+        wkt_trim: WKT_TRIM.load(Ordering::Relaxed),
+    }
+}
+
+pub(crate) fn default_quadsegs() -> i32 {
+    DEFAULT_QUADSEGS.load(Ordering::Relaxed)
+}
+
+pub(crate) fn wkt_trim() -> bool {
+    WKT_TRIM.load(Ordering::Relaxed)
+}
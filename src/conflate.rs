@@ -0,0 +1,336 @@
+//! Alignment of a layer of geometries on another one, to fix the small gaps and overlaps left
+//! between datasets digitized separately.
+
+use crate::{CoordSeq, Geom, Geometry, GeometryTypes, STRtree, SpatialIndex};
+use error::{Error, GResult};
+use functions::{coords_from_seq, visit_coord_seqs};
+use std::fmt;
+
+/// How much the vertices of a geometry moved when snapped by [`snap_layer`](fn.snap_layer.html).
+///
+/// The vertices are compared one by one, unless snapping changed their number: the displacement
+/// of a vertex is then approximated by its distance to the closest vertex of the snapped geometry.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct SnapStats {
+    /// Number of the vertices which moved.
+    pub moved_vertices: usize,
+    /// Largest displacement of a vertex.
+    pub max_displacement: f64,
+    /// Mean displacement of the vertices which moved (`0` if none did).
+    pub mean_displacement: f64,
+}
+
+/// Edges found along the boundaries of two adjacent geometries by
+/// [`edge_match`](fn.edge_match.html).
+pub struct EdgeMatch<'a> {
+    /// Index of the geometry in the first layer.
+    pub index_a: usize,
+    /// Index of the geometry in the second layer.
+    pub index_b: usize,
+    /// The part of the edges of the first geometry which is within the tolerance of the edges of
+    /// the second one.
+    pub shared_edge: Geometry<'a>,
+    /// The vertices of the first geometry within the tolerance of the edges of the second one
+    /// (but not on them), with the closest point of those edges, as
+    /// `[[x, y], [target_x, target_y]]`.
+    pub adjustments: Vec<[[f64; 2]; 2]>,
+}
+
+impl<'a> fmt::Debug for EdgeMatch<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // `Geometry` isn't `Debug`, its WKT is used instead.
+        f.debug_struct("EdgeMatch")
+            .field("index_a", &self.index_a)
+            .field("index_b", &self.index_b)
+            .field("shared_edge", &format_args!("{}", self.shared_edge))
+            .field("adjustments", &self.adjustments)
+            .finish()
+    }
+}
+
+fn check_tolerance(tolerance: f64) -> GResult<()> {
+    if tolerance.is_nan() || tolerance < 0. {
+        return Err(Error::InvalidInput(format!(
+            "the tolerance must be positive, got {}",
+            tolerance
+        )));
+    }
+    Ok(())
+}
+
+/// Returns the boundary of polygons, the geometry itself otherwise.
+fn edges<'a, G: Geom<'a>>(geom: &G) -> GResult<Geometry<'a>> {
+    match geom.geometry_type() {
+        GeometryTypes::Polygon | GeometryTypes::MultiPolygon => geom.boundary(),
+        _ => Ok(Geom::clone(geom)),
+    }
+}
+
+fn vertices<'a, G: Geom<'a>>(geom: &G) -> GResult<Vec<[f64; 3]>> {
+    let mut res = Vec::new();
+    visit_coord_seqs(geom, &mut |seq, _| {
+        res.extend(coords_from_seq(seq)?);
+        Ok(())
+    })?;
+    Ok(res)
+}
+
+/// Returns the distance from `point` to the closest of the `sorted` points (ordered by X).
+fn nearest_distance(point: &[f64; 3], sorted: &[[f64; 3]]) -> f64 {
+    let distance = |b: &[f64; 3]| (point[0] - b[0]).hypot(point[1] - b[1]);
+    let start = sorted.partition_point(|b| b[0] < point[0]);
+    let mut best = f64::INFINITY;
+    // Walks away from `point` along X, until the X gap alone exceeds the best distance.
+    for b in sorted[start..].iter() {
+        if b[0] - point[0] > best {
+            break;
+        }
+        best = best.min(distance(b));
+    }
+    for b in sorted[..start].iter().rev() {
+        if point[0] - b[0] > best {
+            break;
+        }
+        best = best.min(distance(b));
+    }
+    best
+}
+
+/// Computes the displacement of each vertex of `before`. Snapping usually keeps the vertices (it
+/// only moves them), so they're compared one by one when their number didn't change. Otherwise
+/// (when snapping merged some vertices), the displacement is approximated by the distance to the
+/// closest vertex of `after`.
+fn snap_stats(before: &[[f64; 3]], after: &[[f64; 3]]) -> SnapStats {
+    let displacements: Vec<f64> = if before.len() == after.len() {
+        before
+            .iter()
+            .zip(after)
+            .map(|(a, b)| (a[0] - b[0]).hypot(a[1] - b[1]))
+            .collect()
+    } else {
+        let mut sorted = after.to_vec();
+        sorted.sort_by(|a, b| a[0].total_cmp(&b[0]));
+        before
+            .iter()
+            .map(|a| nearest_distance(a, &sorted))
+            .collect()
+    };
+    let mut stats = SnapStats::default();
+    let mut total = 0.;
+    for displacement in displacements {
+        if displacement > 0. && displacement.is_finite() {
+            stats.moved_vertices += 1;
+            stats.max_displacement = stats.max_displacement.max(displacement);
+            total += displacement;
+        }
+    }
+    if stats.moved_vertices > 0 {
+        stats.mean_displacement = total / stats.moved_vertices as f64;
+    }
+    stats
+}
+
+/// Snaps every geometry of `targets` to the vertices and segments of the `references` within
+/// `tolerance` (using [`Geom::snap`](../trait.Geom.html#tymethod.snap)), and returns how much
+/// each target moved.
+///
+/// The references close to each target are found with an `STRtree`, so only those are used to
+/// snap it. The SRID and user data of the targets are kept. Snapping with a large tolerance can
+/// make a geometry invalid, so it's best to keep it close to the expected misalignment.
+///
+/// # Example
+///
+/// ```
+/// use geos::{Geom, Geometry};
+/// use geos::conflate::snap_layer;
+///
+/// let references = vec![
+///     Geometry::new_from_wkt("POLYGON((0 0, 10 0, 10 10, 0 10, 0 0))").expect("Invalid geometry"),
+/// ];
+/// let mut targets = vec![
+///     Geometry::new_from_wkt("POLYGON((10.2 0, 20 0, 20 10, 9.9 10, 10.2 0))")
+///         .expect("Invalid geometry"),
+///     Geometry::new_from_wkt("POINT(50 50)").expect("Invalid geometry"),
+/// ];
+///
+/// let stats = snap_layer(&mut targets, &references, 0.5).expect("snap_layer failed");
+/// assert_eq!(targets[0].to_wkt().unwrap(), "POLYGON ((10 0, 20 0, 20 10, 10 10, 10 0))");
+/// assert_eq!(stats[0].moved_vertices, 3);
+/// assert!((stats[0].max_displacement - 0.2).abs() < 1e-9);
+/// assert_eq!(stats[1].moved_vertices, 0);
+/// ```
+pub fn snap_layer<'a, 'b, G: Geom<'b>>(
+    targets: &mut [Geometry<'a>],
+    references: &[G],
+    tolerance: f64,
+) -> GResult<Vec<SnapStats>> {
+    check_tolerance(tolerance)?;
+    let mut tree = STRtree::with_capacity(10)?;
+    for (i, reference) in references.iter().enumerate() {
+        if !reference.is_empty()? {
+            tree.insert(reference, i);
+        }
+    }
+
+    let mut res = Vec::with_capacity(targets.len());
+    for target in targets.iter_mut() {
+        if target.is_empty()? {
+            res.push(SnapStats::default());
+            continue;
+        }
+        let mut candidates = Vec::new();
+        tree.query(&target.envelope_expanded(tolerance)?, |i: &usize| {
+            candidates.push(*i)
+        });
+        if candidates.is_empty() {
+            res.push(SnapStats::default());
+            continue;
+        }
+        candidates.sort_unstable();
+        let nearby = candidates
+            .iter()
+            .map(|&i| Geom::clone(&references[i]))
+            .collect();
+        let nearby = Geometry::create_geometry_collection(nearby)?;
+
+        let mut snapped = target.snap(&nearby, tolerance)?;
+        snapped.set_srid(target.get_srid()?);
+        snapped.set_user_data(target.get_user_data());
+        res.push(snap_stats(&vertices(target)?, &vertices(&snapped)?));
+        *target = snapped;
+    }
+    Ok(res)
+}
+
+/// Finds the corresponding edges of two adjacent layers (like the parcels of two neighbouring
+/// datasets along their common border), and the adjustments which would make them coincide.
+///
+/// The edges of a geometry are the boundary of polygons, or the geometry itself for the other
+/// types. For every pair of geometries whose edges come within `tolerance` of each other, an
+/// [`EdgeMatch`](struct.EdgeMatch.html) is returned, ordered by `index_a` then `index_b`. The
+/// adjustments move the vertices of the first geometry onto the closest points of the edges of
+/// the second one. They aren't applied: [`snap_layer`](fn.snap_layer.html) doesn't give the
+/// same result, since it snaps to the vertices of the references first.
+///
+/// # Example
+///
+/// ```
+/// use geos::{Geom, Geometry};
+/// use geos::conflate::edge_match;
+///
+/// let layer_a = vec![
+///     Geometry::new_from_wkt("POLYGON((0 0, 10 0, 9.8 10, 0 10, 0 0))").expect("Invalid geometry"),
+/// ];
+/// let layer_b = vec![
+///     Geometry::new_from_wkt("POLYGON((10 0, 20 0, 20 10, 10 10, 10 0))").expect("Invalid geometry"),
+///     Geometry::new_from_wkt("POLYGON((30 0, 40 0, 40 10, 30 0))").expect("Invalid geometry"),
+/// ];
+///
+/// let matches = edge_match(&layer_a, &layer_b, 0.5).expect("edge_match failed");
+/// assert_eq!(matches.len(), 1);
+/// assert_eq!((matches[0].index_a, matches[0].index_b), (0, 0));
+/// assert_eq!(matches[0].adjustments, vec![[[9.8, 10.], [10., 10.]]]);
+/// ```
+pub fn edge_match<'a, 'b, G1: Geom<'a>, G2: Geom<'b>>(
+    layer_a: &[G1],
+    layer_b: &[G2],
+    tolerance: f64,
+) -> GResult<Vec<EdgeMatch<'a>>> {
+    check_tolerance(tolerance)?;
+    let mut tree = STRtree::with_capacity(10)?;
+    let mut edges_b = Vec::with_capacity(layer_b.len());
+    // The buffers of the edges of `layer_b`, built once they're needed.
+    let mut buffers_b: Vec<Option<Geometry<'b>>> = (0..layer_b.len()).map(|_| None).collect();
+    for (i, geom) in layer_b.iter().enumerate() {
+        if geom.is_empty()? {
+            edges_b.push(None);
+        } else {
+            tree.insert(geom, i);
+            edges_b.push(Some(edges(geom)?));
+        }
+    }
+
+    let mut res = Vec::new();
+    for (index_a, geom) in layer_a.iter().enumerate() {
+        if geom.is_empty()? {
+            continue;
+        }
+        let mut candidates = Vec::new();
+        tree.query(&geom.envelope_expanded(tolerance)?, |i: &usize| {
+            candidates.push(*i)
+        });
+        if candidates.is_empty() {
+            continue;
+        }
+        candidates.sort_unstable();
+        let edges_a = edges(geom)?;
+        for index_b in candidates {
+            let other = match edges_b[index_b] {
+                Some(ref other) => other,
+                None => continue,
+            };
+            if edges_a.distance(other)? > tolerance {
+                continue;
+            }
+            let buffer = match buffers_b[index_b] {
+                Some(ref buffer) => buffer,
+                ref mut buffer => {
+                    // A zero width buffer would be empty, a tiny one still catches the shared
+                    // edges.
+                    let width = tolerance.max(f64::EPSILON);
+                    buffer.get_or_insert(other.buffer(width, 8)?)
+                }
+            };
+            let shared_edge = edges_a.intersection(buffer)?;
+            if shared_edge.is_empty()? {
+                continue;
+            }
+            let mut adjustments = Vec::new();
+            for vertex in vertices(&edges_a)? {
+                let point =
+                    Geometry::create_point(CoordSeq::new_from_vec(&[[vertex[0], vertex[1]]])?)?;
+                if point.distance(other)? > tolerance {
+                    continue;
+                }
+                let nearest = point.nearest_points(other)?;
+                let target = [nearest.get_x(1)?, nearest.get_y(1)?];
+                if target != [vertex[0], vertex[1]]
+                    && !adjustments
+                        .iter()
+                        .any(|a: &[[f64; 2]; 2]| a[0] == [vertex[0], vertex[1]])
+                {
+                    adjustments.push([[vertex[0], vertex[1]], target]);
+                }
+            }
+            res.push(EdgeMatch {
+                index_a,
+                index_b,
+                shared_edge,
+                adjustments,
+            });
+        }
+    }
+    Ok(res)
+}
+
+#[cfg(test)]
+mod test {
+    use super::snap_stats;
+
+    #[test]
+    fn snap_stats_compares_vertices_in_order() {
+        // The vertices swapped places: each one moved, though the set of vertices is the same.
+        let stats = snap_stats(&[[0., 0., 0.], [1., 0., 0.]], &[[1., 0., 0.], [0., 0., 0.]]);
+        assert_eq!(stats.moved_vertices, 2);
+        assert_eq!(stats.max_displacement, 1.);
+    }
+
+    #[test]
+    fn snap_stats_merged_vertices() {
+        let before = [[0., 0., 0.], [0.1, 0., 0.], [5., 0., 0.], [5., 3., 0.]];
+        let after = [[0., 0., 0.], [5., 0., 0.], [5., 3., 0.]];
+        let stats = snap_stats(&before, &after);
+        assert_eq!(stats.moved_vertices, 1);
+        assert!((stats.max_displacement - 0.1).abs() < 1e-12);
+    }
+}
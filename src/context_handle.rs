@@ -1,12 +1,13 @@
-use enums::{ByteOrder, OutputDimension};
+use enums::{ByteOrder, EmptyCheck, OutputDimension};
 use error::{Error, GResult};
 use geos_sys::*;
 use libc::{c_char, c_void, strlen};
+use memory;
 use std::convert::TryFrom;
 use std::ffi::CStr;
 use std::ops::Deref;
 use std::slice;
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
 
 macro_rules! set_callbacks {
     ($c_func:ident, $kind:ident, $callback_name:ident, $last:ident) => {
@@ -71,6 +72,7 @@ pub(crate) struct InnerContext<'a> {
 pub struct ContextHandle<'a> {
     ptr: PtrWrap<GEOSContextHandle_t>,
     pub(crate) inner: PtrWrap<*mut InnerContext<'a>>,
+    empty_check: EmptyCheck,
 }
 
 impl<'a> ContextHandle<'a> {
@@ -87,6 +89,26 @@ impl<'a> ContextHandle<'a> {
         Self::init_e(None)
     }
 
+    /// Returns a [`ContextHandleBuilder`], to configure the handlers and the WKB output defaults
+    /// of a new `ContextHandle` in one place.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{ByteOrder, ContextHandle, OutputDimension};
+    ///
+    /// let context_handle = ContextHandle::builder()
+    ///     .error_message_handler(Box::new(|s| eprintln!("GEOS error: {}", s)))
+    ///     .wkb_output_dimensions(OutputDimension::TwoD)
+    ///     .wkb_byte_order(ByteOrder::LittleEndian)
+    ///     .build()
+    ///     .expect("invalid init");
+    /// assert_eq!(context_handle.get_wkb_output_dimensions(), Ok(OutputDimension::TwoD));
+    /// ```
+    pub fn builder() -> ContextHandleBuilder<'a> {
+        ContextHandleBuilder::default()
+    }
+
     pub(crate) fn init_e(caller: Option<&str>) -> GResult<Self> {
         let ptr = unsafe { GEOS_init_r() };
         if ptr.is_null() {
@@ -117,13 +139,21 @@ impl<'a> ContextHandle<'a> {
         set_notif(ptr, inner);
         set_error(ptr, inner);
 
+        memory::increment(&memory::CONTEXTS);
         Ok(ContextHandle {
             ptr: PtrWrap(ptr),
             inner: PtrWrap(inner),
+            empty_check: EmptyCheck::Off,
         })
     }
 
+    /// Returns a new context for the constructors which don't take one.
+    pub(crate) fn init_default(caller: &str) -> GResult<Arc<ContextHandle<'a>>> {
+        Ok(Arc::new(Self::init_e(Some(caller))?))
+    }
+
     pub(crate) fn as_raw(&self) -> GEOSContextHandle_t {
+        debug_assert!(!self.ptr.is_null(), "GEOS context handle is null");
         *self.ptr
     }
 
@@ -301,6 +331,149 @@ impl<'a> ContextHandle<'a> {
         ByteOrder::try_from(unsafe { GEOS_setWKBByteOrder_r(self.as_raw(), byte_order.into()) })
             .expect("failed to convert to ByteOrder")
     }
+
+    /// Gets the check of the empty inputs of the operations (see
+    /// [`set_empty_check`](#method.set_empty_check)).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{ContextHandle, EmptyCheck};
+    ///
+    /// let context_handle = ContextHandle::init().expect("invalid init");
+    /// assert_eq!(context_handle.get_empty_check(), EmptyCheck::Off);
+    /// ```
+    pub fn get_empty_check(&self) -> EmptyCheck {
+        self.empty_check
+    }
+
+    /// Sets the check of the empty inputs of the operations using this context.
+    ///
+    /// It's a partial, opt-in check: with [`EmptyCheck::Error`], only these operations return
+    /// [`Error::EmptyGeometry`](crate::Error::EmptyGeometry) when one of their inputs is empty:
+    /// `buffer`, `intersection`, `union`, `difference`, `sym_difference`, `unary_union`,
+    /// `envelope`, `convex_hull`, `boundary`, `get_centroid`, `point_on_surface`, `snap`,
+    /// `delaunay_triangulation`, `minimum_rotated_rectangle`, `interpolate`, `distance`,
+    /// `hausdorff_distance`, `simplify` and `topology_preserve_simplify` (and the `*_prec`
+    /// overlays with the `v3_9_0` feature), along with the operations built on them. The other
+    /// ones (like the predicates, `project` or `interpolate_normalized`) aren't affected. The
+    /// check of the context of `self` is the one used, whatever the context of the other
+    /// geometry.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{ContextHandle, EmptyCheck, Error, Geom, Geometry};
+    /// use std::sync::Arc;
+    ///
+    /// let mut context_handle = ContextHandle::init().expect("invalid init");
+    /// context_handle.set_empty_check(EmptyCheck::Error);
+    /// let context_handle = Arc::new(context_handle);
+    ///
+    /// let empty = Geometry::new_from_wkt_with_context("POLYGON EMPTY", Arc::clone(&context_handle))
+    ///                      .expect("Invalid geometry");
+    /// assert_eq!(empty.buffer(1., 8).err(),
+    ///            Some(Error::EmptyGeometry("buffer".to_owned())));
+    ///
+    /// let square = Geometry::new_from_wkt_with_context("POLYGON((0 0, 1 0, 1 1, 0 1, 0 0))",
+    ///                                                  context_handle)
+    ///                       .expect("Invalid geometry");
+    /// assert!(square.intersection(&empty).is_err());
+    /// assert!(square.buffer(1., 8).is_ok());
+    /// ```
+    pub fn set_empty_check(&mut self, check: EmptyCheck) {
+        self.empty_check = check;
+    }
+}
+
+type MessageHandler<'a> = Box<dyn Fn(&str) + Send + Sync + 'a>;
+
+/// Builder for [`ContextHandle`], created with [`ContextHandle::builder`].
+///
+/// The built handle can be wrapped in an `Arc` and given to the `*_with_context` constructors
+/// (like [`Geometry::new_from_wkt_with_context`](crate::Geometry::new_from_wkt_with_context) or
+/// [`WKBReader::new_with_context`](crate::WKBReader::new_with_context)).
+///
+/// The interrupt callback and the precision model can't be configured here: GEOS only provides a
+/// process-wide interrupt callback, and a precision model is applied per geometry (see
+/// [`Geom::set_precision`](crate::Geom::set_precision)).
+#[derive(Default)]
+pub struct ContextHandleBuilder<'a> {
+    notice_message_handler: Option<MessageHandler<'a>>,
+    error_message_handler: Option<MessageHandler<'a>>,
+    wkb_output_dimensions: Option<OutputDimension>,
+    wkb_byte_order: Option<ByteOrder>,
+    empty_check: Option<EmptyCheck>,
+}
+
+impl<'a> ContextHandleBuilder<'a> {
+    /// Sets the notice message handler (see [`ContextHandle::set_notice_message_handler`]).
+    pub fn notice_message_handler(mut self, nf: Box<dyn Fn(&str) + Send + Sync + 'a>) -> Self {
+        self.notice_message_handler = Some(nf);
+        self
+    }
+
+    /// Sets the error message handler (see [`ContextHandle::set_error_message_handler`]).
+    pub fn error_message_handler(mut self, ef: Box<dyn Fn(&str) + Send + Sync + 'a>) -> Self {
+        self.error_message_handler = Some(ef);
+        self
+    }
+
+    /// Sets the WKB output dimensions (see [`ContextHandle::set_wkb_output_dimensions`]).
+    pub fn wkb_output_dimensions(mut self, dimensions: OutputDimension) -> Self {
+        self.wkb_output_dimensions = Some(dimensions);
+        self
+    }
+
+    /// Sets the WKB byte order (see [`ContextHandle::set_wkb_byte_order`]).
+    pub fn wkb_byte_order(mut self, byte_order: ByteOrder) -> Self {
+        self.wkb_byte_order = Some(byte_order);
+        self
+    }
+
+    /// Sets the check of the empty inputs of the operations (see
+    /// [`ContextHandle::set_empty_check`]).
+    pub fn empty_check(mut self, check: EmptyCheck) -> Self {
+        self.empty_check = Some(check);
+        self
+    }
+
+    /// Creates the `ContextHandle`. The handlers are set before anything else so they get the
+    /// messages emitted while applying the other settings.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{ContextHandle, Geom, Geometry};
+    /// use std::sync::Arc;
+    ///
+    /// let context_handle = ContextHandle::builder()
+    ///     .notice_message_handler(Box::new(|s| println!("GEOS notice: {}", s)))
+    ///     .build()
+    ///     .expect("invalid init");
+    /// let geom = Geometry::new_from_wkt_with_context("POINT (1 2)", Arc::new(context_handle))
+    ///     .expect("Invalid geometry");
+    /// assert_eq!(geom.to_wkt().unwrap(), "POINT (1 2)");
+    /// ```
+    pub fn build(self) -> GResult<ContextHandle<'a>> {
+        let mut context = ContextHandle::init_e(Some("ContextHandleBuilder::build"))?;
+        if self.notice_message_handler.is_some() {
+            context.set_notice_message_handler(self.notice_message_handler);
+        }
+        if self.error_message_handler.is_some() {
+            context.set_error_message_handler(self.error_message_handler);
+        }
+        if let Some(dimensions) = self.wkb_output_dimensions {
+            context.set_wkb_output_dimensions(dimensions)?;
+        }
+        if let Some(byte_order) = self.wkb_byte_order {
+            context.set_wkb_byte_order(byte_order);
+        }
+        if let Some(check) = self.empty_check {
+            context.set_empty_check(check);
+        }
+        Ok(context)
+    }
 }
 
 impl<'a> Drop for ContextHandle<'a> {
@@ -312,5 +485,6 @@ impl<'a> Drop for ContextHandle<'a> {
             // Now we just have to clear stuff!
             let _inner: Box<InnerContext<'a>> = Box::from_raw(self.inner.0);
         }
+        memory::decrement(&memory::CONTEXTS);
     }
 }
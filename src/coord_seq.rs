@@ -58,15 +58,37 @@ impl<'a> CoordSeq<'a> {
     /// assert_eq!(coord_seq2.get_x(1), Ok(1.));
     /// ```
     pub fn new(size: u32, dims: CoordDimensions) -> GResult<CoordSeq<'a>> {
-        match ContextHandle::init_e(Some("CoordSeq::new")) {
-            Ok(context_handle) => unsafe {
-                let ptr = GEOSCoordSeq_create_r(context_handle.as_raw(), size, dims.into());
-                CoordSeq::new_from_raw(ptr, Arc::new(context_handle), size, dims.into(), "new")
-            },
+        match ContextHandle::init_default("CoordSeq::new") {
+            Ok(context_handle) => CoordSeq::new_with_context(size, dims, context_handle),
             Err(e) => Err(e),
         }
     }
 
+    /// Same as [`new`](#method.new), but the created `CoordSeq` uses the given `context` instead
+    /// of a new one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{ContextHandle, ContextHandling, CoordDimensions, CoordSeq};
+    /// use std::sync::Arc;
+    ///
+    /// let context = Arc::new(ContextHandle::init().expect("invalid init"));
+    /// let coord_seq = CoordSeq::new_with_context(2, CoordDimensions::TwoD, Arc::clone(&context))
+    ///                          .expect("failed to create CoordSeq");
+    /// assert!(Arc::ptr_eq(&coord_seq.clone_context(), &context));
+    /// ```
+    pub fn new_with_context(
+        size: u32,
+        dims: CoordDimensions,
+        context: Arc<ContextHandle<'a>>,
+    ) -> GResult<CoordSeq<'a>> {
+        unsafe {
+            let ptr = GEOSCoordSeq_create_r(context.as_raw(), size, dims.into());
+            CoordSeq::new_from_raw(ptr, context, size, dims.into(), "new_with_context")
+        }
+    }
+
     /// Creates a new `CoordSeq`.
     ///
     /// # Example
@@ -93,6 +115,31 @@ impl<'a> CoordSeq<'a> {
     /// assert!(CoordSeq::new_from_vec(&[x]).is_err());
     /// ```
     pub fn new_from_vec<T: AsRef<[f64]>>(data: &[T]) -> GResult<CoordSeq<'a>> {
+        match ContextHandle::init_default("CoordSeq::new_from_vec") {
+            Ok(context_handle) => CoordSeq::new_from_vec_with_context(data, context_handle),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Same as [`new_from_vec`](#method.new_from_vec), but the created `CoordSeq` uses the given
+    /// `context` instead of a new one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{ContextHandle, ContextHandling, CoordSeq};
+    /// use std::sync::Arc;
+    ///
+    /// let context = Arc::new(ContextHandle::init().expect("invalid init"));
+    /// let coords = CoordSeq::new_from_vec_with_context(&[&[0., 1.], &[2., 3.]],
+    ///                                                  Arc::clone(&context))
+    ///                       .expect("failed to create CoordSeq");
+    /// assert!(Arc::ptr_eq(&coords.clone_context(), &context));
+    /// ```
+    pub fn new_from_vec_with_context<T: AsRef<[f64]>>(
+        data: &[T],
+        context: Arc<ContextHandle<'a>>,
+    ) -> GResult<CoordSeq<'a>> {
         let size = data.len();
 
         if size > 0 {
@@ -105,18 +152,9 @@ impl<'a> CoordSeq<'a> {
                     "All vec entries must have the same size!".into(),
                 ));
             }
-            match ContextHandle::init_e(Some("CoordSeq::new_from_vec")) {
-                Ok(context_handle) => unsafe {
-                    let ptr = GEOSCoordSeq_create_r(context_handle.as_raw(), size as _, dims as _);
-                    CoordSeq::new_from_raw(
-                        ptr,
-                        Arc::new(context_handle),
-                        size as _,
-                        dims as _,
-                        "new_from_vec",
-                    )
-                },
-                Err(e) => return Err(e),
+            unsafe {
+                let ptr = GEOSCoordSeq_create_r(context.as_raw(), size as _, dims as _);
+                CoordSeq::new_from_raw(ptr, context, size as _, dims as _, "new_from_vec")
             }
             .and_then(|mut coord| {
                 let raw_context = coord.get_raw_context();
@@ -196,7 +234,7 @@ impl<'a> CoordSeq<'a> {
             GEOSCoordSeq_setX_r(self.get_raw_context(), self.as_raw_mut(), line as _, val)
         };
         if ret_val == 0 {
-            Err(Error::GeosError("impossible to set x for coord".into()))
+            Err(self.failure("CoordSeq::set_x"))
         } else {
             Ok(())
         }
@@ -224,7 +262,7 @@ impl<'a> CoordSeq<'a> {
             GEOSCoordSeq_setY_r(self.get_raw_context(), self.as_raw_mut(), line as _, val)
         };
         if ret_val == 0 {
-            Err(Error::GeosError("impossible to set y for coord".into()))
+            Err(self.failure("CoordSeq::set_y"))
         } else {
             Ok(())
         }
@@ -252,7 +290,7 @@ impl<'a> CoordSeq<'a> {
             GEOSCoordSeq_setZ_r(self.get_raw_context(), self.as_raw_mut(), line as _, val)
         };
         if ret_val == 0 {
-            Err(Error::GeosError("impossible to set z for coord".into()))
+            Err(self.failure("CoordSeq::set_z"))
         } else {
             Ok(())
         }
@@ -288,10 +326,7 @@ impl<'a> CoordSeq<'a> {
             )
         };
         if ret_val == 0 {
-            Err(Error::GeosError(format!(
-                "impossible to set value for ordinate {}",
-                ordinate
-            )))
+            Err(self.failure("CoordSeq::set_ordinate"))
         } else {
             Ok(())
         }
@@ -317,9 +352,7 @@ impl<'a> CoordSeq<'a> {
             GEOSCoordSeq_getX_r(self.get_raw_context(), self.as_raw(), line as _, &mut n)
         };
         if ret_val == 0 {
-            Err(Error::GeosError(
-                "failed to get coordinates from CoordSeq".into(),
-            ))
+            Err(self.failure("CoordSeq::get_x"))
         } else {
             Ok(n as f64)
         }
@@ -348,9 +381,7 @@ impl<'a> CoordSeq<'a> {
             GEOSCoordSeq_getY_r(self.get_raw_context(), self.as_raw(), line as _, &mut n)
         };
         if ret_val == 0 {
-            Err(Error::GeosError(
-                "failed to get coordinates from CoordSeq".into(),
-            ))
+            Err(self.failure("CoordSeq::get_y"))
         } else {
             Ok(n as f64)
         }
@@ -379,9 +410,7 @@ impl<'a> CoordSeq<'a> {
             GEOSCoordSeq_getZ_r(self.get_raw_context(), self.as_raw(), line as _, &mut n)
         };
         if ret_val == 0 {
-            Err(Error::GeosError(
-                "failed to get coordinates from CoordSeq".into(),
-            ))
+            Err(self.failure("CoordSeq::get_z"))
         } else {
             Ok(n as f64)
         }
@@ -418,7 +447,7 @@ impl<'a> CoordSeq<'a> {
             )
         } != 1
         {
-            Err(Error::GeosError("getting size from CoordSeq".into()))
+            Err(self.failure("CoordSeq::get_ordinate"))
         } else {
             Ok(val)
         }
@@ -444,7 +473,7 @@ impl<'a> CoordSeq<'a> {
         let ret_val =
             unsafe { GEOSCoordSeq_getSize_r(self.get_raw_context(), self.as_raw(), &mut n) };
         if ret_val == 0 {
-            Err(Error::GeosError("getting size from CoordSeq".into()))
+            Err(self.failure("CoordSeq::size"))
         } else {
             Ok(n as usize)
         }
@@ -491,7 +520,7 @@ impl<'a> CoordSeq<'a> {
         let ret_val =
             unsafe { GEOSCoordSeq_getDimensions_r(self.get_raw_context(), self.as_raw(), &mut n) };
         if ret_val == 0 {
-            Err(Error::GeosError("getting dimensions from CoordSeq".into()))
+            Err(self.failure("CoordSeq::dimensions"))
         } else {
             Ok(CoordDimensions::try_from(n).expect("Failed to convert to CoordDimensions"))
         }
@@ -526,7 +555,7 @@ impl<'a> CoordSeq<'a> {
     ///
     /// let geom = Geometry::create_point(coords).expect("Failed to create point");
     ///
-    /// assert_eq!(geom.to_wkt().unwrap(), "POINT (1.0000000000000000 2.0000000000000000)");
+    /// assert_eq!(geom.to_wkt().unwrap(), "POINT (1 2)");
     /// ```
     pub fn create_point(self) -> GResult<Geometry<'a>> {
         Geometry::create_point(self)
@@ -544,9 +573,7 @@ impl<'a> CoordSeq<'a> {
     ///
     /// let geom = Geometry::create_line_string(coords).expect("Failed to create line string");
     ///
-    /// assert_eq!(geom.to_wkt().unwrap(),
-    ///            "LINESTRING (1.0000000000000000 2.0000000000000000, \
-    ///                         3.0000000000000000 4.0000000000000000)");
+    /// assert_eq!(geom.to_wkt().unwrap(), "LINESTRING (1 2, 3 4)");
     /// ```
     pub fn create_line_string(self) -> GResult<Geometry<'a>> {
         Geometry::create_line_string(self)
@@ -586,6 +613,16 @@ impl<'a> Clone for CoordSeq<'a> {
     }
 }
 
+impl<'a> CoordSeq<'a> {
+    /// Returns the error of a failed GEOS call, with the last error message of the context.
+    fn failure(&self, op: &'static str) -> Error {
+        Error::Operation {
+            op,
+            message: self.get_last_error().unwrap_or_default(),
+        }
+    }
+}
+
 impl<'a> ContextInteractions<'a> for CoordSeq<'a> {
     /// Set the context handle to the `CoordSeq`.
     ///
@@ -0,0 +1,157 @@
+use crate::{Geom, Geometry};
+use error::{Error, GResult};
+use geos_sys::*;
+use std::cell::Cell;
+use std::sync::atomic::{AtomicPtr, Ordering};
+use std::sync::Once;
+use std::time::{Duration, Instant};
+
+thread_local! {
+    // The deadline of the operations running on this thread, if any.
+    static DEADLINE: Cell<Option<Instant>> = const { Cell::new(None) };
+}
+
+// The interrupt callback registered before ours, called by ours.
+static PREVIOUS_CALLBACK: AtomicPtr<GEOSInterruptCallback> = AtomicPtr::new(std::ptr::null_mut());
+
+// Called regularly by GEOS from the thread running an operation.
+extern "C" fn check_deadline() {
+    let previous = PREVIOUS_CALLBACK.load(Ordering::Relaxed);
+    if !previous.is_null() {
+        // GEOS passes the callbacks themselves around, not pointers to them.
+        let previous: extern "C" fn() = unsafe { std::mem::transmute(previous) };
+        previous();
+    }
+    let expired =
+        DEADLINE.with(|d| matches!(d.get(), Some(deadline) if Instant::now() >= deadline));
+    if expired {
+        unsafe { GEOS_interruptRequest() };
+    }
+}
+
+fn register_callback() {
+    static REGISTER: Once = Once::new();
+    REGISTER.call_once(|| unsafe {
+        let callback = check_deadline as extern "C" fn() as *mut GEOSInterruptCallback;
+        let previous = GEOS_interruptRegisterCallback(callback);
+        PREVIOUS_CALLBACK.store(previous, Ordering::Relaxed);
+    });
+}
+
+// GEOS reports an interruption with an `InterruptedException: Interrupted!` error, whatever the
+// operation.
+fn is_interruption(e: &Error) -> bool {
+    e.to_string().contains("Interrupted")
+}
+
+// Restores the deadline of the thread, even if the operation panics.
+struct DeadlineGuard(Option<Instant>);
+
+impl Drop for DeadlineGuard {
+    fn drop(&mut self) {
+        DEADLINE.with(|d| d.set(self.0));
+    }
+}
+
+/// A geometry whose operations are interrupted once a deadline has passed, returned by
+/// [`Geom::with_deadline`](trait.Geom.html#tymethod.with_deadline).
+///
+/// The interruption relies on the interrupt callback of GEOS, which is process-wide: the crate
+/// registers its own one (calling the previously registered one, if any) the first time a
+/// deadline is used. It checks the deadline of the thread running the operation. The time it
+/// takes to return after the deadline depends on how often the operation checks for
+/// interruptions.
+///
+/// # Warning
+///
+/// GEOS only has a process-wide interruption flag, so when a deadline passes, an operation
+/// running on another thread at the very same moment (with or without a deadline) can catch the
+/// interruption instead and fail spuriously, with an "Interrupted" error. Don't use deadlines
+/// in a process running GEOS operations concurrently if such failures are not acceptable.
+///
+/// # Example
+///
+/// ```
+/// use geos::{Error, Geom, Geometry};
+/// use std::time::Duration;
+///
+/// let a = Geometry::new_from_wkt("POLYGON((0 0, 2 0, 2 2, 0 2, 0 0))").expect("Invalid geometry");
+/// let b = Geometry::new_from_wkt("POLYGON((1 1, 3 1, 3 3, 1 3, 1 1))").expect("Invalid geometry");
+///
+/// let both = a.with_deadline(Duration::from_secs(10)).intersection(&b).expect("timed out");
+/// assert_eq!(both.area(), Ok(1.));
+///
+/// assert_eq!(a.with_deadline(Duration::from_secs(0)).intersection(&b).err(), Some(Error::TimedOut));
+/// ```
+pub struct Deadline<'g, G> {
+    geom: &'g G,
+    // `None` if the timeout is too long to be represented.
+    deadline: Option<Instant>,
+}
+
+impl<'g, G> Deadline<'g, G> {
+    pub(crate) fn new(geom: &'g G, timeout: Duration) -> Deadline<'g, G> {
+        Deadline {
+            geom,
+            deadline: Instant::now().checked_add(timeout),
+        }
+    }
+
+    /// Runs `op` on the geometry, interrupting it once the deadline has passed. If it is
+    /// interrupted after the deadline, [`Error::TimedOut`](../enum.Error.html#variant.TimedOut)
+    /// is returned instead of the error GEOS reported. Nested deadlines are supported.
+    pub fn run<T, F: FnOnce(&G) -> GResult<T>>(&self, op: F) -> GResult<T> {
+        if matches!(self.deadline, Some(deadline) if Instant::now() >= deadline) {
+            return Err(Error::TimedOut);
+        }
+        register_callback();
+        let previous = DEADLINE.with(|d| d.get());
+        let _guard = DeadlineGuard(previous);
+        let deadline = match (previous, self.deadline) {
+            (Some(previous), Some(deadline)) => Some(previous.min(deadline)),
+            (previous, deadline) => previous.or(deadline),
+        };
+        DEADLINE.with(|d| d.set(deadline));
+        match op(self.geom) {
+            Err(e)
+                if is_interruption(&e)
+                    && matches!(deadline, Some(deadline) if Instant::now() >= deadline) =>
+            {
+                Err(Error::TimedOut)
+            }
+            res => res,
+        }
+    }
+}
+
+impl<'a, 'g, G: Geom<'a>> Deadline<'g, G> {
+    /// [`Geom::intersection`](trait.Geom.html#tymethod.intersection) with the deadline.
+    pub fn intersection<'b, O: Geom<'b>>(&self, other: &O) -> GResult<Geometry<'a>> {
+        self.run(|g| g.intersection(other))
+    }
+
+    /// [`Geom::union`](trait.Geom.html#tymethod.union) with the deadline.
+    pub fn union<'b, O: Geom<'b>>(&self, other: &O) -> GResult<Geometry<'a>> {
+        self.run(|g| g.union(other))
+    }
+
+    /// [`Geom::difference`](trait.Geom.html#tymethod.difference) with the deadline.
+    pub fn difference<'b, O: Geom<'b>>(&self, other: &O) -> GResult<Geometry<'a>> {
+        self.run(|g| g.difference(other))
+    }
+
+    /// [`Geom::sym_difference`](trait.Geom.html#tymethod.sym_difference) with the deadline.
+    pub fn sym_difference<'b, O: Geom<'b>>(&self, other: &O) -> GResult<Geometry<'a>> {
+        self.run(|g| g.sym_difference(other))
+    }
+
+    /// [`Geom::unary_union`](trait.Geom.html#tymethod.unary_union) with the deadline.
+    pub fn unary_union(&self) -> GResult<Geometry<'a>> {
+        self.run(|g| g.unary_union())
+    }
+
+    /// [`Geom::buffer`](trait.Geom.html#tymethod.buffer) with the deadline.
+    pub fn buffer(&self, width: f64, quadsegs: i32) -> GResult<Geometry<'a>> {
+        self.run(|g| g.buffer(width, quadsegs))
+    }
+}
@@ -1,6 +1,7 @@
 use libc::{c_int, size_t};
 
 use std::convert::TryFrom;
+use std::str::FromStr;
 
 #[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq)]
 pub enum CoordDimensions {
@@ -130,6 +131,12 @@ pub enum GeometryTypes {
     MultiLineString,
     MultiPolygon,
     GeometryCollection,
+    // The curved types are only created by GEOS 3.13 and later (see `Geom::linearize`).
+    CircularString,
+    CompoundCurve,
+    CurvePolygon,
+    MultiCurve,
+    MultiSurface,
     #[doc(hidden)]
     __Unknown(u32),
 }
@@ -147,11 +154,40 @@ impl TryFrom<c_int> for GeometryTypes {
             5 => Ok(GeometryTypes::MultiLineString),
             6 => Ok(GeometryTypes::MultiPolygon),
             7 => Ok(GeometryTypes::GeometryCollection),
+            8 => Ok(GeometryTypes::CircularString),
+            9 => Ok(GeometryTypes::CompoundCurve),
+            10 => Ok(GeometryTypes::CurvePolygon),
+            11 => Ok(GeometryTypes::MultiCurve),
+            12 => Ok(GeometryTypes::MultiSurface),
             x => Ok(GeometryTypes::__Unknown(x as _)),
         }
     }
 }
 
+impl GeometryTypes {
+    /// Returns `true` for the curved types (`CircularString`, `CompoundCurve`, `CurvePolygon`,
+    /// `MultiCurve` and `MultiSurface`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::GeometryTypes;
+    ///
+    /// assert!(GeometryTypes::CircularString.is_curved());
+    /// assert!(!GeometryTypes::LineString.is_curved());
+    /// ```
+    pub fn is_curved(self) -> bool {
+        matches!(
+            self,
+            GeometryTypes::CircularString
+                | GeometryTypes::CompoundCurve
+                | GeometryTypes::CurvePolygon
+                | GeometryTypes::MultiCurve
+                | GeometryTypes::MultiSurface
+        )
+    }
+}
+
 #[allow(clippy::from_over_into)]
 impl Into<c_int> for GeometryTypes {
     fn into(self) -> c_int {
@@ -164,6 +200,11 @@ impl Into<c_int> for GeometryTypes {
             GeometryTypes::MultiLineString => 5,
             GeometryTypes::MultiPolygon => 6,
             GeometryTypes::GeometryCollection => 7,
+            GeometryTypes::CircularString => 8,
+            GeometryTypes::CompoundCurve => 9,
+            GeometryTypes::CurvePolygon => 10,
+            GeometryTypes::MultiCurve => 11,
+            GeometryTypes::MultiSurface => 12,
             GeometryTypes::__Unknown(x) => x as _,
         }
     }
@@ -295,3 +336,106 @@ impl Into<c_int> for JoinStyle {
         }
     }
 }
+
+/// Binary spatial predicates, to be evaluated with [`Geom::evaluate`](trait.Geom.html#tymethod.evaluate).
+///
+/// It can be parsed from its snake case name, so predicates can be read from configuration:
+///
+/// ```
+/// use geos::BinaryPredicate;
+///
+/// assert_eq!("covered_by".parse(), Ok(BinaryPredicate::CoveredBy));
+/// assert!("nearby".parse::<BinaryPredicate>().is_err());
+/// ```
+#[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum BinaryPredicate {
+    Intersects,
+    Disjoint,
+    Contains,
+    Within,
+    Touches,
+    Crosses,
+    Overlaps,
+    Equals,
+    Covers,
+    CoveredBy,
+}
+
+impl FromStr for BinaryPredicate {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "intersects" => Ok(BinaryPredicate::Intersects),
+            "disjoint" => Ok(BinaryPredicate::Disjoint),
+            "contains" => Ok(BinaryPredicate::Contains),
+            "within" => Ok(BinaryPredicate::Within),
+            "touches" => Ok(BinaryPredicate::Touches),
+            "crosses" => Ok(BinaryPredicate::Crosses),
+            "overlaps" => Ok(BinaryPredicate::Overlaps),
+            "equals" => Ok(BinaryPredicate::Equals),
+            "covers" => Ok(BinaryPredicate::Covers),
+            "covered_by" => Ok(BinaryPredicate::CoveredBy),
+            _ => Err("Unknown binary predicate"),
+        }
+    }
+}
+
+/// What [`Geom::scrub_non_finite`](trait.Geom.html#tymethod.scrub_non_finite) does with the
+/// coordinates having a NaN or infinite X or Y value.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NonFinitePolicy {
+    /// Removes the coordinate. Lines and rings left with too few coordinates become empty.
+    DropPoint,
+    /// Returns an error.
+    Fail,
+    /// Replaces the non-finite values by the given one.
+    Replace(f64),
+}
+
+/// An opt-in check of the empty inputs of some operations, configured on the context with
+/// [`ContextHandle::set_empty_check`](struct.ContextHandle.html#method.set_empty_check).
+///
+/// It only covers the operations listed there (mostly constructive operations and distances);
+/// the other ones, including the predicates, always let GEOS handle empty geometries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmptyCheck {
+    /// Doesn't check anything: GEOS handles empty geometries, which usually gives an empty
+    /// result. This is the default.
+    Off,
+    /// Returns [`Error::EmptyGeometry`](enum.Error.html#variant.EmptyGeometry) when an empty
+    /// geometry is given to one of the operations checking it, to find where empty geometries
+    /// come from instead of carrying them around.
+    Error,
+}
+
+/// Overlay operations, to be run with [`Geom::overlay`](trait.Geom.html#tymethod.overlay).
+///
+/// Available using the `v3_9_0` feature.
+#[cfg(any(feature = "v3_9_0", feature = "dox"))]
+#[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum OverlayOp {
+    Intersection,
+    Union,
+    Difference,
+    SymDifference,
+}
+
+/// The overlay algorithm used by [`Geom::overlay`](trait.Geom.html#tymethod.overlay).
+///
+/// Available using the `v3_9_0` feature.
+#[cfg(any(feature = "v3_9_0", feature = "dox"))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OverlayAlgorithm {
+    /// The algorithm GEOS uses by default, like [`Geom::intersection`] and the other overlay
+    /// methods. It depends on how GEOS was built.
+    ///
+    /// [`Geom::intersection`]: trait.Geom.html#tymethod.intersection
+    Default,
+    /// OverlayNG, explicitly. The coordinates are snapped to a grid of cells of `grid_size`
+    /// (which must not be negative) and the result is guaranteed to be valid for this precision
+    /// model; a zero `grid_size` keeps the floating precision.
+    ///
+    /// GEOS doesn't expose the strict mode of OverlayNG in its C API, so it can't be selected.
+    Ng { grid_size: f64 },
+}
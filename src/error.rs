@@ -1,17 +1,70 @@
 use std::{self, fmt};
 
+/// Errors returned by this crate.
+///
+/// # Example
+///
+/// ```
+/// use geos::{Error, Geometry};
+///
+/// match Geometry::new_from_wkt("POINT (1") {
+///     Err(Error::Parse { format, .. }) => assert_eq!(format, "WKT"),
+///     _ => panic!("expected a parse error"),
+/// }
+/// ```
+#[allow(deprecated)]
 #[derive(Debug, Ord, PartialOrd, Eq, PartialEq, Clone)]
 pub enum Error {
     InvalidGeometry(String),
     ImpossibleOperation(String),
+    #[deprecated(
+        note = "the GEOS failures are reported with `Overlay`, `Operation`, `Parse` \
+                         or `Predicate`"
+    )]
     GeosError(String),
     GeosFunctionError(PredicateType, i32),
     NoConstructionFromNullPtr(String),
     ConversionError(String),
     GenericError(String),
+    /// The input can't be given to GEOS, like a string containing a nul byte.
+    InvalidInput(String),
+    /// An overlay operation (intersection, union...) failed, usually because of an invalid
+    /// input (so it may succeed after a `make_valid`).
+    Overlay {
+        op: &'static str,
+        message: String,
+    },
+    /// A GEOS operation other than an overlay (like computing an area or reading a coordinate)
+    /// failed. `message` is the last error of GEOS, if any.
+    Operation {
+        op: &'static str,
+        message: String,
+    },
+    /// The input couldn't be read. `offset` is the position of the error in the input, when
+    /// known.
+    Parse {
+        format: &'static str,
+        message: String,
+        offset: Option<usize>,
+    },
+    /// A predicate couldn't be evaluated.
+    Predicate {
+        kind: PredicateType,
+        message: String,
+    },
+    /// The input contains a curved geometry (its type is given), which GEOS can't read. It needs
+    /// to be linearized beforehand, like `ST_CurveToLine` does in PostGIS.
+    UnsupportedCurveType(String),
+    /// The operation was interrupted because its deadline passed (see
+    /// [`Geom::with_deadline`](crate::Geom::with_deadline)).
+    TimedOut,
+    /// An empty geometry was given to an operation (its name is given) while the
+    /// [`EmptyCheck`](crate::EmptyCheck) of its context is `Error`.
+    EmptyGeometry(String),
 }
 
 impl fmt::Display for Error {
+    #[allow(deprecated)]
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
             Error::InvalidGeometry(ref s) => write!(f, "Invalid geometry, {}", s),
@@ -29,10 +82,41 @@ impl fmt::Display for Error {
             ),
             Error::ConversionError(ref s) => write!(f, "impossible to convert geometry, {}", s),
             Error::GenericError(ref s) => write!(f, "generic error: {}", s),
+            Error::InvalidInput(ref s) => write!(f, "invalid input, {}", s),
+            Error::Overlay { op, ref message } => write!(f, "{} failed: {}", op, message),
+            Error::Operation { op, ref message } => write!(f, "{} failed: {}", op, message),
+            Error::Parse {
+                format,
+                ref message,
+                offset: Some(offset),
+            } => write!(
+                f,
+                "failed to parse {} at offset {}: {}",
+                format, offset, message
+            ),
+            Error::Parse {
+                format,
+                ref message,
+                offset: None,
+            } => write!(f, "failed to parse {}: {}", format, message),
+            Error::Predicate { kind, ref message } => {
+                write!(f, "error while evaluating {}: {}", kind, message)
+            }
+            Error::UnsupportedCurveType(ref s) => write!(
+                f,
+                "unsupported curve type {}, it must be linearized first",
+                s
+            ),
+            Error::TimedOut => write!(f, "the operation timed out"),
+            Error::EmptyGeometry(ref s) => write!(f, "{} was given an empty geometry", s),
         }
     }
 }
 
+// GEOS only reports its failures with a message (kept in the variants), so no variant wraps
+// another error and `source` is left to its default.
+impl std::error::Error for Error {}
+
 pub type GResult<T> = std::result::Result<T, Error>;
 
 #[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq)]
@@ -0,0 +1,118 @@
+//! Batch predicates on plain coordinates.
+//!
+//! Probing a geometry with millions of points is dominated by the creation of the GEOS points
+//! (and of their contexts). The functions of this module take plain [`Point`]s instead: the
+//! ones outside of the bounding box of the geometry are rejected in Rust, and a GEOS point is
+//! only created, in the context of the probed geometry, for the remaining ones.
+
+use crate::{CoordSeq, GResult, Geom, Geometry};
+use bbox::Rect;
+use std::sync::Arc;
+
+/// A plain `(x, y)` point, only converted to a GEOS geometry when needed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Point(pub f64, pub f64);
+
+impl Point {
+    /// Creates the matching GEOS point.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::Geom;
+    /// use geos::fast::Point;
+    ///
+    /// let geom = Point(1., 2.).to_geometry().expect("to_geometry failed");
+    /// assert_eq!(geom.to_wkt().unwrap(), "POINT (1 2)");
+    /// ```
+    pub fn to_geometry<'a>(&self) -> GResult<Geometry<'a>> {
+        Geometry::point(self.0, self.1)
+    }
+}
+
+impl From<(f64, f64)> for Point {
+    fn from((x, y): (f64, f64)) -> Point {
+        Point(x, y)
+    }
+}
+
+impl From<[f64; 2]> for Point {
+    fn from([x, y]: [f64; 2]) -> Point {
+        Point(x, y)
+    }
+}
+
+/// Calls `f` with the GEOS version of each point of `points` inside `bounds`, and returns
+/// `false` for the others.
+fn probe<'a, G, F>(geom: &G, points: &[Point], bounds: Rect, mut f: F) -> GResult<Vec<bool>>
+where
+    G: Geom<'a>,
+    F: FnMut(&Geometry<'a>) -> GResult<bool>,
+{
+    let context = geom.clone_context();
+    let mut res = Vec::with_capacity(points.len());
+    for point in points {
+        if !bounds.contains_point(point.0, point.1) {
+            res.push(false);
+            continue;
+        }
+        let seq = CoordSeq::new_from_vec_with_context(&[[point.0, point.1]], Arc::clone(&context))?;
+        res.push(f(&Geometry::create_point(seq)?)?);
+    }
+    Ok(res)
+}
+
+/// Returns, for each point of `points`, whether `geom` contains it (see [`Geom::contains`]).
+///
+/// # Example
+///
+/// ```
+/// use geos::Geometry;
+/// use geos::fast::{contains_points, Point};
+///
+/// let geom = Geometry::new_from_wkt("POLYGON((0 0, 4 0, 4 4, 0 4, 0 0), (1 1, 2 1, 2 2, 1 1))")
+///                     .expect("Invalid geometry");
+/// let points = [Point(3., 3.), Point(1.8, 1.2), Point(5., 1.), Point(0., 2.)];
+/// assert_eq!(contains_points(&geom, &points), Ok(vec![true, false, false, false]));
+/// ```
+pub fn contains_points<'a, G: Geom<'a>>(geom: &G, points: &[Point]) -> GResult<Vec<bool>> {
+    let bounds = match Rect::from_geom(geom)? {
+        Some(bounds) => bounds,
+        None => return Ok(vec![false; points.len()]),
+    };
+    let prepared = geom.to_prepared_geom()?;
+    probe(geom, points, bounds, |point| prepared.contains(point))
+}
+
+/// Returns, for each point of `points`, whether it is within `distance` of `geom` (see
+/// [`Geom::distance`]).
+///
+/// # Example
+///
+/// ```
+/// use geos::Geometry;
+/// use geos::fast::{dwithin_points, Point};
+///
+/// let geom = Geometry::new_from_wkt("LINESTRING(0 0, 10 0)").expect("Invalid geometry");
+/// let points = [Point(5., 1.), Point(5., 3.), Point(11., 0.5), Point(-2., 0.)];
+/// assert_eq!(dwithin_points(&geom, &points, 1.5), Ok(vec![true, false, true, false]));
+/// ```
+pub fn dwithin_points<'a, G: Geom<'a>>(
+    geom: &G,
+    points: &[Point],
+    distance: f64,
+) -> GResult<Vec<bool>> {
+    let bounds = match Rect::from_geom(geom)? {
+        Some(bounds) => bounds,
+        None => return Ok(vec![false; points.len()]),
+    };
+    let bounds = Rect::new(
+        bounds.xmin - distance,
+        bounds.ymin - distance,
+        bounds.xmax + distance,
+        bounds.ymax + distance,
+    );
+    probe(geom, points, bounds, |point| {
+        Ok(geom.distance(point)? <= distance)
+    })
+}
@@ -13,10 +13,15 @@ fn create_coord_seq<'a, 'b, It>(points: It, len: usize) -> Result<CoordSeq<'b>,
 where
     It: Iterator<Item = &'a Vec<f64>>,
 {
-    let mut coord_seq =
-        CoordSeq::new(len as u32, CoordDimensions::TwoD).expect("failed to create CoordSeq");
+    let mut coord_seq = CoordSeq::new(len as u32, CoordDimensions::TwoD)?;
 
     for (i, p) in points.enumerate() {
+        if p.len() < 2 {
+            return Err(Error::InvalidInput(format!(
+                "GeoJSON positions need at least 2 values, got {}",
+                p.len()
+            )));
+        }
         coord_seq.set_x(i, p[0])?;
         coord_seq.set_y(i, p[1])?;
     }
@@ -39,6 +44,21 @@ fn create_closed_coord_seq_from_vec<'a, 'b>(points: &'a [Vec<f64>]) -> Result<Co
     }
 }
 
+fn create_polygon<'b>(rings: &[Vec<Vec<f64>>]) -> GResult<GGeometry<'b>> {
+    let exterior = match rings.first() {
+        Some(exterior) => exterior,
+        None => return GGeometry::create_empty_polygon(),
+    };
+    let exterior_ring =
+        GGeometry::create_linear_ring(create_closed_coord_seq_from_vec(exterior.as_slice())?)?;
+    let interiors = rings
+        .iter()
+        .skip(1)
+        .map(|r| GGeometry::create_linear_ring(create_closed_coord_seq_from_vec(r.as_slice())?))
+        .collect::<GResult<Vec<GGeometry>>>()?;
+    GGeometry::create_polygon(exterior_ring, interiors)
+}
+
 impl<'a, 'b> TryFrom<&'a Geometry> for GGeometry<'b> {
     type Error = Error;
 
@@ -66,39 +86,11 @@ impl<'a, 'b> TryFrom<&'a Geometry> for GGeometry<'b> {
                     .collect::<GResult<Vec<GGeometry>>>()?;
                 GGeometry::create_multiline_string(gglines)
             }
-            Value::Polygon(ref rings) => {
-                let exterior_ring = GGeometry::create_linear_ring(
-                    create_closed_coord_seq_from_vec(rings[0].as_slice())?,
-                )?;
-                let interiors = rings
-                    .iter()
-                    .skip(1)
-                    .map(|r| {
-                        GGeometry::create_linear_ring(create_closed_coord_seq_from_vec(
-                            r.as_slice(),
-                        )?)
-                    })
-                    .collect::<GResult<Vec<GGeometry>>>()?;
-                GGeometry::create_polygon(exterior_ring, interiors)
-            }
+            Value::Polygon(ref rings) => create_polygon(rings),
             Value::MultiPolygon(ref polygons) => {
                 let ggpolys = polygons
                     .iter()
-                    .map(|rings| {
-                        let exterior_ring = GGeometry::create_linear_ring(
-                            create_closed_coord_seq_from_vec(rings[0].as_slice())?,
-                        )?;
-                        let interiors = rings
-                            .iter()
-                            .skip(1)
-                            .map(|r| {
-                                GGeometry::create_linear_ring(create_closed_coord_seq_from_vec(
-                                    r.as_slice(),
-                                )?)
-                            })
-                            .collect::<GResult<Vec<GGeometry>>>()?;
-                        GGeometry::create_polygon(exterior_ring, interiors)
-                    })
+                    .map(|rings| create_polygon(rings))
                     .collect::<GResult<Vec<GGeometry>>>()?;
                 GGeometry::create_multipolygon(ggpolys)
             }
@@ -272,6 +264,17 @@ mod test {
         );
     }
 
+    #[test]
+    fn geom_from_geojson_invalid_input() {
+        let geojson_pt = Geometry::new(Value::Point(vec![1.]));
+        let res: Result<GGeometry, _> = (&geojson_pt).try_into();
+        assert!(res.is_err());
+
+        let geojson_polygon = Geometry::new(Value::Polygon(vec![]));
+        let gpolygon: GGeometry = (&geojson_polygon).try_into().unwrap();
+        assert_eq!(gpolygon.is_empty(), Ok(true));
+    }
+
     #[test]
     fn geom_from_geojson_geometry_collection() {
         let geojson_gc = Geometry::new(Value::GeometryCollection(vec![
@@ -1,9 +1,12 @@
-use crate::{AsRawMut, ContextHandle, ContextHandling, Geom};
+use crate::{AsRawMut, ContextHandle, ContextHandling, CoordSeq, Geom, WKBWriter};
+use bbox::BBox;
 use context_handle::PtrWrap;
 use enums::*;
 use error::{Error, GResult, PredicateType};
-use geometry::Geometry;
+use geometry::{ConstGeometry, Geometry};
 use geos_sys::*;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
 use std::convert::TryFrom;
 use std::ffi::CStr;
 use std::os::raw::c_char;
@@ -66,6 +69,34 @@ pub fn version() -> GResult<String> {
     unsafe { unmanaged_string(GEOSversion(), "version") }
 }
 
+/// Returns the byte order of the given WKB or HEX encoded buffer, read from its first byte.
+///
+/// # Example
+///
+/// ```
+/// use geos::{wkb_byte_order, ByteOrder, Geom, Geometry};
+///
+/// let geom = Geometry::new_from_wkt("POINT (1 2)").expect("Invalid geometry");
+/// let wkb = geom.to_wkb().expect("conversion to WKB failed");
+/// assert_eq!(wkb_byte_order(wkb.as_ref()), Ok(ByteOrder::LittleEndian));
+///
+/// assert_eq!(wkb_byte_order(b"00000000013FF00000000000004000000000000000"),
+///            Ok(ByteOrder::BigEndian));
+/// assert!(wkb_byte_order(&[2u8, 0, 0]).is_err());
+/// assert!(wkb_byte_order(&[]).is_err());
+/// ```
+pub fn wkb_byte_order(buf: &[u8]) -> GResult<ByteOrder> {
+    match buf {
+        [0, ..] | [b'0', b'0', ..] => Ok(ByteOrder::BigEndian),
+        [1, ..] | [b'0', b'1', ..] => Ok(ByteOrder::LittleEndian),
+        [] => Err(Error::GenericError("empty WKB buffer".to_owned())),
+        _ => Err(Error::GenericError(format!(
+            "invalid WKB byte order marker {:#04x}",
+            buf[0]
+        ))),
+    }
+}
+
 pub(crate) fn check_geos_predicate(val: i8, p: PredicateType) -> GResult<bool> {
     match val {
         1 => Ok(true),
@@ -74,6 +105,34 @@ pub(crate) fn check_geos_predicate(val: i8, p: PredicateType) -> GResult<bool> {
     }
 }
 
+/// Same as [`check_geos_predicate`], but returns an `Error::Predicate` with the last error of
+/// the context of `g` on failure.
+pub(crate) fn check_geos_predicate_in<'a, C>(val: i8, kind: PredicateType, g: &C) -> GResult<bool>
+where
+    C: ContextHandling<Context = Arc<ContextHandle<'a>>>,
+{
+    check_geos_predicate(val, kind).map_err(|e| Error::Predicate {
+        kind,
+        message: g
+            .clone_context()
+            .get_last_error()
+            .unwrap_or_else(|| e.to_string()),
+    })
+}
+
+/// Returns an `Error::EmptyGeometry` if `g` is empty and `context` (the one of the geometry the
+/// operation is called on) has the `EmptyCheck::Error` check.
+pub(crate) fn check_empty<'a, G: Geom<'a>>(
+    context: &ContextHandle,
+    g: &G,
+    op: &str,
+) -> GResult<()> {
+    if context.get_empty_check() == EmptyCheck::Error && g.is_empty()? {
+        return Err(Error::EmptyGeometry(op.to_owned()));
+    }
+    Ok(())
+}
+
 pub(crate) fn check_ret(val: i32, p: PredicateType) -> GResult<()> {
     match val {
         1 => Ok(()),
@@ -89,12 +148,22 @@ pub(crate) fn create_multi_geom(
     mut geoms: Vec<Geometry<'_>>,
     output_type: GeometryTypes,
 ) -> GResult<Geometry<'_>> {
+    // GEOS would fail on other types, so better not give it the geometries at all.
+    match output_type {
+        GeometryTypes::GeometryCollection
+        | GeometryTypes::MultiPoint
+        | GeometryTypes::MultiLineString
+        | GeometryTypes::MultiPolygon => {}
+        _ => {
+            return Err(Error::GenericError(format!(
+                "{:?} is not a collection type",
+                output_type
+            )))
+        }
+    }
     let nb_geoms = geoms.len();
     let context = if geoms.is_empty() {
-        match ContextHandle::init() {
-            Ok(ch) => Arc::new(ch),
-            _ => return Err(Error::GenericError("GEOS_init_r failed".to_owned())),
-        }
+        ContextHandle::init_default("create_multi_geom")?
     } else {
         geoms[0].clone_context()
     };
@@ -111,8 +180,8 @@ pub(crate) fn create_multi_geom(
         }
     };
 
-    // we'll transfert the ownership of the ptr to the new Geometry,
-    // so the old one needs to forget their c ptr to avoid double cleanup
+    // GEOS takes the ownership of the ptrs, even when it fails (it destroys them then), so the
+    // old geometries need to forget their c ptr to avoid double cleanup.
     for g in geoms.iter_mut() {
         g.ptr = PtrWrap(::std::ptr::null_mut());
     }
@@ -120,6 +189,617 @@ pub(crate) fn create_multi_geom(
     res
 }
 
+/// Returns the `(xmin, ymin, xmax, ymax)` bounds of `g`, or `None` if it is empty.
+pub(crate) fn get_bounds<'a, G: Geom<'a>>(g: &G) -> GResult<Option<BBox>> {
+    if g.is_empty()? {
+        return Ok(None);
+    }
+    // The envelope degenerates to a `Point` or a `LineString` when the geometry has no area
+    // extent along one or both axes.
+    let envelope = g.envelope()?;
+    let coords = match envelope.geometry_type() {
+        GeometryTypes::Point | GeometryTypes::LineString => envelope.get_coord_seq()?,
+        GeometryTypes::Polygon => envelope.get_exterior_ring()?.get_coord_seq()?,
+        geom_type => return unknown_type(geom_type),
+    };
+    let mut bounds = (
+        f64::INFINITY,
+        f64::INFINITY,
+        f64::NEG_INFINITY,
+        f64::NEG_INFINITY,
+    );
+    for i in 0..coords.size()? {
+        let (x, y) = (coords.get_x(i)?, coords.get_y(i)?);
+        bounds.0 = bounds.0.min(x);
+        bounds.1 = bounds.1.min(y);
+        bounds.2 = bounds.2.max(x);
+        bounds.3 = bounds.3.max(y);
+    }
+    Ok(Some(bounds))
+}
+
+/// Creates the rectangular polygon covering the given bounds.
+pub(crate) fn create_rectangle<'a>(
+    xmin: f64,
+    ymin: f64,
+    xmax: f64,
+    ymax: f64,
+) -> GResult<Geometry<'a>> {
+    let context = ContextHandle::init_default("create_rectangle")?;
+    create_rectangle_with_context(xmin, ymin, xmax, ymax, context)
+}
+
+pub(crate) fn create_rectangle_with_context<'a>(
+    xmin: f64,
+    ymin: f64,
+    xmax: f64,
+    ymax: f64,
+    context: Arc<ContextHandle<'a>>,
+) -> GResult<Geometry<'a>> {
+    let ring = CoordSeq::new_from_vec_with_context(
+        &[
+            [xmin, ymin],
+            [xmax, ymin],
+            [xmax, ymax],
+            [xmin, ymax],
+            [xmin, ymin],
+        ],
+        context,
+    )?;
+    Geometry::create_polygon(Geometry::create_linear_ring(ring)?, vec![])
+}
+
+/// Reads the coordinates of `seq`. The Z value is `NaN` when `seq` has less than 3 dimensions.
+pub(crate) fn coords_from_seq(seq: &CoordSeq) -> GResult<Vec<[f64; 3]>> {
+    let has_z = seq.dimensions()? == CoordDimensions::ThreeD;
+    let mut coords = Vec::with_capacity(seq.size()?);
+    for i in 0..seq.size()? {
+        let z = if has_z { seq.get_z(i)? } else { f64::NAN };
+        coords.push([seq.get_x(i)?, seq.get_y(i)?, z]);
+    }
+    Ok(coords)
+}
+
+/// Creates a `CoordSeq` of `dims` dimensions from the given coordinates.
+pub(crate) fn seq_from_coords<'a>(
+    coords: &[[f64; 3]],
+    dims: CoordDimensions,
+) -> GResult<CoordSeq<'a>> {
+    let mut seq = CoordSeq::new(coords.len() as _, dims)?;
+    for (i, coord) in coords.iter().enumerate() {
+        seq.set_x(i, coord[0])?;
+        if dims != CoordDimensions::OneD {
+            seq.set_y(i, coord[1])?;
+        }
+        if dims == CoordDimensions::ThreeD {
+            seq.set_z(i, coord[2])?;
+        }
+    }
+    Ok(seq)
+}
+
+/// Splits every segment of `coords` into the number of sub-segments returned by `nb_parts`.
+pub(crate) fn densify_coords<F: Fn(&[f64; 3], &[f64; 3]) -> usize>(
+    coords: &[[f64; 3]],
+    nb_parts: F,
+) -> Vec<[f64; 3]> {
+    let mut res = Vec::with_capacity(coords.len());
+    for pair in coords.windows(2) {
+        let (a, b) = (&pair[0], &pair[1]);
+        let n = nb_parts(a, b).max(1);
+        for i in 0..n {
+            let t = i as f64 / n as f64;
+            res.push([
+                a[0] + (b[0] - a[0]) * t,
+                a[1] + (b[1] - a[1]) * t,
+                a[2] + (b[2] - a[2]) * t,
+            ]);
+        }
+    }
+    if let Some(last) = coords.last() {
+        res.push(*last);
+    }
+    res
+}
+
+/// Calls `f` on the coordinates of every point, line string and linear ring of `g`.
+pub(crate) fn visit_coord_seqs<'a, G, F>(g: &G, f: &mut F) -> GResult<()>
+where
+    G: Geom<'a>,
+    F: FnMut(&CoordSeq<'a>, GeometryTypes) -> GResult<()>,
+{
+    let geom_type = g.geometry_type();
+    match geom_type {
+        GeometryTypes::Point | GeometryTypes::LineString | GeometryTypes::LinearRing => {
+            f(&g.get_coord_seq()?, geom_type)
+        }
+        GeometryTypes::Polygon => {
+            if g.is_empty()? {
+                return Ok(());
+            }
+            visit_coord_seqs(&g.get_exterior_ring()?, f)?;
+            for n in 0..g.get_num_interior_rings()? {
+                visit_coord_seqs(&g.get_interior_ring_n(n as _)?, f)?;
+            }
+            Ok(())
+        }
+        GeometryTypes::MultiPoint
+        | GeometryTypes::MultiLineString
+        | GeometryTypes::MultiPolygon
+        | GeometryTypes::GeometryCollection => {
+            for n in 0..g.get_num_geometries()? {
+                visit_coord_seqs(&g.get_geometry_n(n)?, f)?;
+            }
+            Ok(())
+        }
+        geom_type if geom_type.is_curved() => unsupported_curve(geom_type),
+        _ => unknown_type(geom_type),
+    }
+}
+
+/// Rebuilds `g` with the same structure, replacing the coordinates of every point, line string
+/// and linear ring by the ones returned by `f`.
+pub(crate) fn map_coord_seqs<'a, 'b, G, F>(g: &G, f: &mut F) -> GResult<Geometry<'b>>
+where
+    G: Geom<'a>,
+    F: FnMut(&CoordSeq<'a>, GeometryTypes) -> GResult<CoordSeq<'b>>,
+{
+    let geom_type = g.geometry_type();
+    let mut res = match geom_type {
+        GeometryTypes::Point => Geometry::create_point(f(&g.get_coord_seq()?, geom_type)?),
+        GeometryTypes::LineString => {
+            Geometry::create_line_string(f(&g.get_coord_seq()?, geom_type)?)
+        }
+        GeometryTypes::LinearRing => {
+            Geometry::create_linear_ring(f(&g.get_coord_seq()?, geom_type)?)
+        }
+        GeometryTypes::Polygon => {
+            if g.is_empty()? {
+                Geometry::create_empty_polygon()
+            } else {
+                let exterior = map_coord_seqs(&g.get_exterior_ring()?, f)?;
+                let mut interiors = Vec::with_capacity(g.get_num_interior_rings()?);
+                for n in 0..g.get_num_interior_rings()? {
+                    interiors.push(map_coord_seqs(&g.get_interior_ring_n(n as _)?, f)?);
+                }
+                Geometry::create_polygon(exterior, interiors)
+            }
+        }
+        GeometryTypes::MultiPoint
+        | GeometryTypes::MultiLineString
+        | GeometryTypes::MultiPolygon
+        | GeometryTypes::GeometryCollection => {
+            let mut parts = Vec::with_capacity(g.get_num_geometries()?);
+            for n in 0..g.get_num_geometries()? {
+                parts.push(map_coord_seqs(&g.get_geometry_n(n)?, f)?);
+            }
+            create_multi_geom(parts, geom_type)
+        }
+        geom_type if geom_type.is_curved() => unsupported_curve(geom_type),
+        _ => unknown_type(geom_type),
+    }?;
+    if let Ok(srid) = g.get_srid() {
+        res.set_srid(srid);
+    }
+    Ok(res)
+}
+
+/// Runs `op` on the original inputs (`None`), then on the inputs snapped to `grid_size` if the
+/// result is invalid, and eventually makes the last result valid. `op` is only retried on the
+/// snapped inputs if `grid_size` is positive.
+#[cfg(any(feature = "v3_8_0", feature = "dox"))]
+pub(crate) fn retry_until_valid<'a, F>(grid_size: f64, mut op: F) -> GResult<Geometry<'a>>
+where
+    F: FnMut(Option<f64>) -> GResult<Geometry<'a>>,
+{
+    let mut res = op(None);
+    match res {
+        Ok(ref geom) if geom.is_valid() => return res,
+        _ => {}
+    }
+    if grid_size > 0. {
+        match op(Some(grid_size)) {
+            Ok(geom) => {
+                if geom.is_valid() {
+                    return Ok(geom);
+                }
+                res = Ok(geom);
+            }
+            Err(e) => {
+                if res.is_err() {
+                    res = Err(e);
+                }
+            }
+        }
+    }
+    res?.make_valid()
+}
+
+/// Returns the signed area of the ring made of `coords` (shoelace formula): positive if it's
+/// counter-clockwise, negative if it's clockwise.
+pub(crate) fn signed_ring_area(coords: &[[f64; 3]]) -> f64 {
+    coords
+        .windows(2)
+        .map(|w| w[0][0] * w[1][1] - w[1][0] * w[0][1])
+        .sum::<f64>()
+        / 2.
+}
+
+/// Mean radius of the Earth, in meters.
+pub(crate) const EARTH_RADIUS: f64 = 6_371_008.8;
+
+/// Returns the unit vector of the given longitude and latitude (in degrees).
+fn to_unit_vector(lon: f64, lat: f64) -> [f64; 3] {
+    let (lon, lat) = (lon.to_radians(), lat.to_radians());
+    [lat.cos() * lon.cos(), lat.cos() * lon.sin(), lat.sin()]
+}
+
+/// Returns the angle (in radians) between the `a` and `b` longitude/latitude coordinates, as
+/// seen from the center of the Earth.
+pub(crate) fn central_angle(a: &[f64; 3], b: &[f64; 3]) -> f64 {
+    let (u, v) = (to_unit_vector(a[0], a[1]), to_unit_vector(b[0], b[1]));
+    let cross = [
+        u[1] * v[2] - u[2] * v[1],
+        u[2] * v[0] - u[0] * v[2],
+        u[0] * v[1] - u[1] * v[0],
+    ];
+    let dot = u[0] * v[0] + u[1] * v[1] + u[2] * v[2];
+    (cross[0].hypot(cross[1]).hypot(cross[2])).atan2(dot)
+}
+
+/// Returns the point at the `t` fraction of the great circle arc going from `a` to `b`. The Z
+/// value is linearly interpolated. Antipodal points have no unique arc, so a straight line is
+/// used instead.
+pub(crate) fn great_circle_point(a: &[f64; 3], b: &[f64; 3], t: f64) -> [f64; 3] {
+    let z = a[2] + (b[2] - a[2]) * t;
+    let angle = central_angle(a, b);
+    if angle.sin().abs() < 1e-12 {
+        return [a[0] + (b[0] - a[0]) * t, a[1] + (b[1] - a[1]) * t, z];
+    }
+    let (u, v) = (to_unit_vector(a[0], a[1]), to_unit_vector(b[0], b[1]));
+    let (wa, wb) = (
+        ((1. - t) * angle).sin() / angle.sin(),
+        (t * angle).sin() / angle.sin(),
+    );
+    let p = [
+        wa * u[0] + wb * v[0],
+        wa * u[1] + wb * v[1],
+        wa * u[2] + wb * v[2],
+    ];
+    [
+        p[1].atan2(p[0]).to_degrees(),
+        p[2].atan2(p[0].hypot(p[1])).to_degrees(),
+        z,
+    ]
+}
+
+#[derive(PartialEq)]
+struct Visit {
+    distance: f64,
+    node: usize,
+}
+
+impl Eq for Visit {}
+
+impl PartialOrd for Visit {
+    fn partial_cmp(&self, other: &Visit) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Visit {
+    // Reversed so that `BinaryHeap` pops the closest node first.
+    fn cmp(&self, other: &Visit) -> Ordering {
+        other
+            .distance
+            .partial_cmp(&self.distance)
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+/// Dijkstra from `start`: returns the distance to every node (infinite if unreachable) and the
+/// previous node on the shortest path.
+fn shortest_paths(adjacency: &[Vec<(usize, f64)>], start: usize) -> (Vec<f64>, Vec<Option<usize>>) {
+    let mut distances = vec![f64::INFINITY; adjacency.len()];
+    let mut previous = vec![None; adjacency.len()];
+    let mut heap = BinaryHeap::new();
+    distances[start] = 0.;
+    heap.push(Visit {
+        distance: 0.,
+        node: start,
+    });
+    while let Some(Visit { distance, node }) = heap.pop() {
+        if distance > distances[node] {
+            continue;
+        }
+        for &(next, length) in &adjacency[node] {
+            if distance + length < distances[next] {
+                distances[next] = distance + length;
+                previous[next] = Some(node);
+                heap.push(Visit {
+                    distance: distance + length,
+                    node: next,
+                });
+            }
+        }
+    }
+    (distances, previous)
+}
+
+/// Returns the vertices of the longest shortest path of the graph made of `segments`, found by
+/// running Dijkstra twice on each connected component (which is exact when the graph is a tree).
+pub(crate) fn longest_path(segments: &[[[f64; 2]; 2]]) -> Vec<[f64; 2]> {
+    let mut nodes = Vec::new();
+    let mut ids = HashMap::new();
+    let mut adjacency: Vec<Vec<(usize, f64)>> = Vec::new();
+    for segment in segments {
+        let mut ends = [0; 2];
+        for (end, coord) in ends.iter_mut().zip(segment.iter()) {
+            *end = *ids
+                .entry((coord[0].to_bits(), coord[1].to_bits()))
+                .or_insert_with(|| {
+                    nodes.push(*coord);
+                    adjacency.push(Vec::new());
+                    nodes.len() - 1
+                });
+        }
+        let length = (segment[1][0] - segment[0][0]).hypot(segment[1][1] - segment[0][1]);
+        adjacency[ends[0]].push((ends[1], length));
+        adjacency[ends[1]].push((ends[0], length));
+    }
+
+    let farthest = |distances: &[f64]| {
+        let mut res = None;
+        for (node, &distance) in distances.iter().enumerate() {
+            let is_better = match res {
+                Some((_, best)) => distance > best,
+                None => true,
+            };
+            if distance.is_finite() && is_better {
+                res = Some((node, distance));
+            }
+        }
+        res
+    };
+    let mut visited = vec![false; nodes.len()];
+    let mut best: Option<(f64, Vec<usize>)> = None;
+    for start in 0..nodes.len() {
+        if visited[start] {
+            continue;
+        }
+        let (distances, _) = shortest_paths(&adjacency, start);
+        for (node, distance) in distances.iter().enumerate() {
+            visited[node] |= distance.is_finite();
+        }
+        let (from, _) = match farthest(&distances) {
+            Some(x) => x,
+            None => continue,
+        };
+        let (distances, previous) = shortest_paths(&adjacency, from);
+        let (to, length) = match farthest(&distances) {
+            Some(x) => x,
+            None => continue,
+        };
+        let is_better = match best {
+            Some((best_length, _)) => length > best_length,
+            None => true,
+        };
+        if is_better {
+            let mut path = vec![to];
+            while let Some(node) = previous[path[path.len() - 1]] {
+                path.push(node);
+            }
+            best = Some((length, path));
+        }
+    }
+    best.map_or_else(Vec::new, |(_, path)| {
+        path.into_iter().rev().map(|node| nodes[node]).collect()
+    })
+}
+
+/// Returns the error for a curved geometry type, which has to be linearized to be handled.
+pub(crate) fn unsupported_curve<T>(geom_type: GeometryTypes) -> GResult<T> {
+    Err(Error::UnsupportedCurveType(format!("{:?}", geom_type)))
+}
+
+/// Returns the error for a geometry type unknown to the crate.
+pub(crate) fn unknown_type<T>(geom_type: GeometryTypes) -> GResult<T> {
+    let code: i32 = geom_type.into();
+    Err(Error::GenericError(format!(
+        "Unknown geometry type {}",
+        code
+    )))
+}
+
+const CURVE_TYPES: [&str; 5] = [
+    "CircularString",
+    "CompoundCurve",
+    "CurvePolygon",
+    "MultiCurve",
+    "MultiSurface",
+];
+
+/// Returns the name of the first curved geometry type found in `input` (in the given `format`),
+/// if any. It's only meant to explain a parsing failure, since GEOS doesn't read curves.
+pub(crate) fn find_curve_type(format: &str, input: &[u8]) -> Option<&'static str> {
+    match format {
+        "WKT" => str::from_utf8(input)
+            .ok()?
+            .split(|c: char| !c.is_ascii_alphabetic())
+            .find_map(|word| {
+                CURVE_TYPES
+                    .iter()
+                    .find(|name| name.eq_ignore_ascii_case(word))
+                    .cloned()
+            }),
+        "HEX" => {
+            let wkb = input
+                .chunks(2)
+                .map(|pair| {
+                    str::from_utf8(pair)
+                        .ok()
+                        .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+                })
+                .collect::<Option<Vec<_>>>()?;
+            wkb_curve_type(&wkb, &mut 0, 0)?
+        }
+        "WKB" => wkb_curve_type(input, &mut 0, 0)?,
+        _ => None,
+    }
+}
+
+/// Maximum nesting of the collections walked by `wkb_curve_type`, to bound its recursion on
+/// untrusted input.
+const MAX_WKB_DEPTH: usize = 256;
+
+/// Walks the (ISO, extended or OGC) WKB geometry starting at `pos`, returning `None` if it
+/// isn't valid WKB (or nested deeper than `MAX_WKB_DEPTH`) and `Some(name)` if it contains a
+/// curved geometry type.
+fn wkb_curve_type(wkb: &[u8], pos: &mut usize, depth: usize) -> Option<Option<&'static str>> {
+    fn skip(wkb: &[u8], pos: &mut usize, len: usize) -> Option<()> {
+        let end = pos.checked_add(len)?;
+        if end > wkb.len() {
+            return None;
+        }
+        *pos = end;
+        Some(())
+    }
+    fn read_u32(wkb: &[u8], pos: &mut usize, little_endian: bool) -> Option<usize> {
+        let start = *pos;
+        skip(wkb, pos, 4)?;
+        let mut bytes = [0; 4];
+        bytes.copy_from_slice(&wkb[start..*pos]);
+        Some(if little_endian {
+            u32::from_le_bytes(bytes)
+        } else {
+            u32::from_be_bytes(bytes)
+        } as usize)
+    }
+
+    if depth >= MAX_WKB_DEPTH {
+        return None;
+    }
+    let little_endian = *wkb.get(*pos)? == 1;
+    skip(wkb, pos, 1)?;
+    let raw_type = read_u32(wkb, pos, little_endian)?;
+    if raw_type & 0x2000_0000 != 0 {
+        // SRID of the extended WKB.
+        skip(wkb, pos, 4)?;
+    }
+    let iso_type = raw_type & 0x0fff_ffff;
+    let mut dims = 2;
+    if raw_type & 0x8000_0000 != 0 {
+        dims += 1;
+    }
+    if raw_type & 0x4000_0000 != 0 {
+        dims += 1;
+    }
+    dims += match iso_type / 1000 {
+        1 | 2 => 1,
+        3 => 2,
+        _ => 0,
+    };
+    let coord_size = dims * 8;
+    match iso_type % 1000 {
+        1 => skip(wkb, pos, coord_size)?,
+        2 => {
+            let nb_coords = read_u32(wkb, pos, little_endian)?;
+            skip(wkb, pos, nb_coords.checked_mul(coord_size)?)?;
+        }
+        3 => {
+            for _ in 0..read_u32(wkb, pos, little_endian)? {
+                let nb_coords = read_u32(wkb, pos, little_endian)?;
+                skip(wkb, pos, nb_coords.checked_mul(coord_size)?)?;
+            }
+        }
+        4..=7 => {
+            for _ in 0..read_u32(wkb, pos, little_endian)? {
+                if let Some(name) = wkb_curve_type(wkb, pos, depth + 1)? {
+                    return Some(Some(name));
+                }
+            }
+        }
+        kind @ 8..=12 => return Some(Some(CURVE_TYPES[kind - 8])),
+        _ => return None,
+    }
+    Some(None)
+}
+
+/// Returns the WKB of the normalized `g` (little endian, with Z values and SRID), used to order
+/// geometries deterministically.
+pub(crate) fn canonical_wkb<'a, G: Geom<'a>>(g: &G) -> GResult<Vec<u8>> {
+    let mut writer = canonical_wkb_writer(g.clone_context())?;
+    write_canonical_wkb(&mut writer, g)
+}
+
+fn canonical_wkb_writer<'a>(context: Arc<ContextHandle<'a>>) -> GResult<WKBWriter<'a>> {
+    let mut writer = WKBWriter::new_with_context(context)?;
+    writer.set_output_dimension(OutputDimension::ThreeD);
+    writer.set_wkb_byte_order(ByteOrder::LittleEndian);
+    writer.set_include_SRID(true);
+    Ok(writer)
+}
+
+fn write_canonical_wkb<'a, 'b, G: Geom<'b>>(writer: &mut WKBWriter<'a>, g: &G) -> GResult<Vec<u8>> {
+    let mut normalized = Geom::clone(g);
+    normalized.normalize()?;
+    Ok(writer.write_wkb(&normalized)?.as_ref().to_vec())
+}
+
+pub(crate) const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// Feeds `bytes` to the 64-bit FNV-1a `hash`. Unlike the hashers of the standard library, its
+/// output is guaranteed to never change.
+pub(crate) fn fnv1a(hash: &mut u64, bytes: &[u8]) {
+    for &b in bytes {
+        *hash ^= u64::from(b);
+        *hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+}
+
+/// Sorts `geoms` according to [`Geom::canonical_cmp`]. The canonical form of each geometry is
+/// only computed once, and `geoms` is left untouched if one of them fails.
+///
+/// # Example
+///
+/// ```
+/// use geos::{sort_canonical, Geom, Geometry};
+///
+/// let mut geoms = ["POINT (1 2)", "LINESTRING (1 1, 0 0)", "POINT (0 0)", "POLYGON EMPTY"]
+///     .iter()
+///     .map(|wkt| Geometry::new_from_wkt(wkt).expect("Invalid geometry"))
+///     .collect::<Vec<_>>();
+/// let mut reversed = geoms.iter().rev().cloned().collect::<Vec<_>>();
+///
+/// sort_canonical(&mut geoms).expect("sort_canonical failed");
+/// sort_canonical(&mut reversed).expect("sort_canonical failed");
+/// for (a, b) in geoms.iter().zip(reversed.iter()) {
+///     assert_eq!(a.to_wkt(), b.to_wkt());
+/// }
+/// ```
+pub fn sort_canonical<'a, G: Geom<'a>>(geoms: &mut [G]) -> GResult<()> {
+    let mut writer = match geoms.first() {
+        Some(first) => canonical_wkb_writer(first.clone_context())?,
+        None => return Ok(()),
+    };
+    let keys = geoms
+        .iter()
+        .map(|g| write_canonical_wkb(&mut writer, g))
+        .collect::<GResult<Vec<_>>>()?;
+    // `order[i]` is the index of the geometry going to `i`.
+    let mut order = (0..geoms.len()).collect::<Vec<_>>();
+    order.sort_by(|&a, &b| keys[a].cmp(&keys[b]));
+    for i in 0..geoms.len() {
+        // The geometries before `i` were swapped away from their original place: follow them.
+        let mut j = order[i];
+        while j < i {
+            j = order[j];
+        }
+        geoms.swap(i, j);
+    }
+    Ok(())
+}
+
 pub fn orientation_index(
     ax: f64,
     ay: f64,
@@ -193,9 +873,543 @@ pub fn segment_intersection(
     }
 }
 
+/// Returns the distance between `p` and the `[a, b]` segment.
+pub(crate) fn segment_distance(p: &[f64; 3], a: &[f64; 3], b: &[f64; 3]) -> f64 {
+    let (dx, dy) = (b[0] - a[0], b[1] - a[1]);
+    let length2 = dx * dx + dy * dy;
+    let fraction = if length2 > 0. {
+        (((p[0] - a[0]) * dx + (p[1] - a[1]) * dy) / length2).clamp(0., 1.)
+    } else {
+        0.
+    };
+    (p[0] - a[0] - fraction * dx).hypot(p[1] - a[1] - fraction * dy)
+}
+
+/// Douglas-Peucker simplification of `coords`, returning whether each of them is kept.
+pub(crate) fn douglas_peucker_mask(coords: &[[f64; 3]], tolerance: f64) -> Vec<bool> {
+    let mut mask = vec![false; coords.len()];
+    if coords.is_empty() {
+        return mask;
+    }
+    mask[0] = true;
+    mask[coords.len() - 1] = true;
+    let mut stack = vec![(0, coords.len() - 1)];
+    while let Some((start, end)) = stack.pop() {
+        let mut farthest = None;
+        let mut max_distance = tolerance;
+        for i in start + 1..end {
+            let distance = segment_distance(&coords[i], &coords[start], &coords[end]);
+            if distance > max_distance {
+                max_distance = distance;
+                farthest = Some(i);
+            }
+        }
+        if let Some(i) = farthest {
+            mask[i] = true;
+            stack.push((start, i));
+            stack.push((i, end));
+        }
+    }
+    mask
+}
+
+/// One pass of Chaikin's corner cutting on `coords`, keeping the ends of open lines.
+pub(crate) fn chaikin(coords: &[[f64; 3]], closed: bool) -> Vec<[f64; 3]> {
+    let lerp = |a: &[f64; 3], b: &[f64; 3], f: f64| {
+        [
+            a[0] + (b[0] - a[0]) * f,
+            a[1] + (b[1] - a[1]) * f,
+            a[2] + (b[2] - a[2]) * f,
+        ]
+    };
+    let mut res = Vec::with_capacity(coords.len() * 2);
+    if !closed {
+        res.extend(coords.first());
+    }
+    for pair in coords.windows(2) {
+        res.push(lerp(&pair[0], &pair[1], 0.25));
+        res.push(lerp(&pair[0], &pair[1], 0.75));
+    }
+    if closed {
+        let first = res[0];
+        res.push(first);
+    } else {
+        res.extend(coords.last());
+    }
+    res
+}
+
+/// Area of the `abc` triangle.
+fn triangle_area(a: &[f64; 3], b: &[f64; 3], c: &[f64; 3]) -> f64 {
+    ((b[0] - a[0]) * (c[1] - a[1]) - (c[0] - a[0]) * (b[1] - a[1])).abs() / 2.
+}
+
+#[derive(PartialEq)]
+struct VwCandidate {
+    area: f64,
+    index: usize,
+    prev: usize,
+    next: usize,
+}
+
+impl Eq for VwCandidate {}
+
+impl PartialOrd for VwCandidate {
+    fn partial_cmp(&self, other: &VwCandidate) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for VwCandidate {
+    // Reversed so that `BinaryHeap` pops the smallest triangle first.
+    fn cmp(&self, other: &VwCandidate) -> Ordering {
+        other
+            .area
+            .partial_cmp(&self.area)
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+/// Visvalingam-Whyatt simplification of `coords`, returning whether each of them is kept.
+///
+/// Vertices are removed, smallest triangle first, while their triangle is smaller than
+/// `tolerance`, more than `min_points` vertices are left and `can_remove(prev, index, next,
+/// mask)` accepts it. The first and last vertices are always kept.
+pub(crate) fn visvalingam_whyatt_mask<F>(
+    coords: &[[f64; 3]],
+    tolerance: f64,
+    min_points: usize,
+    mut can_remove: F,
+) -> Vec<bool>
+where
+    F: FnMut(usize, usize, usize, &[bool]) -> bool,
+{
+    let n = coords.len();
+    let mut mask = vec![true; n];
+    if n < 3 || n <= min_points {
+        return mask;
+    }
+    let mut prev = (0..n).map(|i| i.saturating_sub(1)).collect::<Vec<_>>();
+    let mut next = (1..=n).collect::<Vec<_>>();
+    let mut heap = (1..n - 1)
+        .map(|i| VwCandidate {
+            area: triangle_area(&coords[i - 1], &coords[i], &coords[i + 1]),
+            index: i,
+            prev: i - 1,
+            next: i + 1,
+        })
+        .collect::<BinaryHeap<_>>();
+    let mut remaining = n;
+    while let Some(c) = heap.pop() {
+        if c.area >= tolerance || remaining <= min_points {
+            break;
+        }
+        // Outdated candidate, its neighbours changed since it was computed.
+        if !mask[c.index] || prev[c.index] != c.prev || next[c.index] != c.next {
+            continue;
+        }
+        if !can_remove(c.prev, c.index, c.next, &mask) {
+            continue;
+        }
+        mask[c.index] = false;
+        remaining -= 1;
+        next[c.prev] = c.next;
+        prev[c.next] = c.prev;
+        for &j in &[c.prev, c.next] {
+            if j != 0 && j != n - 1 {
+                heap.push(VwCandidate {
+                    area: triangle_area(&coords[prev[j]], &coords[j], &coords[next[j]]),
+                    index: j,
+                    prev: prev[j],
+                    next: next[j],
+                });
+            }
+        }
+    }
+    mask
+}
+
+/// Minimum number of vertices kept by the Visvalingam-Whyatt simplification.
+pub(crate) fn vw_min_points(geom_type: GeometryTypes) -> usize {
+    match geom_type {
+        GeometryTypes::LinearRing => 4,
+        GeometryTypes::LineString => 2,
+        // Points aren't simplified.
+        _ => usize::MAX,
+    }
+}
+
+/// Returns the coordinates whose `mask` value is `true`.
+pub(crate) fn apply_mask(coords: &[[f64; 3]], mask: &[bool]) -> Vec<[f64; 3]> {
+    coords
+        .iter()
+        .zip(mask)
+        .filter(|(_, keep)| **keep)
+        .map(|(c, _)| *c)
+        .collect()
+}
+
+fn orientation(a: &[f64; 3], b: &[f64; 3], c: &[f64; 3]) -> f64 {
+    (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])
+}
+
+/// Returns `true` if the `[a, b]` and `[c, d]` segments cross or touch. Touching at a shared
+/// endpoint isn't taken into account.
+fn segments_intersect(a: &[f64; 3], b: &[f64; 3], c: &[f64; 3], d: &[f64; 3]) -> bool {
+    let shared = |p: &[f64; 3]| p[..2] == a[..2] || p[..2] == b[..2];
+    let d1 = orientation(c, d, a);
+    let d2 = orientation(c, d, b);
+    let d3 = orientation(a, b, c);
+    let d4 = orientation(a, b, d);
+    if d1 * d2 < 0. && d3 * d4 < 0. {
+        return true;
+    }
+    let on_segment = |p: &[f64; 3], q: &[f64; 3], r: &[f64; 3]| {
+        r[0] >= p[0].min(q[0])
+            && r[0] <= p[0].max(q[0])
+            && r[1] >= p[1].min(q[1])
+            && r[1] <= p[1].max(q[1])
+    };
+    (d1 == 0. && !shared(a) && on_segment(c, d, a))
+        || (d2 == 0. && !shared(b) && on_segment(c, d, b))
+        || (d3 == 0. && !shared(c) && on_segment(a, b, c))
+        || (d4 == 0. && !shared(d) && on_segment(a, b, d))
+}
+
+/// Returns `true` if `p` is strictly inside of the `abc` triangle.
+fn in_triangle(p: &[f64; 3], a: &[f64; 3], b: &[f64; 3], c: &[f64; 3]) -> bool {
+    let (d1, d2, d3) = (
+        orientation(a, b, p),
+        orientation(b, c, p),
+        orientation(c, a, p),
+    );
+    (d1 > 0. && d2 > 0. && d3 > 0.) || (d1 < 0. && d2 < 0. && d3 < 0.)
+}
+
+/// Returns `true` if removing the vertex `index` (between `prev` and `next`) of the `current`
+/// coordinates of `all` (whose kept vertices are given by `mask`) doesn't change the topology:
+/// the new segment must not intersect any other one, and no vertex may be inside of the removed
+/// triangle.
+pub(crate) fn vw_keeps_topology(
+    all: &[Vec<[f64; 3]>],
+    current: usize,
+    mask: &[bool],
+    prev: usize,
+    index: usize,
+    next: usize,
+) -> bool {
+    let coords = &all[current];
+    let (a, b, c) = (&coords[prev], &coords[index], &coords[next]);
+    for (k, other) in all.iter().enumerate() {
+        let kept = other
+            .iter()
+            .enumerate()
+            .filter(|&(i, _)| k != current || (mask[i] && i != prev && i != index && i != next))
+            .map(|(_, p)| p)
+            .collect::<Vec<_>>();
+        if kept.iter().any(|p| in_triangle(p, a, b, c)) {
+            return false;
+        }
+        let segments: Vec<(&[f64; 3], &[f64; 3])> = if k == current {
+            let kept_indexes = (0..coords.len()).filter(|&i| mask[i]).collect::<Vec<_>>();
+            kept_indexes
+                .windows(2)
+                .filter(|w| w[0] != prev && w[1] != next)
+                .map(|w| (&coords[w[0]], &coords[w[1]]))
+                .collect()
+        } else {
+            other.windows(2).map(|w| (&w[0], &w[1])).collect()
+        };
+        if segments.iter().any(|(p, q)| segments_intersect(a, c, p, q)) {
+            return false;
+        }
+    }
+    true
+}
+
+/// Returns the length of `coords` up to each of their vertices, divided by their total length
+/// (all zeros for a zero-length line).
+pub(crate) fn arc_fractions(coords: &[[f64; 3]]) -> Vec<f64> {
+    let mut res = Vec::with_capacity(coords.len());
+    let mut total = 0.;
+    for (i, c) in coords.iter().enumerate() {
+        if i > 0 {
+            total += (c[0] - coords[i - 1][0]).hypot(c[1] - coords[i - 1][1]);
+        }
+        res.push(total);
+    }
+    if total > 0. {
+        res.iter_mut().for_each(|f| *f /= total);
+    }
+    res
+}
+
+fn lerp(a: &[f64; 3], b: &[f64; 3], t: f64) -> [f64; 3] {
+    [
+        a[0] + (b[0] - a[0]) * t,
+        a[1] + (b[1] - a[1]) * t,
+        a[2] + (b[2] - a[2]) * t,
+    ]
+}
+
+/// Returns the point at `fraction` of the length of `coords`, `fractions` being their
+/// [`arc_fractions`].
+pub(crate) fn point_at_fraction(coords: &[[f64; 3]], fractions: &[f64], fraction: f64) -> [f64; 3] {
+    let i = match fractions.iter().position(|&f| f >= fraction) {
+        Some(i) => i,
+        None => return coords[coords.len() - 1],
+    };
+    if i == 0 || fractions[i] == fraction {
+        return coords[i];
+    }
+    let r = (fraction - fractions[i - 1]) / (fractions[i] - fractions[i - 1]);
+    lerp(&coords[i - 1], &coords[i], r)
+}
+
+/// Returns `n` points equally spaced along the ring `coords` (starting from its first vertex),
+/// followed by the first one again to close the ring.
+pub(crate) fn resample_ring(coords: &[[f64; 3]], n: usize) -> Vec<[f64; 3]> {
+    let fractions = arc_fractions(coords);
+    let mut res = (0..n)
+        .map(|i| point_at_fraction(coords, &fractions, i as f64 / n as f64))
+        .collect::<Vec<_>>();
+    if let Some(&first) = res.first() {
+        res.push(first);
+    }
+    res
+}
+
+/// Makes the closed ring `coords` counter-clockwise and, if given, starting from its vertex
+/// closest to `start`.
+fn align_ring(coords: &mut Vec<[f64; 3]>, start: Option<&[f64; 3]>) {
+    if signed_ring_area(coords) < 0. {
+        coords.reverse();
+    }
+    let start = match start {
+        Some(start) => start,
+        None => return,
+    };
+    coords.pop();
+    let distance = |c: &[f64; 3]| (c[0] - start[0]).hypot(c[1] - start[1]);
+    let first = (0..coords.len())
+        .min_by(|&i, &j| {
+            distance(&coords[i])
+                .partial_cmp(&distance(&coords[j]))
+                .unwrap_or(Ordering::Equal)
+        })
+        .unwrap_or(0);
+    coords.rotate_left(first);
+    if let Some(&c) = coords.first() {
+        coords.push(c);
+    }
+}
+
+/// Interpolates between the lines (or closed rings) `a` and `b`: their vertices are matched by
+/// their relative position along the lines, the ones of each line being inserted in the other
+/// one, so `t = 0` gives back `a` and `t = 1` gives back `b` (with extra collinear vertices).
+pub(crate) fn morph_coords(a: &[[f64; 3]], b: &[[f64; 3]], closed: bool, t: f64) -> Vec<[f64; 3]> {
+    let (mut a, mut b) = (a.to_vec(), b.to_vec());
+    if closed && !a.is_empty() {
+        align_ring(&mut a, None);
+        align_ring(&mut b, Some(&a[0]));
+    }
+    let (fractions_a, fractions_b) = (arc_fractions(&a), arc_fractions(&b));
+    let mut fractions = fractions_a
+        .iter()
+        .chain(fractions_b.iter())
+        .cloned()
+        .collect::<Vec<_>>();
+    fractions.sort_by(|x, y| x.partial_cmp(y).unwrap_or(Ordering::Equal));
+    fractions.dedup();
+    fractions
+        .iter()
+        .map(|&f| {
+            lerp(
+                &point_at_fraction(&a, &fractions_a, f),
+                &point_at_fraction(&b, &fractions_b, f),
+                t,
+            )
+        })
+        .collect()
+}
+
+/// Rebuilds `geom` with the parts for which `keep` returns `true`, keeping its type and SRID.
+/// A non-collection geometry is its own only part, and becomes empty if it isn't kept.
+pub(crate) fn retain_parts<'a, G, F>(geom: &G, mut keep: F) -> GResult<Geometry<'a>>
+where
+    G: Geom<'a>,
+    F: FnMut(&ConstGeometry<'a, '_>) -> GResult<bool>,
+{
+    if geom.is_empty()? {
+        return Ok(Geom::clone(geom));
+    }
+    let geom_type = geom.geometry_type();
+    let mut parts = Vec::new();
+    // GEOS returns the geometry itself as the only part of a non-collection geometry.
+    for n in 0..geom.get_num_geometries()? {
+        let part = geom.get_geometry_n(n)?;
+        if keep(&part)? {
+            parts.push(Geom::clone(&part));
+        }
+    }
+    let mut res = match geom_type {
+        GeometryTypes::MultiPoint
+        | GeometryTypes::MultiLineString
+        | GeometryTypes::MultiPolygon
+        | GeometryTypes::GeometryCollection => {
+            if parts.is_empty() {
+                Geometry::create_empty_collection(geom_type)?
+            } else {
+                create_multi_geom(parts, geom_type)?
+            }
+        }
+        _ if !parts.is_empty() => parts.remove(0),
+        GeometryTypes::Point => Geometry::create_empty_point()?,
+        GeometryTypes::LineString | GeometryTypes::LinearRing => {
+            Geometry::create_empty_line_string()?
+        }
+        GeometryTypes::Polygon => Geometry::create_empty_polygon()?,
+        geom_type if geom_type.is_curved() => return unsupported_curve(geom_type),
+        _ => return unknown_type(geom_type),
+    };
+    res.set_srid(geom.get_srid()?);
+    Ok(res)
+}
+
+/// Rebuilds the `LineString` or `LinearRing` `geom` from its coordinates edited by `edit` (which
+/// is told if they form a ring).
+pub(crate) fn edit_line_vertices<'a, 'b, G, F>(geom: &G, edit: F) -> GResult<Geometry<'b>>
+where
+    G: Geom<'a>,
+    F: FnOnce(&mut Vec<[f64; 3]>, bool) -> GResult<()>,
+{
+    let closed = match geom.geometry_type() {
+        GeometryTypes::LineString => false,
+        GeometryTypes::LinearRing => true,
+        t => {
+            return Err(Error::GenericError(format!(
+                "expected a LineString or a LinearRing, got a {:?}",
+                t
+            )))
+        }
+    };
+    let seq = geom.get_coord_seq()?;
+    let mut coords = coords_from_seq(&seq)?;
+    edit(&mut coords, closed)?;
+    let seq = seq_from_coords(&coords, seq.dimensions()?)?;
+    if closed {
+        Geometry::create_linear_ring(seq)
+    } else {
+        Geometry::create_line_string(seq)
+    }
+}
+
+/// Rebuilds the `Polygon` `geom`, replacing its ring `ring` (0 being the exterior one) by the
+/// one returned by `edit`.
+pub(crate) fn edit_polygon_ring<'a, G, F>(geom: &G, ring: usize, edit: F) -> GResult<Geometry<'a>>
+where
+    G: Geom<'a>,
+    F: FnOnce(&ConstGeometry<'a, '_>) -> GResult<Geometry<'a>>,
+{
+    if geom.geometry_type() != GeometryTypes::Polygon {
+        return Err(Error::GenericError(format!(
+            "expected a Polygon, got a {:?}",
+            geom.geometry_type()
+        )));
+    }
+    let nb_interiors = if geom.is_empty()? {
+        return Err(Error::InvalidInput("the polygon is empty".to_owned()));
+    } else {
+        geom.get_num_interior_rings()?
+    };
+    if ring > nb_interiors {
+        return Err(Error::InvalidInput(format!(
+            "ring index {} out of bounds (only {} rings)",
+            ring,
+            nb_interiors + 1
+        )));
+    }
+    let mut edit = Some(edit);
+    let mut rebuild = |n: usize, r: ConstGeometry<'a, '_>| -> GResult<Geometry<'a>> {
+        match (n == ring, edit.take()) {
+            (true, Some(edit)) => edit(&r),
+            _ => Ok(Geom::clone(&r)),
+        }
+    };
+    let exterior = rebuild(0, geom.get_exterior_ring()?)?;
+    let mut interiors = Vec::with_capacity(nb_interiors);
+    for n in 0..nb_interiors {
+        interiors.push(rebuild(n + 1, geom.get_interior_ring_n(n as _)?)?);
+    }
+    Geometry::create_polygon(exterior, interiors)
+}
+
+/// Inserts `coord` before the vertex `index` of `coords` (at the end if `index` is the number of
+/// vertices), keeping the ring closed if `closed`. Empty lines and rings are refused, as a
+/// single vertex wouldn't make a valid one.
+pub(crate) fn insert_vertex(
+    coords: &mut Vec<[f64; 3]>,
+    closed: bool,
+    index: usize,
+    coord: [f64; 3],
+) -> GResult<()> {
+    if coords.is_empty() {
+        return Err(Error::InvalidInput(
+            "can't insert a vertex in an empty line or ring".to_owned(),
+        ));
+    }
+    let nb_vertices = if closed {
+        coords.len() - 1
+    } else {
+        coords.len()
+    };
+    if index > nb_vertices {
+        return Err(Error::InvalidInput(format!(
+            "vertex index {} out of bounds (only {} vertices)",
+            index, nb_vertices
+        )));
+    }
+    coords.insert(index, coord);
+    if closed && index == 0 {
+        let last = coords.len() - 1;
+        coords[last] = coord;
+    }
+    Ok(())
+}
+
+/// Removes the vertex `index` of `coords`, keeping the ring closed if `closed`. Lines keep at
+/// least 2 vertices, rings at least 3 (plus the closing one).
+pub(crate) fn remove_vertex(coords: &mut Vec<[f64; 3]>, closed: bool, index: usize) -> GResult<()> {
+    let (nb_vertices, min_vertices) = if closed {
+        (coords.len().saturating_sub(1), 3)
+    } else {
+        (coords.len(), 2)
+    };
+    if index >= nb_vertices {
+        return Err(Error::InvalidInput(format!(
+            "vertex index {} out of bounds (only {} vertices)",
+            index, nb_vertices
+        )));
+    }
+    if nb_vertices <= min_vertices {
+        return Err(Error::InvalidInput(format!(
+            "can't remove a vertex, at least {} are needed",
+            min_vertices
+        )));
+    }
+    coords.remove(index);
+    if closed && index == 0 {
+        let last = coords.len() - 1;
+        coords[last] = coords[0];
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod test {
-    use super::check_geos_predicate;
+    use super::{
+        check_geos_predicate, find_curve_type, insert_vertex, remove_vertex, MAX_WKB_DEPTH,
+    };
     use error::PredicateType;
 
     #[test]
@@ -224,4 +1438,68 @@ mod test {
             "error while calling libgeos method Intersects (error number = 42)".to_string()
         );
     }
+
+    #[test]
+    fn find_curve_type_test() {
+        let wkt = b"GEOMETRYCOLLECTION(POINT(0 0), circularstring(0 0, 1 1, 2 0))";
+        assert_eq!(find_curve_type("WKT", wkt), Some("CircularString"));
+        assert_eq!(find_curve_type("WKT", b"LINESTRING(0 0, 1"), None);
+
+        // MULTIPOINT(0 0) then a COMPOUNDCURVE in a big endian collection.
+        let mut wkb = vec![0u8, 0, 0, 0, 7, 0, 0, 0, 2];
+        wkb.extend(&[1u8, 4, 0, 0, 0, 1, 0, 0, 0]);
+        wkb.extend(&[1u8, 1, 0, 0, 0]);
+        wkb.extend(&[0u8; 16]);
+        wkb.extend(&[1u8, 9, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(find_curve_type("WKB", &wkb), Some("CompoundCurve"));
+        assert_eq!(find_curve_type("WKB", &wkb[..20]), None);
+        assert_eq!(find_curve_type("HEX", b"010A000000"), Some("CurvePolygon"));
+    }
+
+    #[test]
+    fn find_curve_type_depth_test() {
+        // `depth` collections of one geometry each, around a CIRCULARSTRING.
+        fn nested(depth: usize) -> Vec<u8> {
+            let mut wkb = Vec::new();
+            for _ in 0..depth {
+                wkb.extend(&[1u8, 7, 0, 0, 0, 1, 0, 0, 0]);
+            }
+            wkb.extend(&[1u8, 8, 0, 0, 0]);
+            wkb
+        }
+
+        assert_eq!(
+            find_curve_type("WKB", &nested(MAX_WKB_DEPTH - 1)),
+            Some("CircularString")
+        );
+        assert_eq!(find_curve_type("WKB", &nested(MAX_WKB_DEPTH)), None);
+        assert_eq!(find_curve_type("WKB", &nested(100_000)), None);
+    }
+
+    #[test]
+    fn edit_vertices_test() {
+        let nan = f64::NAN;
+        let mut ring = vec![[0., 0., nan], [1., 0., nan], [0., 1., nan], [0., 0., nan]];
+        insert_vertex(&mut ring, true, 0, [-1., -1., nan]).unwrap();
+        assert_eq!(ring[0][..2], [-1., -1.]);
+        assert_eq!(ring[4][..2], [-1., -1.]);
+        assert!(insert_vertex(&mut ring, true, 5, [2., 2., nan]).is_err());
+        remove_vertex(&mut ring, true, 0).unwrap();
+        assert_eq!(ring[0][..2], [0., 0.]);
+        assert_eq!(ring[3][..2], [0., 0.]);
+        assert!(remove_vertex(&mut ring, true, 1).is_err());
+
+        let mut line = vec![[0., 0., nan], [1., 0., nan]];
+        insert_vertex(&mut line, false, 2, [2., 0., nan]).unwrap();
+        assert_eq!(line.len(), 3);
+        remove_vertex(&mut line, false, 1).unwrap();
+        assert_eq!(line[1][..2], [2., 0.]);
+        assert!(remove_vertex(&mut line, false, 0).is_err());
+
+        let mut empty = Vec::new();
+        assert!(insert_vertex(&mut empty, true, 0, [0., 0., nan]).is_err());
+        assert!(insert_vertex(&mut empty, false, 0, [0., 0., nan]).is_err());
+        assert!(remove_vertex(&mut empty, true, 0).is_err());
+        assert!(empty.is_empty());
+    }
 }
@@ -0,0 +1,144 @@
+//! Point-in-polygon lookups against a fixed set of polygons.
+
+use crate::{
+    ContextHandling, CoordSeq, Error, GResult, Geom, Geometry, PreparedGeometry, STRtree,
+    SpatialIndex,
+};
+use bbox::Rect;
+
+/// Index of a fence in the slice given to [`Geofence::new`].
+pub type FenceId = usize;
+
+/// A set of fences (usually polygons), indexed with a [`STRtree`] and prepared, to find quickly
+/// which ones contain a given point.
+///
+/// A point on the boundary of a fence is considered inside of it. Empty fences never match.
+///
+/// # Example
+///
+/// ```
+/// use geos::Geometry;
+/// use geos::geofence::Geofence;
+///
+/// let fences = vec![
+///     Geometry::new_from_wkt("POLYGON((0 0, 10 0, 10 10, 0 10, 0 0))").expect("Invalid geometry"),
+///     Geometry::new_from_wkt("POLYGON((5 5, 15 5, 15 15, 5 15, 5 5))").expect("Invalid geometry"),
+///     Geometry::new_from_wkt("POLYGON((20 0, 30 0, 30 10, 20 0))").expect("Invalid geometry"),
+/// ];
+/// let geofence = Geofence::new(&fences).expect("Geofence::new failed");
+///
+/// assert_eq!(geofence.hit_test(1., 1.), Ok(vec![0]));
+/// assert_eq!(geofence.hit_test(7., 7.), Ok(vec![0, 1]));
+/// assert_eq!(geofence.hit_test(18., 1.), Ok(vec![]));
+/// assert_eq!(geofence.hit_test_batch(&[(12., 12.), (25., 1.)]), Ok(vec![vec![1], vec![2]]));
+/// ```
+pub struct Geofence<'a, G> {
+    fences: &'a [G],
+    prepared: Vec<Option<PreparedGeometry<'a>>>,
+    tree: STRtree<'a, FenceId>,
+}
+
+impl<'a, 'b: 'a, G: Geom<'b>> Geofence<'a, G> {
+    /// Indexes and prepares the given fences.
+    pub fn new(fences: &'a [G]) -> GResult<Geofence<'a, G>> {
+        let mut tree = STRtree::with_capacity(10)?;
+        let mut prepared = Vec::with_capacity(fences.len());
+        for (id, fence) in fences.iter().enumerate() {
+            if fence.is_empty()? {
+                prepared.push(None);
+            } else {
+                tree.insert(fence, id);
+                prepared.push(Some(PreparedGeometry::new(fence)?));
+            }
+        }
+        Ok(Geofence {
+            fences,
+            prepared,
+            tree,
+        })
+    }
+
+    /// Returns the number of fences.
+    pub fn len(&self) -> usize {
+        self.fences.len()
+    }
+
+    /// Returns `true` if there is no fence.
+    pub fn is_empty(&self) -> bool {
+        self.fences.is_empty()
+    }
+
+    fn candidates<'c, Q: Geom<'c>>(&self, query: &Q) -> Vec<FenceId> {
+        let mut candidates = Vec::new();
+        self.tree.query(query, |id: &FenceId| candidates.push(*id));
+        candidates.sort_unstable();
+        candidates
+    }
+
+    /// Returns the sorted ids of the fences containing the `(x, y)` point.
+    pub fn hit_test(&self, x: f64, y: f64) -> GResult<Vec<FenceId>> {
+        let seq = CoordSeq::new_from_vec_with_context(&[[x, y]], self.tree.clone_context())?;
+        let point = Geometry::create_point(seq)?;
+        let mut res = Vec::new();
+        for id in self.candidates(&point) {
+            if let Some(ref prepared) = self.prepared[id] {
+                if prepared.intersects(&point)? {
+                    res.push(id);
+                }
+            }
+        }
+        Ok(res)
+    }
+
+    /// Same as [`hit_test`](#method.hit_test), for each point of `points`.
+    pub fn hit_test_batch(&self, points: &[(f64, f64)]) -> GResult<Vec<Vec<FenceId>>> {
+        points.iter().map(|&(x, y)| self.hit_test(x, y)).collect()
+    }
+
+    /// Returns the sorted ids of the fences within `distance` of the `(x, y)` point, like
+    /// `ST_DWithin` does in PostGIS.
+    ///
+    /// Returns [`Error::InvalidInput`] if `distance` is negative or NaN.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::Geometry;
+    /// use geos::geofence::Geofence;
+    ///
+    /// let fences = vec![
+    ///     Geometry::new_from_wkt("POLYGON((0 0, 10 0, 10 10, 0 10, 0 0))")
+    ///              .expect("Invalid geometry"),
+    ///     Geometry::new_from_wkt("POLYGON((12 0, 20 0, 20 10, 12 10, 12 0))")
+    ///              .expect("Invalid geometry"),
+    /// ];
+    /// let geofence = Geofence::new(&fences).expect("Geofence::new failed");
+    ///
+    /// assert_eq!(geofence.hit_test_within(11., 5., 0.5), Ok(vec![]));
+    /// assert_eq!(geofence.hit_test_within(11., 5., 1.), Ok(vec![0, 1]));
+    /// assert_eq!(geofence.hit_test_within(5., 5., 1.), Ok(vec![0]));
+    /// assert!(geofence.hit_test_within(5., 5., -1.).is_err());
+    /// ```
+    pub fn hit_test_within(&self, x: f64, y: f64, distance: f64) -> GResult<Vec<FenceId>> {
+        if distance.is_nan() || distance < 0. {
+            return Err(Error::InvalidInput(format!(
+                "the distance must be positive, got {}",
+                distance
+            )));
+        }
+        if distance == 0. {
+            return self.hit_test(x, y);
+        }
+        let window =
+            Rect::new(x - distance, y - distance, x + distance, y + distance).to_geometry()?;
+        let seq = CoordSeq::new_from_vec_with_context(&[[x, y]], self.tree.clone_context())?;
+        let point = Geometry::create_point(seq)?;
+        let mut res = Vec::new();
+        for id in self.candidates(&window) {
+            if self.fences[id].distance(&point)? <= distance {
+                res.push(id);
+            }
+        }
+        Ok(res)
+    }
+}
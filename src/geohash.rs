@@ -0,0 +1,107 @@
+use error::{Error, GResult};
+
+const BASE32: &[u8; 32] = b"0123456789bcdefghjkmnpqrstuvwxyz";
+/// A geohash longer than that is already below floating point resolution.
+pub(crate) const MAX_PRECISION: usize = 12;
+
+fn check_precision(precision: usize) -> GResult<()> {
+    if precision == 0 || precision > MAX_PRECISION {
+        return Err(Error::GenericError(format!(
+            "geohash precision must be between 1 and {}, got {}",
+            MAX_PRECISION, precision
+        )));
+    }
+    Ok(())
+}
+
+/// Encodes the given position into a geohash of `precision` characters.
+pub(crate) fn encode(lon: f64, lat: f64, precision: usize) -> GResult<String> {
+    check_precision(precision)?;
+    if !(-180. ..=180.).contains(&lon) || !(-90. ..=90.).contains(&lat) {
+        return Err(Error::GenericError(format!(
+            "({}, {}) is not a valid longitude/latitude position",
+            lon, lat
+        )));
+    }
+    let mut lon_range = (-180., 180.);
+    let mut lat_range = (-90., 90.);
+    let mut hash = String::with_capacity(precision);
+    let mut even = true;
+
+    while hash.len() < precision {
+        let mut idx = 0;
+        for _ in 0..5 {
+            let (range, value) = if even {
+                (&mut lon_range, lon)
+            } else {
+                (&mut lat_range, lat)
+            };
+            let mid = (range.0 + range.1) / 2.;
+            idx <<= 1;
+            if value >= mid {
+                idx |= 1;
+                range.0 = mid;
+            } else {
+                range.1 = mid;
+            }
+            even = !even;
+        }
+        hash.push(BASE32[idx] as char);
+    }
+    Ok(hash)
+}
+
+/// Decodes the given geohash into the `(xmin, ymin, xmax, ymax)` bounds of its cell.
+pub(crate) fn decode_bbox(hash: &str) -> GResult<(f64, f64, f64, f64)> {
+    check_precision(hash.len())?;
+    let mut lon_range = (-180., 180.);
+    let mut lat_range = (-90., 90.);
+    let mut even = true;
+
+    for c in hash.bytes() {
+        let idx = match BASE32.iter().position(|b| *b == c.to_ascii_lowercase()) {
+            Some(idx) => idx,
+            None => {
+                return Err(Error::GenericError(format!(
+                    "invalid character {:?} in geohash \"{}\"",
+                    c as char, hash
+                )))
+            }
+        };
+        for bit in (0..5).rev() {
+            let range = if even { &mut lon_range } else { &mut lat_range };
+            let mid = (range.0 + range.1) / 2.;
+            if idx & (1 << bit) != 0 {
+                range.0 = mid;
+            } else {
+                range.1 = mid;
+            }
+            even = !even;
+        }
+    }
+    Ok((lon_range.0, lat_range.0, lon_range.1, lat_range.1))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{decode_bbox, encode};
+
+    #[test]
+    fn encode_known_positions() {
+        assert_eq!(encode(-5.6, 42.6, 5).unwrap(), "ezs42");
+        assert_eq!(encode(10.40744, 57.64911, 11).unwrap(), "u4pruydqqvj");
+        assert!(encode(0., 0., 0).is_err());
+        assert!(encode(0., 0., 13).is_err());
+        assert!(encode(200., 0., 5).is_err());
+    }
+
+    #[test]
+    fn decode_contains_encoded_position() {
+        let (xmin, ymin, xmax, ymax) = decode_bbox("ezs42").unwrap();
+        assert!(xmin <= -5.6 && -5.6 <= xmax);
+        assert!(ymin <= 42.6 && 42.6 <= ymax);
+        assert_eq!(decode_bbox("EZS42").unwrap(), (xmin, ymin, xmax, ymax));
+        assert!(decode_bbox("ezs4a").is_err());
+        assert!(decode_bbox("").is_err());
+    }
+}
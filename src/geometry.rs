@@ -2,19 +2,41 @@
 use crate::Precision;
 use crate::{
     AsRaw, AsRawMut, ContextHandle, ContextHandling, ContextInteractions, CoordSeq,
-    PreparedGeometry, WKTWriter,
+    GeometryVisitor, PreparedGeometry, Segments, WKBReader, WKBWriter, WKTWriter,
 };
+use bbox;
 use c_vec::CVec;
+use config;
 use context_handle::PtrWrap;
+use deadline::Deadline;
 use enums::*;
 use error::{Error, GResult, PredicateType};
 use functions::*;
+use geohash;
 use geos_sys::*;
+use lenient::LenientCollections;
+use memory;
+use overlay::{self, ChangeMetrics};
+#[cfg(feature = "proj")]
+use proj4rs::{proj::Proj, transform::transform};
 use std::borrow::Borrow;
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::ffi::CString;
+use std::sync::atomic::{self, AtomicU8};
 use std::sync::Arc;
-use std::{self, str};
+use std::time::Duration;
+use std::{self, fmt, str};
+use visitor;
+
+thread_local! {
+    // Reused by `into_wkb_owned` and `from_wkb_owned`, along with their own context, which is
+    // never given to the geometries.
+    static WKB_OWNED_WRITER: RefCell<Option<WKBWriter<'static>>> = const { RefCell::new(None) };
+    static WKB_OWNED_READER: RefCell<Option<WKBReader<'static>>> = const { RefCell::new(None) };
+}
 
 /// Representation of a GEOS geometry.
 ///
@@ -28,11 +50,38 @@ use std::{self, str};
 /// assert_eq!(point_geom.get_x(), Ok(2.5));
 /// assert_eq!(point_geom.get_y(), Ok(3.5));
 /// ```
+///
+/// Geometries can be formatted as WKT, the format precision being used as the rounding
+/// precision:
+///
+/// ```
+/// use geos::Geometry;
+///
+/// let point_geom = Geometry::new_from_wkt("POINT (2.5 3.5)").expect("Invalid geometry");
+/// assert_eq!(format!("{:.2}", point_geom), "POINT (2.50 3.50)");
+/// ```
+///
+/// # Destruction
+///
+/// A geometry holds a reference to its [`ContextHandle`], as do the geometries computed from it
+/// and the [`PreparedGeometry`] borrowing it: a context is only finished once every object using
+/// it has been destroyed, whatever order they're dropped in (at the end of a scope, a thread or
+/// the process). Dropping a geometry can't report errors, [`Geometry::destroy`] can.
 pub struct Geometry<'a> {
     pub(crate) ptr: PtrWrap<*mut GEOSGeometry>,
     pub(crate) context: Arc<ContextHandle<'a>>,
+    /// Result of the last validity check (see `is_valid_cached`), reset when the geometry may
+    /// have been modified.
+    validity: AtomicU8,
 }
 
+const VALIDITY_UNKNOWN: u8 = 0;
+const VALIDITY_VALID: u8 = 1;
+const VALIDITY_INVALID: u8 = 2;
+
+// Each pass of `smooth` doubles the number of vertices.
+const MAX_SMOOTH_ITERATIONS: usize = 10;
+
 // Representation of a GEOS geometry. Since it's only a view over another GEOS geometry data,
 /// only not mutable operations are implemented on it.
 ///
@@ -129,8 +178,9 @@ pub trait Geom<'a>:
     /// assert_eq!(geom1.area(), Ok(60.));
     /// ```
     fn area(&self) -> GResult<f64>;
-    /// Returns a WKT representation of the geometry. It defaults to 2 dimensions output. Use
-    /// [`WKTWriter`] type directly if you want more control.
+    /// Returns a WKT representation of the geometry, with the trailing zeros of the coordinates
+    /// trimmed (unless [`GlobalConfig::wkt_trim`](crate::GlobalConfig) is unset). It defaults to
+    /// 2 dimensions output. Use [`WKTWriter`] type directly if you want more control.
     ///
     /// # Examples
     ///
@@ -139,29 +189,37 @@ pub trait Geom<'a>:
     ///
     /// let point_geom = Geometry::new_from_wkt("POINT (2.5 2.5)")
     ///                           .expect("Invalid geometry");
-    /// assert_eq!(
-    ///     point_geom.to_wkt().unwrap(),
-    ///     "POINT (2.5000000000000000 2.5000000000000000)",
-    /// );
+    /// assert_eq!(point_geom.to_wkt().unwrap(), "POINT (2.5 2.5)");
     ///
     /// // A three dimension point will be output just as a 2 dimension:
     /// let point_geom = Geometry::new_from_wkt("POINT (2.5 2.5 3)")
     ///                           .expect("Invalid geometry");
-    /// assert_eq!(
-    ///     point_geom.to_wkt().unwrap(),
-    ///     "POINT (2.5000000000000000 2.5000000000000000)",
-    /// );
+    /// assert_eq!(point_geom.to_wkt().unwrap(), "POINT (2.5 2.5)");
     ///
     /// // To "fix" it, use `WKTWriter` instead:
     /// let mut wkt_writer = WKTWriter::new()
     ///                                .expect("Failed to create WKTWriter");
     /// wkt_writer.set_output_dimension(OutputDimension::ThreeD);
+    /// wkt_writer.set_trim(true);
+    /// assert_eq!(wkt_writer.write(&point_geom).unwrap(), "POINT Z (2.5 2.5 3)");
+    /// ```
+    fn to_wkt(&self) -> GResult<String>;
+    /// Returns a WKT representation of the geometry like [`to_wkt`](#tymethod.to_wkt) used to,
+    /// with 16 decimals for every coordinate.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let point_geom = Geometry::new_from_wkt("POINT (2.5 2.5)")
+    ///                           .expect("Invalid geometry");
     /// assert_eq!(
-    ///     wkt_writer.write(&point_geom).unwrap(),
-    ///     "POINT Z (2.5000000000000000 2.5000000000000000 3.0000000000000000)",
+    ///     point_geom.to_wkt_legacy().unwrap(),
+    ///     "POINT (2.5000000000000000 2.5000000000000000)",
     /// );
     /// ```
-    fn to_wkt(&self) -> GResult<String>;
+    fn to_wkt_legacy(&self) -> GResult<String>;
     /// Returns a WKT representation of the geometry with the given `precision`. It is a wrapper
     /// around [`WKTWriter::set_rounding_precision`].
     ///
@@ -964,7 +1022,9 @@ pub trait Geom<'a>:
     /// assert_eq!(geom.get_num_interior_rings(), Ok(2));
     /// ```
     fn get_num_interior_rings(&self) -> GResult<usize>;
-    /// Returns the number of coordinates inside `self`.
+    /// Returns the number of coordinates inside `self`, counting the ones of all the parts and
+    /// rings (the closing coordinate of rings included). It doesn't copy them, so it's cheap
+    /// even on big geometries.
     ///
     /// # Example
     ///
@@ -1025,6 +1085,9 @@ pub trait Geom<'a>:
     ///                                                       POLYGON((-7 4.2,-7.1 5,-7.1 4.3,-7 4.2)))")
     ///                     .expect("Invalid geometry");
     /// assert_eq!(geom.get_num_geometries(), Ok(3));
+    ///
+    /// let geom = Geometry::new_from_wkt("GEOMETRYCOLLECTION EMPTY").expect("Invalid geometry");
+    /// assert_eq!(geom.get_num_geometries(), Ok(0));
     /// ```
     fn get_num_geometries(&self) -> GResult<usize>;
     /// Get SRID of `self`.
@@ -1295,7 +1358,7 @@ pub trait Geom<'a>:
     ///     .expect("failed to get third point");
     /// assert_eq!(
     ///     point_nb3.to_wkt().unwrap(),
-    ///     "POINT (3.0000000000000000 3.0000000000000000)",
+    ///     "POINT (3 3)",
     /// );
     /// ```
     fn get_geometry_n<'c>(&'c self, n: usize) -> GResult<ConstGeometry<'a, 'c>>;
@@ -1313,12 +1376,7 @@ pub trait Geom<'a>:
     /// let interior = geom
     ///     .get_interior_ring_n(0)
     ///     .expect("failed to get interior ring");
-    /// assert_eq!(interior.to_wkt().unwrap(),
-    ///            "LINEARRING (1.0000000000000000 1.0000000000000000, \
-    ///                         2.0000000000000000 1.0000000000000000, \
-    ///                         2.0000000000000000 5.0000000000000000, \
-    ///                         1.0000000000000000 5.0000000000000000, \
-    ///                         1.0000000000000000 1.0000000000000000)");
+    /// assert_eq!(interior.to_wkt().unwrap(), "LINEARRING (1 1, 2 1, 2 5, 1 5, 1 1)");
     /// ```
     fn get_interior_ring_n<'c>(&'c self, n: u32) -> GResult<ConstGeometry<'a, 'c>>;
     /// Returns the exterior ring.
@@ -1335,896 +1393,3039 @@ pub trait Geom<'a>:
     /// let exterior = point_geom
     ///     .get_exterior_ring()
     ///     .expect("failed to get exterior ring");
-    /// assert_eq!(exterior.to_wkt().unwrap(),
-    ///            "LINEARRING (0.0000000000000000 0.0000000000000000, \
-    ///                         10.0000000000000000 0.0000000000000000, \
-    ///                         10.0000000000000000 6.0000000000000000, \
-    ///                         0.0000000000000000 6.0000000000000000, \
-    ///                         0.0000000000000000 0.0000000000000000)");
+    /// assert_eq!(exterior.to_wkt().unwrap(), "LINEARRING (0 0, 10 0, 10 6, 0 6, 0 0)");
     /// ```
     fn get_exterior_ring<'c>(&'c self) -> GResult<ConstGeometry<'a, 'c>>;
-}
-
-macro_rules! impl_geom {
-    ($ty_name:ident) => (
-        impl_geom!($ty_name,,);
-    );
-    ($ty_name:ident, $lt:lifetime) => (
-        impl_geom!($ty_name, $lt, original);
-    );
-    ($ty_name:ident, $($lt:lifetime)?, $($field:ident)?) => (
-impl<'a$(, $lt)?> Geom<'a> for $ty_name<'a$(, $lt)?> {
-    fn get_type(&self) -> GResult<String> {
-        unsafe {
-            let ptr = GEOSGeomType_r(self.get_raw_context(), self.as_raw());
-            managed_string(ptr, self.get_context_handle(), "GGeom::get_type")
-        }
-    }
-
-    fn geometry_type(&self) -> GeometryTypes {
-        let type_geom = unsafe { GEOSGeomTypeId_r(self.get_raw_context(), self.as_raw()) as i32 };
-
-        GeometryTypes::try_from(type_geom).expect("Failed to convert to GeometryTypes")
-    }
-
-    fn is_valid(&self) -> bool {
-        unsafe { GEOSisValid_r(self.get_raw_context(), self.as_raw()) == 1 }
-    }
-
-    fn is_valid_reason(&self) -> GResult<String> {
-        unsafe {
-            let ptr = GEOSisValidReason_r(self.get_raw_context(), self.as_raw());
-            managed_string(ptr, self.get_context_handle(), "GGeom::is_valid_reason")
-        }
-    }
-
-    fn get_coord_seq(&self) -> GResult<CoordSeq<'a>> {
-        let type_geom = self.geometry_type();
-        match type_geom {
-            GeometryTypes::Point | GeometryTypes::LineString | GeometryTypes::LinearRing => unsafe {
-                let coord = GEOSGeom_getCoordSeq_r(self.get_raw_context(), self.as_raw());
-                let t = GEOSCoordSeq_clone_r(self.get_raw_context(), coord);
-                let mut size = 0;
-                let mut dims = 0;
-
-                if GEOSCoordSeq_getSize_r(self.get_raw_context(), coord, &mut size) == 0 {
-                    return Err(Error::GenericError("GEOSCoordSeq_getSize_r failed".to_owned()));
-                }
-                if GEOSCoordSeq_getDimensions_r(self.get_raw_context(), coord, &mut dims) == 0 {
-                    return Err(Error::GenericError("GEOSCoordSeq_getDimensions_r failed".to_owned()));
-                }
-                CoordSeq::new_from_raw(t, self.clone_context(), size, dims, "get_coord_seq")
-            },
-            _ => Err(Error::ImpossibleOperation(
-                "Geometry must be a Point, LineString or LinearRing to extract its coordinates"
-                    .into(),
-            )),
-        }
-    }
-
-    fn area(&self) -> GResult<f64> {
-        let mut n = 0.;
-
-        let res = unsafe { GEOSArea_r(self.get_raw_context(), self.as_raw(), &mut n) };
-        if res != 1 {
-            Err(Error::GeosError(format!("area failed with code {}", res)))
-        } else {
-            Ok(n as f64)
-        }
-    }
-
-    fn to_wkt(&self) -> GResult<String> {
-        match WKTWriter::new_with_context(self.clone_context()) {
-            Ok(mut w) => w.write(self),
-            Err(e) => Err(e),
-        }
-    }
-
-    fn to_wkt_precision(&self, precision: u32) -> GResult<String> {
-        unsafe {
-            let writer = GEOSWKTWriter_create_r(self.get_raw_context());
-            GEOSWKTWriter_setRoundingPrecision_r(self.get_raw_context(), writer, precision as _);
-            let c_result = GEOSWKTWriter_write_r(self.get_raw_context(), writer, self.as_raw());
-            GEOSWKTWriter_destroy_r(self.get_raw_context(), writer);
-            managed_string(c_result, self.get_context_handle(), "GResult::to_wkt_precision")
-        }
-    }
-
-    fn is_ring(&self) -> GResult<bool> {
-        let rv = unsafe { GEOSisRing_r(self.get_raw_context(), self.as_raw()) };
-        check_geos_predicate(rv as _, PredicateType::IsRing)
-    }
-
-    fn intersects<'b, G: Geom<'b>>(&self, other: &G) -> GResult<bool> {
-        let ret_val = unsafe {
-            GEOSIntersects_r(self.get_raw_context(), self.as_raw(), other.as_raw())
-        };
-        check_geos_predicate(ret_val as _, PredicateType::Intersects)
-    }
-
-    fn crosses<'b, G: Geom<'b>>(&self, other: &G) -> GResult<bool> {
-        let ret_val = unsafe {
-            GEOSCrosses_r(self.get_raw_context(), self.as_raw(), other.as_raw())
-        };
-        check_geos_predicate(ret_val as _, PredicateType::Crosses)
-    }
-
-    fn disjoint<'b, G: Geom<'b>>(&self, other: &G) -> GResult<bool> {
-        let ret_val = unsafe {
-            GEOSDisjoint_r(self.get_raw_context(), self.as_raw(), other.as_raw())
-        };
-        check_geos_predicate(ret_val as _, PredicateType::Disjoint)
-    }
-
-    fn touches<'b, G: Geom<'b>>(&self, other: &G) -> GResult<bool> {
-        let ret_val = unsafe {
-            GEOSTouches_r(self.get_raw_context(), self.as_raw(), other.as_raw())
-        };
-        check_geos_predicate(ret_val as _, PredicateType::Touches)
-    }
-
-    fn overlaps<'b, G: Geom<'b>>(&self, other: &G) -> GResult<bool> {
-        let ret_val = unsafe {
-            GEOSOverlaps_r(self.get_raw_context(), self.as_raw(), other.as_raw())
-        };
-        check_geos_predicate(ret_val as _, PredicateType::Overlaps)
-    }
-
-    fn within<'b, G: Geom<'b>>(&self, other: &G) -> GResult<bool> {
-        let ret_val = unsafe {
-            GEOSWithin_r(self.get_raw_context(), self.as_raw(), other.as_raw())
-        };
-        check_geos_predicate(ret_val as _, PredicateType::Within)
-    }
-
-    fn equals<'b, G: Geom<'b>>(&self, other: &G) -> GResult<bool> {
-        let ret_val = unsafe {
-            GEOSEquals_r(self.get_raw_context(), self.as_raw(), other.as_raw())
-        };
-        check_geos_predicate(ret_val as _, PredicateType::Equals)
-    }
-
-    fn equals_exact<'b, G: Geom<'b>>(&self, other: &G, precision: f64) -> GResult<bool> {
-        let ret_val = unsafe {
-            GEOSEqualsExact_r(self.get_raw_context(), self.as_raw(), other.as_raw(), precision)
-        };
-        check_geos_predicate(ret_val as _, PredicateType::EqualsExact)
-    }
-
-    fn covers<'b, G: Geom<'b>>(&self, other: &G) -> GResult<bool> {
-        let ret_val = unsafe {
-            GEOSCovers_r(self.get_raw_context(), self.as_raw(), other.as_raw())
-        };
-        check_geos_predicate(ret_val as _, PredicateType::Covers)
-    }
-
-    fn covered_by<'b, G: Geom<'b>>(&self, other: &G) -> GResult<bool> {
-        let ret_val = unsafe {
-            GEOSCoveredBy_r(self.get_raw_context(), self.as_raw(), other.as_raw())
-        };
-        check_geos_predicate(ret_val as _, PredicateType::CoveredBy)
-    }
-
-    fn contains<'b, G: Geom<'b>>(&self, other: &G) -> GResult<bool> {
-        let ret_val = unsafe {
-            GEOSContains_r(self.get_raw_context(), self.as_raw(), other.as_raw())
-        };
-        check_geos_predicate(ret_val as _, PredicateType::Contains)
-    }
-
-    fn buffer(&self, width: f64, quadsegs: i32) -> GResult<Geometry<'a>> {
-        assert!(quadsegs > 0);
-        unsafe {
-            let ptr = GEOSBuffer_r(
-                self.get_raw_context(),
-                self.as_raw(),
-                width,
-                quadsegs as _,
-            );
-            Geometry::new_from_raw(ptr, self.clone_context(), "buffer")
-        }
-    }
-
-    fn is_empty(&self) -> GResult<bool> {
-        let ret_val = unsafe { GEOSisEmpty_r(self.get_raw_context(), self.as_raw()) };
-        check_geos_predicate(ret_val as _, PredicateType::IsEmpty)
-    }
-
-    fn is_simple(&self) -> GResult<bool> {
-        let ret_val = unsafe { GEOSisSimple_r(self.get_raw_context(), self.as_raw()) };
-        check_geos_predicate(ret_val as _, PredicateType::IsSimple)
-    }
-
-    fn difference<'b, G: Geom<'b>>(&self, other: &G) -> GResult<Geometry<'a>> {
-        unsafe {
-            let ptr = GEOSDifference_r(self.get_raw_context(), self.as_raw(), other.as_raw());
-            Geometry::new_from_raw(ptr, self.clone_context(), "difference")
-        }
-    }
-
-    fn envelope(&self) -> GResult<Geometry<'a>> {
-        unsafe {
-            let ptr = GEOSEnvelope_r(self.get_raw_context(), self.as_raw());
-            Geometry::new_from_raw(ptr, self.clone_context(), "envelope")
-        }
-    }
-
-    fn sym_difference<'b, G: Geom<'b>>(&self, other: &G) -> GResult<Geometry<'a>> {
-        unsafe {
-            let ptr = GEOSSymDifference_r(self.get_raw_context(), self.as_raw(), other.as_raw());
-            Geometry::new_from_raw(ptr, self.clone_context(), "sym_difference")
-        }
-    }
-
-    fn union<'b, G: Geom<'b>>(&self, other: &G) -> GResult<Geometry<'a>> {
-        unsafe {
-            let ptr = GEOSUnion_r(self.get_raw_context(), self.as_raw(), other.as_raw());
-            Geometry::new_from_raw(ptr, self.clone_context(), "union")
-        }
-    }
-
-    fn get_centroid(&self) -> GResult<Geometry<'a>> {
-        unsafe {
-            let ptr = GEOSGetCentroid_r(self.get_raw_context(), self.as_raw());
-            Geometry::new_from_raw(ptr, self.clone_context(), "get_centroid")
-        }
-    }
-
-    fn unary_union(&self) -> GResult<Geometry<'a>> {
-        unsafe {
-            let ptr = GEOSUnaryUnion_r(self.get_raw_context(), self.as_raw());
-            Geometry::new_from_raw(ptr, self.clone_context(), "unary_union")
-        }
-    }
-
-    fn voronoi<'b, G: Geom<'b>>(
-        &self,
-        envelope: Option<&G>,
-        tolerance: f64,
-        only_edges: bool,
-    ) -> GResult<Geometry<'a>> {
-        unsafe {
-            let raw_voronoi = GEOSVoronoiDiagram_r(
-                self.get_raw_context(),
-                self.as_raw(),
-                envelope
-                    .map(|e| e.as_raw())
+    /// Returns the geohash of `self`, using at most `precision` characters (between 1 and 12).
+    ///
+    /// For a point, this is the geohash of the cell containing it. For other geometries, this is
+    /// the geohash of the smallest cell containing their whole bounding box, which can therefore
+    /// be shorter than `precision` (or even empty if the bounding box spans several top-level
+    /// cells). Coordinates are expected to be longitude/latitude.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let point = Geometry::new_from_wkt("POINT (10.40744 57.64911)").expect("Invalid geometry");
+    /// assert_eq!(point.to_geohash(11), Ok("u4pruydqqvj".to_owned()));
+    ///
+    /// let line = Geometry::new_from_wkt("LINESTRING (10.40744 57.64911, 10.40745 57.64912)")
+    ///                     .expect("Invalid geometry");
+    /// assert_eq!(line.to_geohash(11), Ok("u4pruydq".to_owned()));
+    /// ```
+    fn to_geohash(&self, precision: usize) -> GResult<String>;
+    /// Returns a densified copy of `self`, where each segment is split into equal sub-segments
+    /// whose length is a `fraction` of the original segment length.
+    ///
+    /// This is the same densification as the one done by [`Geom::hausdorff_distance_densify`]
+    /// and `frechet_distance_densify`, so densifying once and using the non-densifying
+    /// measures afterwards gives the same results.
+    ///
+    /// `fraction` must be in the `(0, 1]` range.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let line = Geometry::new_from_wkt("LINESTRING (0 0, 4 0)").expect("Invalid geometry");
+    /// let dense = line.densify_fraction(0.25).expect("densify_fraction failed");
+    ///
+    /// assert_eq!(dense.to_wkt_precision(1).unwrap(),
+    ///            "LINESTRING (0.0 0.0, 1.0 0.0, 2.0 0.0, 3.0 0.0, 4.0 0.0)");
+    /// assert!(line.densify_fraction(0.).is_err());
+    /// ```
+    fn densify_fraction(&self, fraction: f64) -> GResult<Geometry<'a>>;
+    /// Returns the directed (discrete) Hausdorff distance from `self` to `other`: the greatest
+    /// distance between a vertex of `self` and `other`.
+    ///
+    /// Unlike [`Geom::hausdorff_distance`], it isn't symmetric: it only tells how far `self`
+    /// strays from `other`, which is what matters when matching a short probe against a longer
+    /// reference. Use [`Geom::densify_fraction`] on `self` first for a finer approximation.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let probe = Geometry::new_from_wkt("LINESTRING (0 1, 5 1)").expect("Invalid geometry");
+    /// let reference = Geometry::new_from_wkt("LINESTRING (0 0, 100 0)")
+    ///                          .expect("Invalid geometry");
+    ///
+    /// assert_eq!(probe.directed_hausdorff_distance(&reference), Ok(1.));
+    /// assert!(reference.directed_hausdorff_distance(&probe).unwrap() > 95.);
+    /// ```
+    fn directed_hausdorff_distance<'b, G: Geom<'b>>(&self, other: &G) -> GResult<f64>;
+    /// Returns the envelope of `self` grown by `margin` on every side, as a polygon. A negative
+    /// `margin` shrinks it, as long as the result isn't collapsed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let line = Geometry::new_from_wkt("LINESTRING (0 0, 4 2)").expect("Invalid geometry");
+    /// let window = line.envelope_expanded(1.).expect("envelope_expanded failed");
+    ///
+    /// assert_eq!(window.to_wkt_precision(0).unwrap(),
+    ///            "POLYGON ((-1 -1, 5 -1, 5 3, -1 3, -1 -1))");
+    /// assert!(line.envelope_expanded(-2.).is_err());
+    /// ```
+    fn envelope_expanded(&self, margin: f64) -> GResult<Geometry<'a>>;
+    /// Returns the locations where a line string or a ring of `self` intersects itself, sorted
+    /// and without duplicates. Each line string and ring is checked independently, so a hole
+    /// touching the shell of its polygon isn't reported.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let bowtie = Geometry::new_from_wkt("POLYGON((0 0, 2 2, 2 0, 0 2, 0 0))")
+    ///                       .expect("Invalid geometry");
+    /// assert_eq!(bowtie.self_intersections(), Ok(vec![(1., 1.)]));
+    ///
+    /// let square = Geometry::new_from_wkt("POLYGON((0 0, 2 0, 2 2, 0 2, 0 0))")
+    ///                       .expect("Invalid geometry");
+    /// assert_eq!(square.self_intersections(), Ok(vec![]));
+    /// ```
+    fn self_intersections(&self) -> GResult<Vec<(f64, f64)>>;
+    /// Returns an approximation of the medial axis (or skeleton) of a polygon, as a
+    /// MultiLineString. It can be used to extract the centerline of rivers or roads.
+    ///
+    /// It is built from the Voronoi diagram of the boundary vertices, once densified so that no
+    /// two consecutive vertices are further apart than `densify_distance`: the Voronoi edges
+    /// lying inside the polygon are kept and merged. A smaller `densify_distance` gives a more
+    /// accurate (but more expensive) skeleton.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let road = Geometry::new_from_wkt("POLYGON((0 0, 10 0, 10 2, 0 2, 0 0))")
+    ///                     .expect("Invalid geometry");
+    /// let axis = road.approximate_medial_axis(0.5).expect("approximate_medial_axis failed");
+    ///
+    /// assert_eq!(axis.get_type(), Ok("MultiLineString".to_owned()));
+    /// assert_eq!(axis.within(&road), Ok(true));
+    /// let center = Geometry::new_from_wkt("POINT (5 1)").expect("Invalid geometry");
+    /// assert!(axis.distance(&center).unwrap() < 1e-9);
+    /// ```
+    fn approximate_medial_axis(&self, densify_distance: f64) -> GResult<Geometry<'a>>;
+    /// Returns the longest path through the [`approximate_medial_axis`] of a polygon, as a
+    /// LineString. It follows the polygon along its elongation, which makes it a good support
+    /// to place a curved label.
+    ///
+    /// If the polygon is empty, an empty LineString is returned.
+    ///
+    /// [`approximate_medial_axis`]: #tymethod.approximate_medial_axis
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let lake = Geometry::new_from_wkt("POLYGON((0 0, 10 0, 10 2, 0 2, 0 0))")
+    ///                     .expect("Invalid geometry");
+    /// let path = lake.longest_internal_path(0.5).expect("longest_internal_path failed");
+    ///
+    /// assert_eq!(path.get_type(), Ok("LineString".to_owned()));
+    /// assert_eq!(path.within(&lake), Ok(true));
+    /// // It goes along the lake, through its middle.
+    /// assert!(path.length().unwrap() > 8.);
+    /// let center = Geometry::new_from_wkt("POINT (5 1)").expect("Invalid geometry");
+    /// assert!(path.distance(&center).unwrap() < 1e-9);
+    /// ```
+    fn longest_internal_path(&self, densify_distance: f64) -> GResult<Geometry<'a>>;
+    /// Evaluates the given binary `predicate` between `self` and `other`. It's equivalent to
+    /// calling the method of the same name, e.g. `BinaryPredicate::Within` calls [`within`].
+    ///
+    /// [`within`]: #tymethod.within
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{BinaryPredicate, Geom, Geometry};
+    ///
+    /// let geom1 = Geometry::new_from_wkt("POLYGON((0 0, 10 0, 10 6, 0 6, 0 0))").expect("Invalid geometry");
+    /// let geom2 = Geometry::new_from_wkt("POINT (2.5 2.5)").expect("Invalid geometry");
+    ///
+    /// assert_eq!(geom1.evaluate(&geom2, BinaryPredicate::Contains), Ok(true));
+    /// let predicate = "within".parse().expect("unknown predicate");
+    /// assert_eq!(geom1.evaluate(&geom2, predicate), Ok(false));
+    /// ```
+    fn evaluate<'b, G: Geom<'b>>(&self, other: &G, predicate: BinaryPredicate) -> GResult<bool>;
+    /// Returns a copy of `self` where the X and Y values of every coordinate have been replaced
+    /// by the ones returned by `f`. Z values are kept as is.
+    ///
+    /// As soon as `f` returns an error, the transformation stops and the error is returned. It
+    /// is useful when `f` can fail, like a projection getting a point outside of its domain.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Error, Geom, Geometry};
+    ///
+    /// let geom = Geometry::new_from_wkt("LINESTRING (1 2, 3 4)").expect("Invalid geometry");
+    /// let moved = geom.try_transform_xy(|x, y| Ok((x + 10., y * 2.)))
+    ///                 .expect("try_transform_xy failed");
+    /// assert_eq!(moved.to_wkt_precision(0).unwrap(), "LINESTRING (11 4, 13 8)");
+    ///
+    /// let res = geom.try_transform_xy(|x, y| {
+    ///     if x > 2. {
+    ///         Err(Error::GenericError("out of domain".to_owned()))
+    ///     } else {
+    ///         Ok((x, y))
+    ///     }
+    /// });
+    /// assert!(res.is_err());
+    /// ```
+    fn try_transform_xy<F: FnMut(f64, f64) -> GResult<(f64, f64)>>(
+        &self,
+        f: F,
+    ) -> GResult<Geometry<'a>>;
+    /// Reprojects `self` from the `from` to the `to` coordinate reference systems, given as proj
+    /// strings (like `+proj=longlat +datum=WGS84`). Geographic coordinates are in degrees and the
+    /// axis order follows the `+axis` parameter (longitude first by default).
+    ///
+    /// If a point can't be reprojected (because it's outside of the domain of the projection
+    /// for example), an error is returned.
+    ///
+    /// Available using the `proj` feature. The projections are computed by the
+    /// [proj4rs](https://docs.rs/proj4rs) crate, a pure Rust port of proj4js, rather than by the
+    /// PROJ C library: only the projections of proj4js are supported.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let wgs84 = "+proj=longlat +datum=WGS84 +no_defs";
+    /// let mercator = "+proj=merc +a=6378137 +b=6378137 +lat_ts=0 +lon_0=0 +x_0=0 +y_0=0 +k=1 \
+    ///                 +units=m +no_defs";
+    ///
+    /// let geom = Geometry::new_from_wkt("POINT (10 0)").expect("Invalid geometry");
+    /// let projected = geom.reproject(wgs84, mercator).expect("reproject failed");
+    /// assert!((projected.get_x().unwrap() - 1113194.9079).abs() < 1e-3);
+    ///
+    /// let back = projected.reproject(mercator, wgs84).expect("reproject failed");
+    /// assert!((back.get_x().unwrap() - 10.).abs() < 1e-9);
+    ///
+    /// // The poles can't be represented in Mercator.
+    /// let pole = Geometry::new_from_wkt("POINT (0 90)").expect("Invalid geometry");
+    /// assert!(pole.reproject(wgs84, mercator).is_err());
+    /// ```
+    #[cfg(feature = "proj")]
+    fn reproject(&self, from: &str, to: &str) -> GResult<Geometry<'a>>;
+    /// Returns a copy of `self`, which must be made of longitude/latitude coordinates in
+    /// degrees, where the segments longer than `max_arc_length` (in meters, on a spherical
+    /// Earth) are split into equal sub-segments following the great circle going through their
+    /// extremities.
+    ///
+    /// Do it before reprojecting or running planar operations on long segments, so they follow
+    /// the shortest path on the Earth. Note that the added vertices have longitudes in the
+    /// `[-180, 180]` range, so segments crossing the antimeridian will jump from one side to the
+    /// other.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let geom = Geometry::new_from_wkt("LINESTRING (-60 45, 60 45)").expect("Invalid geometry");
+    /// let densified = geom.densify_geodesic(1_000_000.).expect("densify_geodesic failed");
+    ///
+    /// assert_eq!(densified.get_num_points(), Ok(10));
+    /// // The shortest path goes closer to the pole.
+    /// assert!(densified.get_point_n(4).unwrap().get_y().unwrap() > 60.);
+    /// ```
+    fn densify_geodesic(&self, max_arc_length: f64) -> GResult<Geometry<'a>>;
+    /// Returns a copy of `self` with the X and Y values of every coordinate swapped, to fix data
+    /// read with the wrong axis order (latitude/longitude instead of longitude/latitude for
+    /// example).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let geom = Geometry::new_from_wkt("LINESTRING (48.8 2.3, 45.7 4.8)")
+    ///                    .expect("Invalid geometry");
+    /// let swapped = geom.swap_xy().expect("swap_xy failed");
+    /// assert_eq!(swapped.to_wkt_precision(1).unwrap(), "LINESTRING (2.3 48.8, 4.8 45.7)");
+    /// ```
+    fn swap_xy(&self) -> GResult<Geometry<'a>>;
+    /// Returns `true` if any coordinate of `self` has a NaN or infinite X or Y value, or an
+    /// infinite Z value (a NaN Z value means that there is no Z value for GEOS).
+    ///
+    /// GEOS operations give unpredictable results on such coordinates, see [`scrub_non_finite`]
+    /// to get rid of them.
+    ///
+    /// [`scrub_non_finite`]: #tymethod.scrub_non_finite
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{CoordSeq, Geom, Geometry};
+    ///
+    /// let geom = Geometry::new_from_wkt("LINESTRING (1 2, 3 4)").expect("Invalid geometry");
+    /// assert_eq!(geom.has_non_finite_coords(), Ok(false));
+    ///
+    /// let coords = CoordSeq::new_from_vec(&[&[1., 2.], &[std::f64::NAN, 4.]])
+    ///                       .expect("failed to create CoordSeq");
+    /// let geom = Geometry::create_line_string(coords).expect("Invalid geometry");
+    /// assert_eq!(geom.has_non_finite_coords(), Ok(true));
+    /// ```
+    fn has_non_finite_coords(&self) -> GResult<bool>;
+    /// Returns a copy of `self` where the coordinates having a NaN or infinite X or Y value are
+    /// handled following `policy`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{CoordSeq, Geom, Geometry, NonFinitePolicy};
+    ///
+    /// let coords = CoordSeq::new_from_vec(&[&[1., 2.], &[std::f64::NAN, 4.], &[5., 6.]])
+    ///                       .expect("failed to create CoordSeq");
+    /// let geom = Geometry::create_line_string(coords).expect("Invalid geometry");
+    ///
+    /// let dropped = geom.scrub_non_finite(NonFinitePolicy::DropPoint).expect("scrub failed");
+    /// assert_eq!(dropped.to_wkt_precision(0).unwrap(), "LINESTRING (1 2, 5 6)");
+    /// let replaced = geom.scrub_non_finite(NonFinitePolicy::Replace(0.)).expect("scrub failed");
+    /// assert_eq!(replaced.to_wkt_precision(0).unwrap(), "LINESTRING (1 2, 0 4, 5 6)");
+    /// assert!(geom.scrub_non_finite(NonFinitePolicy::Fail).is_err());
+    /// ```
+    fn scrub_non_finite(&self, policy: NonFinitePolicy) -> GResult<Geometry<'a>>;
+    /// Calls `f` with the X and Y values of every coordinate of `self`, going through all the
+    /// parts and rings.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let geom = Geometry::new_from_wkt("MULTIPOINT ((1 2), (3 5))").expect("Invalid geometry");
+    /// let mut max_y = std::f64::NEG_INFINITY;
+    /// geom.for_each_coord(|_, y| max_y = max_y.max(y)).expect("for_each_coord failed");
+    /// assert_eq!(max_y, 5.);
+    /// ```
+    fn for_each_coord<F: FnMut(f64, f64)>(&self, f: F) -> GResult<()>;
+    /// Returns a copy of `self` where the X and Y values of every coordinate have been replaced
+    /// by the ones returned by `f`. See [`try_transform_xy`] if `f` can fail.
+    ///
+    /// [`try_transform_xy`]: #tymethod.try_transform_xy
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let geom = Geometry::new_from_wkt("LINESTRING (1.26 2.71, 3.14 4.49)")
+    ///                    .expect("Invalid geometry");
+    /// let rounded = geom.map_coords(|x, y| (x.round(), y.round())).expect("map_coords failed");
+    /// assert_eq!(rounded.to_wkt_precision(1).unwrap(), "LINESTRING (1.0 3.0, 3.0 4.0)");
+    /// ```
+    fn map_coords<F: FnMut(f64, f64) -> (f64, f64)>(&self, f: F) -> GResult<Geometry<'a>>;
+    /// Returns the number of coordinates of each part of `self` (see [`get_num_coordinates`]).
+    /// A geometry which isn't a collection has a single part.
+    ///
+    /// [`get_num_coordinates`]: #tymethod.get_num_coordinates
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let geom = Geometry::new_from_wkt("MULTIPOLYGON (((0 0, 1 0, 1 1, 0 0)),\
+    ///                                                 ((5 5, 6 5, 6 6, 5 6, 5 5)))")
+    ///                    .expect("Invalid geometry");
+    /// assert_eq!(geom.get_num_coordinates(), Ok(9));
+    /// assert_eq!(geom.get_num_coordinates_by_part(), Ok(vec![4, 5]));
+    /// ```
+    fn get_num_coordinates_by_part(&self) -> GResult<Vec<usize>>;
+    /// Returns the signed areas of the rings of a polygon, the exterior ring first: they are
+    /// positive for counter-clockwise rings and negative for clockwise ones.
+    ///
+    /// It's useful to check the winding of the rings, or to find holes bigger than their shell.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let geom = Geometry::new_from_wkt("POLYGON((0 0, 0 4, 4 4, 4 0, 0 0),\
+    ///                                            (1 1, 2 1, 2 2, 1 2, 1 1))")
+    ///                    .expect("Invalid geometry");
+    /// assert_eq!(geom.ring_areas(), Ok(vec![-16., 1.]));
+    /// ```
+    fn ring_areas(&self) -> GResult<Vec<f64>>;
+    /// Returns `true` if the exterior ring of a polygon is counter-clockwise, like in GeoJSON
+    /// (shapefiles use clockwise exterior rings). An empty polygon isn't counter-clockwise.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let geom = Geometry::new_from_wkt("POLYGON((0 0, 4 0, 4 4, 0 4, 0 0))")
+    ///                    .expect("Invalid geometry");
+    /// assert_eq!(geom.exterior_is_ccw(), Ok(true));
+    /// ```
+    fn exterior_is_ccw(&self) -> GResult<bool>;
+    /// Same as [`buffer`], but makes sure that the result is valid: if it isn't, the buffer is
+    /// computed again on `self` snapped to a grid of `grid_size` (if positive), and if it's still
+    /// invalid, the result goes through [`make_valid`].
+    ///
+    /// Available using the `v3_8_0` feature.
+    ///
+    /// [`buffer`]: #tymethod.buffer
+    /// [`make_valid`]: #tymethod.make_valid
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let geom = Geometry::new_from_wkt("POINT (1 1)").expect("Invalid geometry");
+    /// let buffered = geom.buffer_checked(2., 8, 1e-6).expect("buffer_checked failed");
+    /// assert!(buffered.is_valid());
+    /// ```
+    #[cfg(any(feature = "v3_8_0", feature = "dox"))]
+    fn buffer_checked(&self, width: f64, quadsegs: i32, grid_size: f64) -> GResult<Geometry<'a>>;
+    /// Same as [`intersection`], but makes sure that the result is valid, like
+    /// [`buffer_checked`] does.
+    ///
+    /// Available using the `v3_8_0` feature.
+    ///
+    /// [`intersection`]: #tymethod.intersection
+    /// [`buffer_checked`]: #tymethod.buffer_checked
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let geom1 = Geometry::new_from_wkt("POLYGON((0 0, 2 0, 2 2, 0 2, 0 0))")
+    ///                      .expect("Invalid geometry");
+    /// let geom2 = Geometry::new_from_wkt("POLYGON((1 1, 3 1, 3 3, 1 3, 1 1))")
+    ///                      .expect("Invalid geometry");
+    /// let res = geom1.intersection_checked(&geom2, 1e-6).expect("intersection_checked failed");
+    /// assert_eq!(res.area(), Ok(1.));
+    /// ```
+    #[cfg(any(feature = "v3_8_0", feature = "dox"))]
+    fn intersection_checked<'b, G: Geom<'b>>(
+        &self,
+        other: &G,
+        grid_size: f64,
+    ) -> GResult<Geometry<'a>>;
+    /// Same as [`union`], but makes sure that the result is valid, like [`buffer_checked`]
+    /// does.
+    ///
+    /// Available using the `v3_8_0` feature.
+    ///
+    /// [`union`]: #tymethod.union
+    /// [`buffer_checked`]: #tymethod.buffer_checked
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let geom1 = Geometry::new_from_wkt("POLYGON((0 0, 2 0, 2 2, 0 2, 0 0))")
+    ///                      .expect("Invalid geometry");
+    /// let geom2 = Geometry::new_from_wkt("POLYGON((1 1, 3 1, 3 3, 1 3, 1 1))")
+    ///                      .expect("Invalid geometry");
+    /// let res = geom1.union_checked(&geom2, 1e-6).expect("union_checked failed");
+    /// assert_eq!(res.area(), Ok(7.));
+    /// ```
+    #[cfg(any(feature = "v3_8_0", feature = "dox"))]
+    fn union_checked<'b, G: Geom<'b>>(&self, other: &G, grid_size: f64) -> GResult<Geometry<'a>>;
+    /// Same as [`difference`], but makes sure that the result is valid, like
+    /// [`buffer_checked`] does.
+    ///
+    /// Available using the `v3_8_0` feature.
+    ///
+    /// [`difference`]: #tymethod.difference
+    /// [`buffer_checked`]: #tymethod.buffer_checked
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let geom1 = Geometry::new_from_wkt("POLYGON((0 0, 2 0, 2 2, 0 2, 0 0))")
+    ///                      .expect("Invalid geometry");
+    /// let geom2 = Geometry::new_from_wkt("POLYGON((1 1, 3 1, 3 3, 1 3, 1 1))")
+    ///                      .expect("Invalid geometry");
+    /// let res = geom1.difference_checked(&geom2, 1e-6).expect("difference_checked failed");
+    /// assert_eq!(res.area(), Ok(3.));
+    /// ```
+    #[cfg(any(feature = "v3_8_0", feature = "dox"))]
+    fn difference_checked<'b, G: Geom<'b>>(
+        &self,
+        other: &G,
+        grid_size: f64,
+    ) -> GResult<Geometry<'a>>;
+    /// Returns a canonical copy of `self`: its X and Y values are rounded to `decimal_places`
+    /// decimals (negative zeros becoming zeros) and it is normalized.
+    ///
+    /// Two geometries with the same shape up to the rounding, the order of their parts, the
+    /// orientation of their rings and their starting points have the same canonical form, with
+    /// identical WKB. It's meant to produce stable output across runs and GEOS versions, for
+    /// hashing or diffing. Z values are kept as is.
+    ///
+    /// A `f64` doesn't have more than 15 significant decimal digits, so greater values of
+    /// `decimal_places` are rejected with an [`Error::InvalidInput`](crate::Error::InvalidInput).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let geom1 = Geometry::new_from_wkt("MULTIPOINT((2 -0.0000001), (0.33333333 1))")
+    ///                      .expect("Invalid geometry");
+    /// let geom2 = Geometry::new_from_wkt("MULTIPOINT((0.333333334 1), (2 0))")
+    ///                      .expect("Invalid geometry");
+    ///
+    /// let canonical1 = geom1.canonicalize(6).expect("canonicalize failed");
+    /// let canonical2 = geom2.canonicalize(6).expect("canonicalize failed");
+    /// assert_eq!(canonical1.to_wkt().unwrap(), "MULTIPOINT (2 0, 0.333333 1)");
+    /// assert_eq!(canonical1.to_wkb().unwrap().as_ref(), canonical2.to_wkb().unwrap().as_ref());
+    /// assert!(geom1.canonicalize(16).is_err());
+    /// ```
+    fn canonicalize(&self, decimal_places: u32) -> GResult<Geometry<'a>>;
+    /// Compares `self` and `other` by their normalized WKB, which gives a total order, arbitrary
+    /// but deterministic. Geometries with the same shape (like two rings with different starting
+    /// points) are equal. Use [`sort_canonical`] to sort many geometries.
+    ///
+    /// It isn't used by `PartialEq`, which relies on [`equals`](#tymethod.equals).
+    ///
+    /// [`sort_canonical`]: fn.sort_canonical.html
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    /// use std::cmp::Ordering;
+    ///
+    /// let ring1 = Geometry::new_from_wkt("LINEARRING (0 0, 1 0, 1 1, 0 0)")
+    ///                      .expect("Invalid geometry");
+    /// let ring2 = Geometry::new_from_wkt("LINEARRING (1 0, 1 1, 0 0, 1 0)")
+    ///                      .expect("Invalid geometry");
+    /// let point = Geometry::new_from_wkt("POINT (0 0)").expect("Invalid geometry");
+    ///
+    /// assert_eq!(ring1.canonical_cmp(&ring2), Ok(Ordering::Equal));
+    /// assert_eq!(point.canonical_cmp(&ring1), ring1.canonical_cmp(&point).map(|o| o.reverse()));
+    /// ```
+    fn canonical_cmp<'b, G: Geom<'b>>(&self, other: &G) -> GResult<Ordering>;
+    /// Returns the part of `self` inside of the given rectangle. It's faster than an
+    /// [`intersection`](#method.intersection) with the rectangle, but the result may be invalid
+    /// (for example, a polygon can get degenerate parts along the rectangle boundary).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let geom = Geometry::new_from_wkt("POLYGON((0 0, 10 0, 10 10, 0 10, 0 0))")
+    ///                     .expect("Invalid geometry");
+    /// let clipped = geom.clip_by_rect(5., 5., 15., 15.).expect("clip_by_rect failed");
+    /// assert_eq!(clipped.area(), Ok(25.));
+    ///
+    /// let line = Geometry::new_from_wkt("LINESTRING(0 0, 10 0)").expect("Invalid geometry");
+    /// let clipped = line.clip_by_rect(5., 5., 15., 15.).expect("clip_by_rect failed");
+    /// assert_eq!(clipped.is_empty(), Ok(true));
+    /// ```
+    fn clip_by_rect(&self, xmin: f64, ymin: f64, xmax: f64, ymax: f64) -> GResult<Geometry<'a>>;
+    /// Returns the segment of the line string closest to the `(x, y)` point, as
+    /// `(segment_index, distance, fraction)`: the segment `segment_index` goes from the point
+    /// `segment_index` to the next one, and `fraction` (between 0 and 1) is the position along it
+    /// of the closest point. If several segments are at the same distance, the first one is
+    /// returned.
+    ///
+    /// Unlike [`project`](#tymethod.project), this tells which segment (and therefore which
+    /// heading) the point is matched to, which is what map-matching needs.
+    ///
+    /// Only works for `LineString` and `LinearRing` geometries with at least 2 points.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let line = Geometry::new_from_wkt("LINESTRING(0 0, 10 0, 10 10)").expect("Invalid geometry");
+    /// assert_eq!(line.closest_segment(2.5, 1.), Ok((0, 1., 0.25)));
+    /// assert_eq!(line.closest_segment(12., 6.), Ok((1, 2., 0.6)));
+    ///
+    /// let point = Geometry::new_from_wkt("POINT(0 0)").expect("Invalid geometry");
+    /// assert!(point.closest_segment(1., 1.).is_err());
+    /// ```
+    fn closest_segment(&self, x: f64, y: f64) -> GResult<(usize, f64, f64)>;
+    /// Returns an iterator over the `((x1, y1), (x2, y2))` segments of the line string. It
+    /// borrows the coordinate sequence of `self` and reads the coordinates lazily, instead of
+    /// copying all of them first.
+    ///
+    /// Only works for `LineString` and `LinearRing` geometries.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{GResult, Geom, Geometry};
+    ///
+    /// let line = Geometry::new_from_wkt("LINESTRING(0 0, 3 4, 3 5)").expect("Invalid geometry");
+    /// let segments = line.segments().expect("segments failed");
+    /// assert_eq!(segments.len(), 2);
+    ///
+    /// let lengths = segments.map(|s| s.map(|((x1, y1), (x2, y2))| (x2 - x1).hypot(y2 - y1)))
+    ///                       .collect::<GResult<Vec<_>>>();
+    /// assert_eq!(lengths, Ok(vec![5., 1.]));
+    /// ```
+    fn segments(&self) -> GResult<Segments<'a, '_>>;
+    /// Smoothes the line strings and the rings of `self` with `iterations` passes of Chaikin's
+    /// corner cutting: each segment is replaced by the two points at a quarter and three quarters
+    /// of its length. The ends of line strings don't move, points are left unchanged.
+    ///
+    /// Each pass doubles the number of vertices, so a few iterations are usually enough: more
+    /// than 10 are rejected with [`Error::InvalidInput`](crate::Error::InvalidInput). Since
+    /// smoothing can make rings cross each other, an error is returned if `self` is valid but the
+    /// result isn't.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let line = Geometry::new_from_wkt("LINESTRING(0 0, 4 0, 4 4)").expect("Invalid geometry");
+    /// let smoothed = line.smooth(1).expect("smooth failed");
+    /// assert_eq!(smoothed.to_wkt().unwrap(), "LINESTRING (0 0, 1 0, 3 0, 4 1, 4 3, 4 4)");
+    ///
+    /// let square = Geometry::new_from_wkt("POLYGON((0 0, 4 0, 4 4, 0 4, 0 0))")
+    ///                       .expect("Invalid geometry");
+    /// let octagon = square.smooth(1).expect("smooth failed");
+    /// assert_eq!(octagon.get_num_coordinates(), Ok(9));
+    /// assert_eq!(octagon.area(), Ok(14.));
+    ///
+    /// assert!(line.smooth(11).is_err());
+    /// ```
+    fn smooth(&self, iterations: usize) -> GResult<Geometry<'a>>;
+    /// Buffers `self` by `width` with mitred joins (and square ends for the lines), keeping sharp
+    /// corners. A corner whose mitre would go farther than `mitre_limit * width` from it is
+    /// bevelled.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let square = Geometry::new_from_wkt("POLYGON((0 0, 10 0, 10 10, 0 10, 0 0))")
+    ///                       .expect("Invalid geometry");
+    /// let buffered = square.buffer_mitred(1., 5.).expect("buffer_mitred failed");
+    /// assert_eq!(buffered.area(), Ok(144.));
+    /// ```
+    fn buffer_mitred(&self, width: f64, mitre_limit: f64) -> GResult<Geometry<'a>>;
+    /// Applies a negative buffer of `distance` to the polygons of `self`, only keeping the parts
+    /// which didn't collapse. Returns `None` if nothing is left, a `MultiPolygon` otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry, GeometryTypes};
+    ///
+    /// let geom = Geometry::new_from_wkt("MULTIPOLYGON(((0 0, 10 0, 10 10, 0 10, 0 0)),
+    ///                                                 ((20 0, 21 0, 21 1, 20 1, 20 0)))")
+    ///                     .expect("Invalid geometry");
+    /// let shrunk = geom.shrink(1.).expect("shrink failed").expect("nothing left");
+    /// assert_eq!(shrunk.geometry_type(), GeometryTypes::MultiPolygon);
+    /// assert_eq!(shrunk.get_num_geometries(), Ok(1));
+    /// assert_eq!(shrunk.area(), Ok(64.));
+    ///
+    /// assert!(geom.shrink(5.).expect("shrink failed").is_none());
+    /// ```
+    fn shrink(&self, distance: f64) -> GResult<Option<Geometry<'a>>>;
+    /// Returns the ring (annulus) of the points whose distance to `self` is between
+    /// `inner_distance` and `outer_distance`: the buffer of `outer_distance` minus the buffer of
+    /// `inner_distance`, both using `quadsegs` segments per quarter circle.
+    ///
+    /// `inner_distance` must be positive (or zero) and smaller than `outer_distance`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let point = Geometry::new_from_wkt("POINT(0 0)").expect("Invalid geometry");
+    /// let ring = point.ring_buffer(1., 2., 16).expect("ring_buffer failed");
+    /// assert_eq!(ring.get_num_interior_rings(), Ok(1));
+    /// assert!((ring.area().unwrap() - 3. * std::f64::consts::PI).abs() < 0.05);
+    ///
+    /// assert!(point.ring_buffer(2., 1., 16).is_err());
+    /// ```
+    fn ring_buffer(
+        &self,
+        inner_distance: f64,
+        outer_distance: f64,
+        quadsegs: i32,
+    ) -> GResult<Geometry<'a>>;
+    /// Returns the non-overlapping bands around `self` between each successive pair of
+    /// `distances`, which must be positive and sorted in increasing order. The first band goes
+    /// from `self` (excluded) to the first distance, so there are as many bands as distances.
+    ///
+    /// Each buffer (using `quadsegs` segments per quarter circle) is only computed once and used
+    /// by the two bands it delimits, so this is about twice as fast as successive calls to
+    /// [`ring_buffer`](#tymethod.ring_buffer).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let square = Geometry::new_from_wkt("POLYGON((0 0, 10 0, 10 10, 0 10, 0 0))")
+    ///                       .expect("Invalid geometry");
+    /// let bands = square.buffer_bands(&[1., 2., 5.], 8).expect("buffer_bands failed");
+    /// assert_eq!(bands.len(), 3);
+    /// // The bands don't overlap and cover the biggest buffer, without the square.
+    /// let total: f64 = bands.iter().map(|b| b.area().unwrap()).sum();
+    /// let outer = square.buffer(5., 8).expect("buffer failed").area().unwrap();
+    /// assert!((total - (outer - 100.)).abs() < 1e-6);
+    /// assert_eq!(bands[0].overlaps(&bands[1]), Ok(false));
+    ///
+    /// assert!(square.buffer_bands(&[2., 1.], 8).is_err());
+    /// ```
+    fn buffer_bands(&self, distances: &[f64], quadsegs: i32) -> GResult<Vec<Geometry<'a>>>;
+    /// Returns a digest of the [canonical form](#tymethod.canonicalize) of `self` (with
+    /// `decimal_places` decimals), meant to be stored as a regression baseline.
+    ///
+    /// It has the `<type>/<number of geometries>/<number of points>/<hash>` form. The hash is
+    /// computed over the coordinates of the canonical geometry by this crate, not over the WKB
+    /// written by GEOS (whose details may change between releases), so it only changes if the
+    /// geometry does.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let geom1 = Geometry::new_from_wkt("POLYGON((0 0, 1 0, 1 1, 0 0))").expect("Invalid geometry");
+    /// // Same polygon, with a different starting point and orientation.
+    /// let geom2 = Geometry::new_from_wkt("POLYGON((1 1, 1 0.0000001, 0 0, 1 1))")
+    ///                      .expect("Invalid geometry");
+    ///
+    /// let signature = geom1.stable_signature(6).expect("stable_signature failed");
+    /// assert!(signature.starts_with("Polygon/1/4/"));
+    /// assert_eq!(geom2.stable_signature(6), Ok(signature));
+    /// ```
+    fn stable_signature(&self, decimal_places: u32) -> GResult<String>;
+    /// Returns the opaque value attached to `self` with
+    /// [`Geometry::set_user_data`](struct.Geometry.html#method.set_user_data), or 0 if there
+    /// is none.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let mut geom = Geometry::new_from_wkt("POINT (2.5 2.5)").expect("Invalid geometry");
+    /// assert_eq!(geom.get_user_data(), 0);
+    /// geom.set_user_data(42);
+    /// assert_eq!(geom.get_user_data(), 42);
+    /// ```
+    fn get_user_data(&self) -> usize;
+    /// Traverses `self` depth first, calling the callbacks of `visitor` on each collection,
+    /// polygon, ring and coordinate sequence. See [`GeometryVisitor`].
+    fn walk<V: GeometryVisitor>(&self, visitor: &mut V) -> GResult<()>;
+    /// Returns a one-line summary of `self` (type, number of parts for the collections, number of
+    /// points and bounding box), much shorter than its WKT on big geometries. It is meant for
+    /// logging, its format may change.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let geom = Geometry::new_from_wkt("MULTIPOLYGON(((0 0, 1 0, 1 1, 0 0)),
+    ///                                                 ((5 5, 6 5, 6 7.5, 5 5)))")
+    ///                     .expect("Invalid geometry");
+    /// assert_eq!(geom.brief(), Ok("MULTIPOLYGON(2 parts, 8 pts, bbox=[0 0, 6 7.5])".to_owned()));
+    ///
+    /// let line = Geometry::new_from_wkt("LINESTRING(0 0, 2 1)").expect("Invalid geometry");
+    /// assert_eq!(line.brief(), Ok("LINESTRING(2 pts, bbox=[0 0, 2 1])".to_owned()));
+    ///
+    /// let empty = Geometry::new_from_wkt("POLYGON EMPTY").expect("Invalid geometry");
+    /// assert_eq!(empty.brief(), Ok("POLYGON EMPTY".to_owned()));
+    /// ```
+    fn brief(&self) -> GResult<String>;
+    /// Returns `self` with its curves converted to line strings.
+    ///
+    /// The GEOS versions handled by this crate don't provide this conversion: curved geometries
+    /// (which only GEOS 3.13 and later can create) get an
+    /// [`UnsupportedCurveType`](crate::Error::UnsupportedCurveType) error, and linear geometries
+    /// are returned unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let geom = Geometry::new_from_wkt("GEOMETRYCOLLECTION(POINT(1 2), LINESTRING(0 0, 1 1))")
+    ///                     .expect("Invalid geometry");
+    /// assert_eq!(geom.linearize().expect("linearize failed").equals(&geom), Ok(true));
+    /// ```
+    fn linearize(&self) -> GResult<Geometry<'a>>;
+    /// Returns the part of the area of `self` covered by `other`, from `0` to `1`
+    /// (`area(self ∩ other) / area(self)`). Geometries without area (including empty ones) give
+    /// `0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let parcel = Geometry::new_from_wkt("POLYGON((0 0, 4 0, 4 4, 0 4, 0 0))")
+    ///                       .expect("Invalid geometry");
+    /// let zone = Geometry::new_from_wkt("POLYGON((1 0, 8 0, 8 4, 1 4, 1 0))")
+    ///                     .expect("Invalid geometry");
+    /// assert_eq!(parcel.overlap_ratio(&zone), Ok(0.75));
+    /// assert_eq!(zone.overlap_ratio(&parcel), Ok(12. / 28.));
+    /// ```
+    fn overlap_ratio<'b, G: Geom<'b>>(&self, other: &G) -> GResult<f64>;
+    /// Returns `true` if at least `threshold` (from `0` to `1`) of the area of `other` is within
+    /// `self`, a "mostly within" test which tolerates the slivers left by imprecise boundaries.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let zone = Geometry::new_from_wkt("POLYGON((1 0, 8 0, 8 4, 1 4, 1 0))")
+    ///                     .expect("Invalid geometry");
+    /// let parcel = Geometry::new_from_wkt("POLYGON((0 0, 4 0, 4 4, 0 4, 0 0))")
+    ///                       .expect("Invalid geometry");
+    /// assert_eq!(zone.contains_fraction(&parcel, 0.7), Ok(true));
+    /// assert_eq!(zone.contains_fraction(&parcel, 0.9), Ok(false));
+    /// assert!(zone.contains_fraction(&parcel, 1.5).is_err());
+    /// ```
+    fn contains_fraction<'b, G: Geom<'b>>(&self, other: &G, threshold: f64) -> GResult<bool>;
+    /// Returns the largest part of a collection: the one with the greatest area, or length if
+    /// none of them has an area (points can't be compared, so the first one is kept). Parts of
+    /// the highest dimension are always preferred, and ties keep the first part. Other geometries
+    /// (and empty collections) are returned unchanged. The SRID of `self` is kept.
+    ///
+    /// This is handy to drop the slivers left by an overlay.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let geom = Geometry::new_from_wkt("MULTIPOLYGON(((0 0, 0.1 0, 0.1 5, 0 0)),
+    ///                                                 ((1 0, 3 0, 3 2, 1 2, 1 0)))")
+    ///                     .expect("Invalid geometry");
+    /// let largest = geom.largest_part().expect("largest_part failed");
+    /// assert_eq!(largest.to_wkt().unwrap(), "POLYGON ((1 0, 3 0, 3 2, 1 2, 1 0))");
+    ///
+    /// let geom = Geometry::new_from_wkt("GEOMETRYCOLLECTION(LINESTRING(0 0, 9 0),
+    ///                                                       POLYGON((0 0, 1 0, 1 1, 0 0)))")
+    ///                     .expect("Invalid geometry");
+    /// let largest = geom.largest_part().expect("largest_part failed");
+    /// assert_eq!(largest.to_wkt().unwrap(), "POLYGON ((0 0, 1 0, 1 1, 0 0))");
+    ///
+    /// let empty = Geometry::new_from_wkt("MULTIPOLYGON EMPTY").expect("Invalid geometry");
+    /// assert_eq!(empty.largest_part().unwrap().to_wkt().unwrap(), "MULTIPOLYGON EMPTY");
+    /// ```
+    fn largest_part(&self) -> GResult<Geometry<'a>>;
+    /// Returns `self` with only the parts for which `keep` returns `true`, keeping its type (an
+    /// empty collection if no part is kept) and its SRID. A geometry which isn't a collection is
+    /// its own only part, and is replaced by an empty geometry of the same type if not kept.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let geom = Geometry::new_from_wkt("MULTIPOINT(0 0, 5 1, 2 7)").expect("Invalid geometry");
+    /// let filtered = geom.filter_parts(|part| part.get_y().unwrap_or(0.) > 0.5)
+    ///                    .expect("filter_parts failed");
+    /// assert_eq!(filtered.to_wkt().unwrap(), "MULTIPOINT (5 1, 2 7)");
+    ///
+    /// let filtered = geom.filter_parts(|_| false).expect("filter_parts failed");
+    /// assert_eq!(filtered.to_wkt().unwrap(), "MULTIPOINT EMPTY");
+    /// ```
+    fn filter_parts<F: FnMut(&ConstGeometry<'a, '_>) -> bool>(
+        &self,
+        keep: F,
+    ) -> GResult<Geometry<'a>>;
+    /// Returns `self` without the polygons whose area is smaller than `min_area`, like the
+    /// slivers left by an overlay. Lines are compared by length instead, and points are always
+    /// kept. See [`filter_parts`](#tymethod.filter_parts) for the other policies.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let geom = Geometry::new_from_wkt("GEOMETRYCOLLECTION(POLYGON((0 0, 0.1 0, 0.1 5, 0 0)),
+    ///                                                       POLYGON((1 0, 3 0, 3 2, 1 2, 1 0)),
+    ///                                                       LINESTRING(0 0, 0 0.5),
+    ///                                                       POINT(4 4))")
+    ///                     .expect("Invalid geometry");
+    /// let cleaned = geom.drop_parts_smaller_than(1.).expect("drop_parts_smaller_than failed");
+    /// assert_eq!(cleaned.to_wkt().unwrap(),
+    ///            "GEOMETRYCOLLECTION (POLYGON ((1 0, 3 0, 3 2, 1 2, 1 0)), POINT (4 4))");
+    /// ```
+    fn drop_parts_smaller_than(&self, min_area: f64) -> GResult<Geometry<'a>>;
+    /// Measures the changes from `self` (the old version) to `other` (the new one), both being
+    /// polygonal geometries. The three areas come from a single
+    /// [`decompose_pair`](overlay/fn.decompose_pair.html) instead of three overlay operations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let before = Geometry::new_from_wkt("POLYGON((0 0, 2 0, 2 2, 0 2, 0 0))")
+    ///                       .expect("Invalid geometry");
+    /// let after = Geometry::new_from_wkt("POLYGON((0 0, 3 0, 3 1, 0 1, 0 0))")
+    ///                      .expect("Invalid geometry");
+    ///
+    /// let metrics = before.change_metrics(&after).expect("change_metrics failed");
+    /// assert_eq!(metrics.added_area, 1.);
+    /// assert_eq!(metrics.removed_area, 2.);
+    /// assert_eq!(metrics.unchanged_area, 2.);
+    /// assert_eq!(metrics.hausdorff_distance, before.hausdorff_distance(&after).unwrap());
+    /// ```
+    fn change_metrics<'b, G: Geom<'b>>(&self, other: &G) -> GResult<ChangeMetrics>;
+    /// Returns the shape at `t` (from `0` for `self` to `1` for `other`) of a linear morphing
+    /// from `self` to `other`, to animate a boundary or estimate it between two dates.
+    ///
+    /// Both geometries must be of the same type: `Point`, `LineString`, `LinearRing` or
+    /// `Polygon`. Their vertices are matched by their relative position along the lines, after
+    /// orienting the rings counter-clockwise and starting the ring of `other` from its vertex
+    /// closest to the start of the one of `self`. Only the exterior rings of polygons are
+    /// interpolated, so the holes are dropped. The SRID of `self` is kept.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let from = Geometry::new_from_wkt("LINESTRING(0 0, 10 0)").expect("Invalid geometry");
+    /// let to = Geometry::new_from_wkt("LINESTRING(0 10, 5 20, 10 10)").expect("Invalid geometry");
+    ///
+    /// let middle = from.interpolate_towards(&to, 0.5).expect("interpolate_towards failed");
+    /// assert_eq!(middle.to_wkt().unwrap(), "LINESTRING (0 5, 5 10, 10 5)");
+    ///
+    /// let square = Geometry::new_from_wkt("POLYGON((0 0, 2 0, 2 2, 0 2, 0 0))")
+    ///                       .expect("Invalid geometry");
+    /// let moved = Geometry::new_from_wkt("POLYGON((4 4, 4 6, 6 6, 6 4, 4 4))")
+    ///                      .expect("Invalid geometry");
+    /// let middle = square.interpolate_towards(&moved, 0.5).expect("interpolate_towards failed");
+    /// assert_eq!(middle.to_wkt().unwrap(), "POLYGON ((2 2, 4 2, 4 4, 2 4, 2 2))");
+    ///
+    /// assert!(square.interpolate_towards(&from, 0.5).is_err());
+    /// ```
+    fn interpolate_towards<'b, G: Geom<'b>>(&self, other: &G, t: f64) -> GResult<Geometry<'a>>;
+    /// Returns `self` (a `Polygon`) with its exterior ring replaced by `n` vertices equally
+    /// spaced along it, starting from its first vertex (plus the closing one). The orientation
+    /// of the ring is kept, and so are the interior rings, which the new exterior ring might now
+    /// cross if `n` is too small.
+    ///
+    /// Rings made of the same number of vertices are easier to compare, as needed by shape
+    /// descriptors or [`interpolate_towards`](#tymethod.interpolate_towards).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let polygon = Geometry::new_from_wkt("POLYGON((0 0, 4 0, 4 4, 0 4, 0 0))")
+    ///                       .expect("Invalid geometry");
+    /// let resampled = polygon.resample_exterior(8).expect("resample_exterior failed");
+    /// assert_eq!(resampled.to_wkt().unwrap(),
+    ///            "POLYGON ((0 0, 2 0, 4 0, 4 2, 4 4, 2 4, 0 4, 0 2, 0 0))");
+    ///
+    /// assert!(polygon.resample_exterior(2).is_err());
+    /// ```
+    fn resample_exterior(&self, n: usize) -> GResult<Geometry<'a>>;
+    /// Returns the [minimum rotated rectangle](#tymethod.minimum_rotated_rectangle) of `self`
+    /// with the angle of its longest side, in degrees counter-clockwise from the X axis, between
+    /// `0` (included) and `180` (excluded). It's the main orientation of a building footprint,
+    /// or the rotation to give to a label.
+    ///
+    /// For degenerate inputs the rectangle can be a line (whose angle is returned) or a point
+    /// (with a `0` angle).
+    ///
+    /// Available using the `v3_6_0` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let geom = Geometry::new_from_wkt("POLYGON((0 0, 4 4, 3 5, -1 1, 0 0))")
+    ///                     .expect("Invalid geometry");
+    /// let (rectangle, angle) = geom.oriented_bbox().expect("oriented_bbox failed");
+    /// assert_eq!(rectangle.area().map(|a| a.round()), Ok(8.));
+    /// assert!((angle - 45.).abs() < 1e-6);
+    /// ```
+    #[cfg(any(feature = "v3_6_0", feature = "dox"))]
+    fn oriented_bbox(&self) -> GResult<(Geometry<'a>, f64)>;
+    /// Returns a wrapper running operations on `self` which are interrupted (with an
+    /// [`Error::TimedOut`](crate::Error::TimedOut)) if they're still running after `timeout`,
+    /// which keeps untrusted inputs from pinning a CPU. See [`Deadline`](crate::Deadline).
+    ///
+    /// Warning: GEOS only has a process-wide interruption flag, so the operations running on
+    /// other threads when a deadline passes can fail spuriously.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    /// use std::time::Duration;
+    ///
+    /// let geom = Geometry::new_from_wkt("POINT(0 0)").expect("Invalid geometry");
+    /// let buffer = geom.with_deadline(Duration::from_secs(1)).buffer(10., 8).expect("timed out");
+    /// assert_eq!(buffer.geometry_type(), geos::GeometryTypes::Polygon);
+    /// ```
+    fn with_deadline(&self, timeout: Duration) -> Deadline<'_, Self>
+    where
+        Self: Sized;
+    /// Returns a wrapper evaluating the predicates on `self` even when GEOS can't, because of a
+    /// `GeometryCollection`: they are then evaluated on the parts of the collections. See
+    /// [`LenientCollections`](crate::LenientCollections).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let collection = Geometry::new_from_wkt("GEOMETRYCOLLECTION(POINT(0 0), LINESTRING(1 1, 2 2))")
+    ///                           .expect("Invalid geometry");
+    /// let point = Geometry::new_from_wkt("POINT(1.5 1.5)").expect("Invalid geometry");
+    /// assert_eq!(collection.lenient_collections().intersects(&point), Ok(true));
+    /// assert_eq!(collection.lenient_collections().disjoint(&point), Ok(false));
+    /// ```
+    fn lenient_collections(&self) -> LenientCollections<'_, Self>
+    where
+        Self: Sized;
+    /// Runs the overlay `op` between `self` and `other` with the given `algorithm`, to opt into
+    /// OverlayNG explicitly (with a fixed precision model if needed) whatever GEOS uses by
+    /// default.
+    ///
+    /// Available using the `v3_9_0` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry, OverlayAlgorithm, OverlayOp};
+    ///
+    /// let geom1 = Geometry::new_from_wkt("POLYGON((0 0, 10 0, 10 10, 0 10, 0 0))")
+    ///                      .expect("Invalid geometry");
+    /// let geom2 = Geometry::new_from_wkt("POLYGON((5.2 5.2, 15 5.2, 15 15, 5.2 15, 5.2 5.2))")
+    ///                      .expect("Invalid geometry");
+    ///
+    /// let ng = OverlayAlgorithm::Ng { grid_size: 1. };
+    /// let snapped = geom1.overlay(&geom2, OverlayOp::Intersection, ng).expect("overlay failed");
+    /// assert_eq!(snapped.area(), Ok(25.));
+    /// let exact = geom1.overlay(&geom2, OverlayOp::Intersection, OverlayAlgorithm::Default)
+    ///                  .expect("overlay failed");
+    /// assert!((exact.area().unwrap() - 23.04).abs() < 1e-9);
+    /// ```
+    #[cfg(any(feature = "v3_9_0", feature = "dox"))]
+    fn overlay<'b, G: Geom<'b>>(
+        &self,
+        other: &G,
+        op: OverlayOp,
+        algorithm: OverlayAlgorithm,
+    ) -> GResult<Geometry<'a>>;
+    /// Same as [`intersection`](#tymethod.intersection), computed with OverlayNG on a grid of
+    /// cells of `grid_size` (a zero `grid_size` keeps the floating precision).
+    ///
+    /// Available using the `v3_9_0` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let geom1 = Geometry::new_from_wkt("LINESTRING(0 0, 10.4 0)").expect("Invalid geometry");
+    /// let geom2 = Geometry::new_from_wkt("LINESTRING(5.2 0, 20 0)").expect("Invalid geometry");
+    ///
+    /// let intersection = geom1.intersection_prec(&geom2, 1.).expect("intersection_prec failed");
+    /// assert_eq!(intersection.to_wkt_precision(0).unwrap(), "LINESTRING (5 0, 10 0)");
+    /// ```
+    #[cfg(any(feature = "v3_9_0", feature = "dox"))]
+    fn intersection_prec<'b, G: Geom<'b>>(
+        &self,
+        other: &G,
+        grid_size: f64,
+    ) -> GResult<Geometry<'a>>;
+    /// Same as [`union`](#tymethod.union), computed with OverlayNG on a grid of cells of
+    /// `grid_size` (a zero `grid_size` keeps the floating precision).
+    ///
+    /// Available using the `v3_9_0` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let geom1 = Geometry::new_from_wkt("POINT(0.2 0.2)").expect("Invalid geometry");
+    /// let geom2 = Geometry::new_from_wkt("POINT(-0.2 -0.2)").expect("Invalid geometry");
+    ///
+    /// let union = geom1.union_prec(&geom2, 1.).expect("union_prec failed");
+    /// assert_eq!(union.to_wkt_precision(0).unwrap(), "POINT (0 0)");
+    /// ```
+    #[cfg(any(feature = "v3_9_0", feature = "dox"))]
+    fn union_prec<'b, G: Geom<'b>>(&self, other: &G, grid_size: f64) -> GResult<Geometry<'a>>;
+    /// Same as [`difference`](#tymethod.difference), computed with OverlayNG on a grid of cells
+    /// of `grid_size` (a zero `grid_size` keeps the floating precision).
+    ///
+    /// Available using the `v3_9_0` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let geom1 = Geometry::new_from_wkt("LINESTRING(0 0, 10 0)").expect("Invalid geometry");
+    /// let geom2 = Geometry::new_from_wkt("LINESTRING(5.2 0, 20 0)").expect("Invalid geometry");
+    ///
+    /// let difference = geom1.difference_prec(&geom2, 1.).expect("difference_prec failed");
+    /// assert_eq!(difference.to_wkt_precision(0).unwrap(), "LINESTRING (0 0, 5 0)");
+    /// ```
+    #[cfg(any(feature = "v3_9_0", feature = "dox"))]
+    fn difference_prec<'b, G: Geom<'b>>(&self, other: &G, grid_size: f64) -> GResult<Geometry<'a>>;
+    /// Same as [`sym_difference`](#tymethod.sym_difference), computed with OverlayNG on a grid
+    /// of cells of `grid_size` (a zero `grid_size` keeps the floating precision).
+    ///
+    /// Available using the `v3_9_0` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let geom1 = Geometry::new_from_wkt("LINESTRING(0 0, 10 0)").expect("Invalid geometry");
+    /// let geom2 = Geometry::new_from_wkt("LINESTRING(0.2 0, 10.2 0)").expect("Invalid geometry");
+    ///
+    /// let sym_difference = geom1.sym_difference_prec(&geom2, 1.)
+    ///                           .expect("sym_difference_prec failed");
+    /// assert_eq!(sym_difference.is_empty(), Ok(true));
+    /// ```
+    #[cfg(any(feature = "v3_9_0", feature = "dox"))]
+    fn sym_difference_prec<'b, G: Geom<'b>>(
+        &self,
+        other: &G,
+        grid_size: f64,
+    ) -> GResult<Geometry<'a>>;
+    /// Same as [`unary_union`](#tymethod.unary_union), computed with OverlayNG on a grid of
+    /// cells of `grid_size` (a zero `grid_size` keeps the floating precision).
+    ///
+    /// Available using the `v3_9_0` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let geom = Geometry::new_from_wkt("MULTIPOINT(0.2 0.2, -0.2 -0.2, 3 3)")
+    ///                     .expect("Invalid geometry");
+    ///
+    /// let union = geom.unary_union_prec(1.).expect("unary_union_prec failed");
+    /// assert_eq!(union.get_num_geometries(), Ok(2));
+    /// ```
+    #[cfg(any(feature = "v3_9_0", feature = "dox"))]
+    fn unary_union_prec(&self, grid_size: f64) -> GResult<Geometry<'a>>;
+}
+
+macro_rules! impl_geom {
+    ($ty_name:ident) => (
+        impl_geom!($ty_name,,);
+    );
+    ($ty_name:ident, $lt:lifetime) => (
+        impl_geom!($ty_name, $lt, original);
+    );
+    ($ty_name:ident, $($lt:lifetime)?, $($field:ident)?) => (
+impl<'a$(, $lt)?> Geom<'a> for $ty_name<'a$(, $lt)?> {
+    fn get_type(&self) -> GResult<String> {
+        unsafe {
+            let ptr = GEOSGeomType_r(self.get_raw_context(), self.as_raw());
+            managed_string(ptr, self.get_context_handle(), "GGeom::get_type")
+        }
+    }
+
+    fn geometry_type(&self) -> GeometryTypes {
+        let type_geom = unsafe { GEOSGeomTypeId_r(self.get_raw_context(), self.as_raw()) as i32 };
+
+        GeometryTypes::try_from(type_geom).expect("Failed to convert to GeometryTypes")
+    }
+
+    fn is_valid(&self) -> bool {
+        unsafe { GEOSisValid_r(self.get_raw_context(), self.as_raw()) == 1 }
+    }
+
+    fn is_valid_reason(&self) -> GResult<String> {
+        unsafe {
+            let ptr = GEOSisValidReason_r(self.get_raw_context(), self.as_raw());
+            managed_string(ptr, self.get_context_handle(), "GGeom::is_valid_reason")
+        }
+    }
+
+    fn get_coord_seq(&self) -> GResult<CoordSeq<'a>> {
+        let type_geom = self.geometry_type();
+        match type_geom {
+            GeometryTypes::Point | GeometryTypes::LineString | GeometryTypes::LinearRing => unsafe {
+                let coord = GEOSGeom_getCoordSeq_r(self.get_raw_context(), self.as_raw());
+                let t = GEOSCoordSeq_clone_r(self.get_raw_context(), coord);
+                let mut size = 0;
+                let mut dims = 0;
+
+                if GEOSCoordSeq_getSize_r(self.get_raw_context(), coord, &mut size) == 0 {
+                    return Err(Error::GenericError("GEOSCoordSeq_getSize_r failed".to_owned()));
+                }
+                if GEOSCoordSeq_getDimensions_r(self.get_raw_context(), coord, &mut dims) == 0 {
+                    return Err(Error::GenericError("GEOSCoordSeq_getDimensions_r failed".to_owned()));
+                }
+                CoordSeq::new_from_raw(t, self.clone_context(), size, dims, "get_coord_seq")
+            },
+            _ => Err(Error::ImpossibleOperation(
+                "Geometry must be a Point, LineString or LinearRing to extract its coordinates"
+                    .into(),
+            )),
+        }
+    }
+
+    fn area(&self) -> GResult<f64> {
+        let mut n = 0.;
+
+        let res = unsafe { GEOSArea_r(self.get_raw_context(), self.as_raw(), &mut n) };
+        if res != 1 {
+            Err(Error::Operation {
+                op: "area",
+                message: self.clone_context().get_last_error().unwrap_or_default(),
+            })
+        } else {
+            Ok(n as f64)
+        }
+    }
+
+    fn to_wkt(&self) -> GResult<String> {
+        match WKTWriter::new_with_context(self.clone_context()) {
+            Ok(mut w) => {
+                w.set_trim(config::wkt_trim());
+                w.write(self)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    fn to_wkt_legacy(&self) -> GResult<String> {
+        match WKTWriter::new_with_context(self.clone_context()) {
+            Ok(mut w) => w.write(self),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn to_wkt_precision(&self, precision: u32) -> GResult<String> {
+        unsafe {
+            let writer = GEOSWKTWriter_create_r(self.get_raw_context());
+            GEOSWKTWriter_setRoundingPrecision_r(self.get_raw_context(), writer, precision as _);
+            let c_result = GEOSWKTWriter_write_r(self.get_raw_context(), writer, self.as_raw());
+            GEOSWKTWriter_destroy_r(self.get_raw_context(), writer);
+            managed_string(c_result, self.get_context_handle(), "GResult::to_wkt_precision")
+        }
+    }
+
+    fn is_ring(&self) -> GResult<bool> {
+        let rv = unsafe { GEOSisRing_r(self.get_raw_context(), self.as_raw()) };
+        check_geos_predicate_in(rv as _, PredicateType::IsRing, self)
+    }
+
+    fn intersects<'b, G: Geom<'b>>(&self, other: &G) -> GResult<bool> {
+        let ret_val = unsafe {
+            GEOSIntersects_r(self.get_raw_context(), self.as_raw(), other.as_raw())
+        };
+        check_geos_predicate_in(ret_val as _, PredicateType::Intersects, self)
+    }
+
+    fn crosses<'b, G: Geom<'b>>(&self, other: &G) -> GResult<bool> {
+        let ret_val = unsafe {
+            GEOSCrosses_r(self.get_raw_context(), self.as_raw(), other.as_raw())
+        };
+        check_geos_predicate_in(ret_val as _, PredicateType::Crosses, self)
+    }
+
+    fn disjoint<'b, G: Geom<'b>>(&self, other: &G) -> GResult<bool> {
+        let ret_val = unsafe {
+            GEOSDisjoint_r(self.get_raw_context(), self.as_raw(), other.as_raw())
+        };
+        check_geos_predicate_in(ret_val as _, PredicateType::Disjoint, self)
+    }
+
+    fn touches<'b, G: Geom<'b>>(&self, other: &G) -> GResult<bool> {
+        let ret_val = unsafe {
+            GEOSTouches_r(self.get_raw_context(), self.as_raw(), other.as_raw())
+        };
+        check_geos_predicate_in(ret_val as _, PredicateType::Touches, self)
+    }
+
+    fn overlaps<'b, G: Geom<'b>>(&self, other: &G) -> GResult<bool> {
+        let ret_val = unsafe {
+            GEOSOverlaps_r(self.get_raw_context(), self.as_raw(), other.as_raw())
+        };
+        check_geos_predicate_in(ret_val as _, PredicateType::Overlaps, self)
+    }
+
+    fn within<'b, G: Geom<'b>>(&self, other: &G) -> GResult<bool> {
+        let ret_val = unsafe {
+            GEOSWithin_r(self.get_raw_context(), self.as_raw(), other.as_raw())
+        };
+        check_geos_predicate_in(ret_val as _, PredicateType::Within, self)
+    }
+
+    fn equals<'b, G: Geom<'b>>(&self, other: &G) -> GResult<bool> {
+        let ret_val = unsafe {
+            GEOSEquals_r(self.get_raw_context(), self.as_raw(), other.as_raw())
+        };
+        check_geos_predicate_in(ret_val as _, PredicateType::Equals, self)
+    }
+
+    fn equals_exact<'b, G: Geom<'b>>(&self, other: &G, precision: f64) -> GResult<bool> {
+        let ret_val = unsafe {
+            GEOSEqualsExact_r(self.get_raw_context(), self.as_raw(), other.as_raw(), precision)
+        };
+        check_geos_predicate_in(ret_val as _, PredicateType::EqualsExact, self)
+    }
+
+    fn covers<'b, G: Geom<'b>>(&self, other: &G) -> GResult<bool> {
+        let ret_val = unsafe {
+            GEOSCovers_r(self.get_raw_context(), self.as_raw(), other.as_raw())
+        };
+        check_geos_predicate_in(ret_val as _, PredicateType::Covers, self)
+    }
+
+    fn covered_by<'b, G: Geom<'b>>(&self, other: &G) -> GResult<bool> {
+        let ret_val = unsafe {
+            GEOSCoveredBy_r(self.get_raw_context(), self.as_raw(), other.as_raw())
+        };
+        check_geos_predicate_in(ret_val as _, PredicateType::CoveredBy, self)
+    }
+
+    fn contains<'b, G: Geom<'b>>(&self, other: &G) -> GResult<bool> {
+        let ret_val = unsafe {
+            GEOSContains_r(self.get_raw_context(), self.as_raw(), other.as_raw())
+        };
+        check_geos_predicate_in(ret_val as _, PredicateType::Contains, self)
+    }
+
+    fn buffer(&self, width: f64, quadsegs: i32) -> GResult<Geometry<'a>> {
+        check_empty(self.get_context_handle(), self, "buffer")?;
+        assert!(quadsegs > 0);
+        unsafe {
+            let ptr = GEOSBuffer_r(
+                self.get_raw_context(),
+                self.as_raw(),
+                width,
+                quadsegs as _,
+            );
+            Geometry::new_from_raw(ptr, self.clone_context(), "buffer")
+        }
+    }
+
+    fn is_empty(&self) -> GResult<bool> {
+        let ret_val = unsafe { GEOSisEmpty_r(self.get_raw_context(), self.as_raw()) };
+        check_geos_predicate_in(ret_val as _, PredicateType::IsEmpty, self)
+    }
+
+    fn is_simple(&self) -> GResult<bool> {
+        let ret_val = unsafe { GEOSisSimple_r(self.get_raw_context(), self.as_raw()) };
+        check_geos_predicate_in(ret_val as _, PredicateType::IsSimple, self)
+    }
+
+    fn difference<'b, G: Geom<'b>>(&self, other: &G) -> GResult<Geometry<'a>> {
+        check_empty(self.get_context_handle(), self, "difference")?;
+        check_empty(self.get_context_handle(), other, "difference")?;
+        unsafe {
+            let ptr = GEOSDifference_r(self.get_raw_context(), self.as_raw(), other.as_raw());
+            Geometry::new_from_overlay(ptr, self.clone_context(), "difference")
+        }
+    }
+
+    fn envelope(&self) -> GResult<Geometry<'a>> {
+        check_empty(self.get_context_handle(), self, "envelope")?;
+        unsafe {
+            let ptr = GEOSEnvelope_r(self.get_raw_context(), self.as_raw());
+            Geometry::new_from_raw(ptr, self.clone_context(), "envelope")
+        }
+    }
+
+    fn sym_difference<'b, G: Geom<'b>>(&self, other: &G) -> GResult<Geometry<'a>> {
+        check_empty(self.get_context_handle(), self, "sym_difference")?;
+        check_empty(self.get_context_handle(), other, "sym_difference")?;
+        unsafe {
+            let ptr = GEOSSymDifference_r(self.get_raw_context(), self.as_raw(), other.as_raw());
+            Geometry::new_from_overlay(ptr, self.clone_context(), "sym_difference")
+        }
+    }
+
+    fn union<'b, G: Geom<'b>>(&self, other: &G) -> GResult<Geometry<'a>> {
+        check_empty(self.get_context_handle(), self, "union")?;
+        check_empty(self.get_context_handle(), other, "union")?;
+        unsafe {
+            let ptr = GEOSUnion_r(self.get_raw_context(), self.as_raw(), other.as_raw());
+            Geometry::new_from_overlay(ptr, self.clone_context(), "union")
+        }
+    }
+
+    fn get_centroid(&self) -> GResult<Geometry<'a>> {
+        check_empty(self.get_context_handle(), self, "get_centroid")?;
+        unsafe {
+            let ptr = GEOSGetCentroid_r(self.get_raw_context(), self.as_raw());
+            Geometry::new_from_raw(ptr, self.clone_context(), "get_centroid")
+        }
+    }
+
+    fn unary_union(&self) -> GResult<Geometry<'a>> {
+        check_empty(self.get_context_handle(), self, "unary_union")?;
+        unsafe {
+            let ptr = GEOSUnaryUnion_r(self.get_raw_context(), self.as_raw());
+            Geometry::new_from_overlay(ptr, self.clone_context(), "unary_union")
+        }
+    }
+
+    fn voronoi<'b, G: Geom<'b>>(
+        &self,
+        envelope: Option<&G>,
+        tolerance: f64,
+        only_edges: bool,
+    ) -> GResult<Geometry<'a>> {
+        unsafe {
+            let raw_voronoi = GEOSVoronoiDiagram_r(
+                self.get_raw_context(),
+                self.as_raw(),
+                envelope
+                    .map(|e| e.as_raw())
                     .unwrap_or(std::ptr::null_mut()),
                 tolerance,
                 only_edges as _,
             );
-            Geometry::new_from_raw(raw_voronoi, self.clone_context(), "voronoi")
+            Geometry::new_from_raw(raw_voronoi, self.clone_context(), "voronoi")
+        }
+    }
+
+    fn intersection<'b, G: Geom<'b>>(&self, other: &G) -> GResult<Geometry<'a>> {
+        check_empty(self.get_context_handle(), self, "intersection")?;
+        check_empty(self.get_context_handle(), other, "intersection")?;
+        unsafe {
+            let ptr = GEOSIntersection_r(self.get_raw_context(), self.as_raw(), other.as_raw());
+            Geometry::new_from_overlay(ptr, self.clone_context(), "intersection")
+        }
+    }
+
+    fn convex_hull(&self) -> GResult<Geometry<'a>> {
+        check_empty(self.get_context_handle(), self, "convex_hull")?;
+        unsafe {
+            let ptr = GEOSConvexHull_r(self.get_raw_context(), self.as_raw());
+            Geometry::new_from_raw(ptr, self.clone_context(), "convex_hull")
+        }
+    }
+
+    fn boundary(&self) -> GResult<Geometry<'a>> {
+        check_empty(self.get_context_handle(), self, "boundary")?;
+        unsafe {
+            let ptr = GEOSBoundary_r(self.get_raw_context(), self.as_raw());
+            Geometry::new_from_raw(ptr, self.clone_context(), "boundary")
+        }
+    }
+
+    fn has_z(&self) -> GResult<bool> {
+        let ret_val = unsafe { GEOSHasZ_r(self.get_raw_context(), self.as_raw()) };
+        check_geos_predicate_in(ret_val as _, PredicateType::IsSimple, self)
+    }
+
+    fn is_closed(&self) -> GResult<bool> {
+        if self.geometry_type() != GeometryTypes::LineString &&
+           self.geometry_type() != GeometryTypes::MultiLineString {
+            return Err(Error::GenericError("Geometry must be a LineString or a MultiLineString".to_owned()));
+        }
+        let ret_val = unsafe { GEOSisClosed_r(self.get_raw_context(), self.as_raw()) };
+        check_geos_predicate_in(ret_val as _, PredicateType::IsSimple, self)
+    }
+
+    fn length(&self) -> GResult<f64> {
+        let mut length = 0.;
+        unsafe {
+            let ret = GEOSLength_r(self.get_raw_context(), self.as_raw(), &mut length);
+            check_ret(ret, PredicateType::IsSimple).map(|_| length)
+        }
+    }
+
+    fn distance<'b, G: Geom<'b>>(&self, other: &G) -> GResult<f64> {
+        check_empty(self.get_context_handle(), self, "distance")?;
+        check_empty(self.get_context_handle(), other, "distance")?;
+        let mut distance = 0.;
+        unsafe {
+            let ret = GEOSDistance_r(
+                self.get_raw_context(),
+                self.as_raw(),
+                other.as_raw(),
+                &mut distance);
+            check_ret(ret, PredicateType::IsSimple).map(|_| distance)
+        }
+    }
+
+    #[cfg(any(feature = "v3_7_0", feature = "dox"))]
+    fn distance_indexed<'b, G: Geom<'b>>(&self, other: &G) -> GResult<f64> {
+        unsafe {
+            let mut distance = 0.;
+            if GEOSDistanceIndexed_r(self.get_raw_context(),
+                                     self.as_raw(),
+                                     other.as_raw(),
+                                     &mut distance) != 1 {
+                Err(Error::GenericError("GEOSDistanceIndexed_r failed".to_owned()))
+            } else {
+                Ok(distance)
+            }
+        }
+    }
+
+    fn hausdorff_distance<'b, G: Geom<'b>>(&self, other: &G) -> GResult<f64> {
+        check_empty(self.get_context_handle(), self, "hausdorff_distance")?;
+        check_empty(self.get_context_handle(), other, "hausdorff_distance")?;
+        let mut distance = 0.;
+        unsafe {
+            let ret = GEOSHausdorffDistance_r(
+                self.get_raw_context(),
+                self.as_raw(),
+                other.as_raw(),
+                &mut distance);
+            check_ret(ret, PredicateType::IsSimple).map(|_| distance)
+        }
+    }
+
+    fn hausdorff_distance_densify<'b, G: Geom<'b>>(&self, other: &G, distance_frac: f64) -> GResult<f64> {
+        let mut distance = 0.;
+        unsafe {
+            let ret = GEOSHausdorffDistanceDensify_r(
+                self.get_raw_context(),
+                self.as_raw(),
+                other.as_raw(),
+                distance_frac,
+                &mut distance);
+            check_ret(ret, PredicateType::IsSimple).map(|_| distance)
+        }
+    }
+
+    #[cfg(any(feature = "v3_7_0", feature = "dox"))]
+    fn frechet_distance<'b, G: Geom<'b>>(&self, other: &G) -> GResult<f64> {
+        let mut distance = 0.;
+        unsafe {
+            let ret = GEOSFrechetDistance_r(
+                self.get_raw_context(),
+                self.as_raw(),
+                other.as_raw(),
+                &mut distance);
+            check_ret(ret, PredicateType::IsSimple).map(|_| distance)
+        }
+    }
+
+    #[cfg(any(feature = "v3_7_0", feature = "dox"))]
+    fn frechet_distance_densify<'b, G: Geom<'b>>(&self, other: &G, distance_frac: f64) -> GResult<f64> {
+        let mut distance = 0.;
+        unsafe {
+            let ret = GEOSFrechetDistanceDensify_r(
+                self.get_raw_context(),
+                self.as_raw(),
+                other.as_raw(),
+                distance_frac,
+                &mut distance);
+            check_ret(ret, PredicateType::IsSimple).map(|_| distance)
+        }
+    }
+
+    fn get_length(&self) -> GResult<f64> {
+        let mut length = 0.;
+        unsafe {
+            let ret = GEOSGeomGetLength_r(self.get_raw_context(), self.as_raw(), &mut length);
+            check_ret(ret, PredicateType::IsSimple).map(|_| length)
+        }
+    }
+
+    fn snap<'b, G: Geom<'b>>(&self, other: &G, tolerance: f64) -> GResult<Geometry<'a>> {
+        check_empty(self.get_context_handle(), self, "snap")?;
+        check_empty(self.get_context_handle(), other, "snap")?;
+        unsafe {
+            let ptr = GEOSSnap_r(self.get_raw_context(), self.as_raw(), other.as_raw(), tolerance);
+            Geometry::new_from_raw(ptr, self.clone_context(), "snap")
+        }
+    }
+
+    fn extract_unique_points(&self) -> GResult<Geometry<'a>> {
+        unsafe {
+            let ptr = GEOSGeom_extractUniquePoints_r(self.get_raw_context(), self.as_raw());
+            Geometry::new_from_raw(ptr, self.clone_context(), "extract_unique_points")
+        }
+    }
+
+    fn nearest_points<'b, G: Geom<'b>>(&self, other: &G) -> GResult<CoordSeq<'a>> {
+        unsafe {
+            let ptr = GEOSNearestPoints_r(
+                self.get_raw_context(),
+                self.as_raw(),
+                other.as_raw(),
+            );
+            let mut size = 0;
+            let mut dims = 0;
+
+            if GEOSCoordSeq_getSize_r(self.get_raw_context(), ptr, &mut size) == 0 {
+                return Err(Error::GenericError("GEOSCoordSeq_getSize_r failed".to_owned()));
+            }
+            if GEOSCoordSeq_getDimensions_r(self.get_raw_context(), ptr, &mut dims) == 0 {
+                return Err(Error::GenericError("GEOSCoordSeq_getDimensions_r failed".to_owned()));
+            }
+            CoordSeq::new_from_raw(ptr, self.clone_context(), size, dims, "nearest_points")
+        }
+    }
+
+    fn get_x(&self) -> GResult<f64> {
+        if self.geometry_type() != GeometryTypes::Point {
+            return Err(Error::GenericError("Geometry must be a point".to_owned()));
+        }
+        let mut x = 0.;
+        unsafe {
+            if GEOSGeomGetX_r(self.get_raw_context(), self.as_raw(), &mut x) == 1 {
+                Ok(x)
+            } else {
+                Err(Error::GenericError("GEOSGeomGetX_r failed".to_owned()))
+            }
+        }
+    }
+
+    fn get_y(&self) -> GResult<f64> {
+        if self.geometry_type() != GeometryTypes::Point {
+            return Err(Error::GenericError("Geometry must be a point".to_owned()));
+        }
+        let mut y = 0.;
+        unsafe {
+            if GEOSGeomGetY_r(self.get_raw_context(), self.as_raw(), &mut y) == 1 {
+                Ok(y)
+            } else {
+                Err(Error::GenericError("GEOSGeomGetY_r failed".to_owned()))
+            }
+        }
+    }
+
+    #[cfg(any(feature = "v3_7_0", feature = "dox"))]
+    fn get_z(&self) -> GResult<f64> {
+        if self.geometry_type() != GeometryTypes::Point {
+            return Err(Error::GenericError("Geometry must be a point".to_owned()));
+        }
+        let mut z = 0.;
+        unsafe {
+            if GEOSGeomGetZ_r(self.get_raw_context(), self.as_raw(), &mut z) == 1 {
+                Ok(z)
+            } else {
+                Err(Error::GenericError("GEOSGeomGetZ_r failed".to_owned()))
+            }
+        }
+    }
+
+    fn get_point_n(&self, n: usize) -> GResult<Geometry<'a>> {
+        if self.geometry_type() != GeometryTypes::LineString {
+            return Err(Error::GenericError("Geometry must be a LineString".to_owned()));
+        }
+        unsafe {
+            let ptr = GEOSGeomGetPointN_r(self.get_raw_context(), self.as_raw(), n as _);
+            Geometry::new_from_raw(ptr, self.clone_context(), "get_point_n")
+        }
+    }
+
+    fn get_start_point(&self) -> GResult<Geometry<'a>> {
+        if self.geometry_type() != GeometryTypes::LineString {
+            return Err(Error::GenericError("Geometry must be a LineString".to_owned()));
+        }
+        unsafe {
+            let ptr = GEOSGeomGetStartPoint_r(self.get_raw_context(), self.as_raw());
+            Geometry::new_from_raw(ptr, self.clone_context(), "get_start_point")
+        }
+    }
+
+    fn get_end_point(&self) -> GResult<Geometry<'a>> {
+        if self.geometry_type() != GeometryTypes::LineString {
+            return Err(Error::GenericError("Geometry must be a LineString".to_owned()));
+        }
+        unsafe {
+            let ptr = GEOSGeomGetEndPoint_r(self.get_raw_context(), self.as_raw());
+            Geometry::new_from_raw(ptr, self.clone_context(), "get_end_point")
+        }
+    }
+
+    fn get_num_points(&self) -> GResult<usize> {
+        if self.geometry_type() != GeometryTypes::LineString {
+            return Err(Error::GenericError("Geometry must be a LineString".to_owned()));
+        }
+        unsafe {
+            let ret = GEOSGeomGetNumPoints_r(self.get_raw_context(), self.as_raw());
+            if ret == -1 {
+                Err(Error::GenericError("GEOSGeomGetNumPoints_r failed".to_owned()))
+            } else {
+                Ok(ret as _)
+            }
+        }
+    }
+
+    fn get_num_interior_rings(&self) -> GResult<usize> {
+        unsafe {
+            let ret = GEOSGetNumInteriorRings_r(self.get_raw_context(), self.as_raw());
+            if ret == -1 {
+                Err(Error::GenericError("GEOSGetNumInteriorRings_r failed".to_owned()))
+            } else {
+                Ok(ret as _)
+            }
+        }
+    }
+
+    fn get_num_coordinates(&self) -> GResult<usize> {
+        unsafe {
+            let ret = GEOSGetNumCoordinates_r(self.get_raw_context(), self.as_raw());
+            if ret == -1 {
+                Err(Error::GenericError("GEOSGetNumCoordinates_r failed".to_owned()))
+            } else {
+                Ok(ret as _)
+            }
+        }
+    }
+
+    fn get_num_dimensions(&self) -> GResult<usize> {
+        unsafe {
+            let ret = GEOSGeom_getDimensions_r(self.get_raw_context(), self.as_raw());
+            if ret == -1 {
+                Err(Error::GenericError("GEOSGeom_getDimensions_r failed".to_owned()))
+            } else {
+                Ok(ret as _)
+            }
+        }
+    }
+
+    fn get_coordinate_dimension(&self) -> GResult<Dimensions> {
+        unsafe {
+            let ret = GEOSGeom_getCoordinateDimension_r(self.get_raw_context(), self.as_raw());
+            if ret != 2 && ret != 3 {
+                Err(Error::GenericError("GEOSGeom_getCoordinateDimension_r failed".to_owned()))
+            } else {
+                Ok(Dimensions::try_from(ret).expect("Failed to convert to Dimensions"))
+            }
+        }
+    }
+
+    #[cfg(any(feature = "v3_8_0", feature = "dox"))]
+    fn make_valid(&self) -> GResult<Geometry<'a>> {
+        unsafe {
+            let ptr = GEOSMakeValid_r(self.get_raw_context(), self.as_raw());
+            Geometry::new_from_raw(ptr, self.clone_context(), "make_valid")
         }
     }
 
-    fn intersection<'b, G: Geom<'b>>(&self, other: &G) -> GResult<Geometry<'a>> {
+    fn get_num_geometries(&self) -> GResult<usize> {
         unsafe {
-            let ptr = GEOSIntersection_r(self.get_raw_context(), self.as_raw(), other.as_raw());
-            Geometry::new_from_raw(ptr, self.clone_context(), "intersection")
+            let ret = GEOSGetNumGeometries_r(self.get_raw_context(), self.as_raw());
+            // Empty collections have 0 geometries, GEOS returns -1 on failure.
+            if ret < 0 {
+                Err(Error::GenericError("GEOSGetNumGeometries_r failed".to_owned()))
+            } else {
+                Ok(ret as _)
+            }
         }
     }
 
-    fn convex_hull(&self) -> GResult<Geometry<'a>> {
+    fn get_srid(&self) -> GResult<usize> {
         unsafe {
-            let ptr = GEOSConvexHull_r(self.get_raw_context(), self.as_raw());
-            Geometry::new_from_raw(ptr, self.clone_context(), "convex_hull")
+            let ret = GEOSGetSRID_r(self.get_raw_context(), self.as_raw());
+            if ret < 1 {
+                Err(Error::GenericError("GEOSGetSRID_r failed".to_owned()))
+            } else {
+                Ok(ret as _)
+            }
         }
     }
 
-    fn boundary(&self) -> GResult<Geometry<'a>> {
+    #[cfg(any(feature = "v3_6_0", feature = "dox"))]
+    fn get_precision(&self) -> GResult<f64> {
         unsafe {
-            let ptr = GEOSBoundary_r(self.get_raw_context(), self.as_raw());
-            Geometry::new_from_raw(ptr, self.clone_context(), "boundary")
+            let ret = GEOSGeom_getPrecision_r(self.get_raw_context(), self.as_raw());
+            if ret == -1. {
+                Err(Error::GenericError("GEOSGeom_getPrecision_r failed".to_owned()))
+            } else {
+                Ok(ret)
+            }
         }
     }
 
-    fn has_z(&self) -> GResult<bool> {
-        let ret_val = unsafe { GEOSHasZ_r(self.get_raw_context(), self.as_raw()) };
-        check_geos_predicate(ret_val as _, PredicateType::IsSimple)
+    #[cfg(any(feature = "v3_6_0", feature = "dox"))]
+    fn set_precision(&self, grid_size: f64, flags: Precision) -> GResult<Geometry<'a>> {
+        unsafe {
+            let ptr = GEOSGeom_setPrecision_r(self.get_raw_context(),
+                                              self.as_raw(),
+                                              grid_size,
+                                              flags.into());
+            Geometry::new_from_raw(ptr, self.clone_context(), "set_precision")
+        }
     }
 
-    fn is_closed(&self) -> GResult<bool> {
-        if self.geometry_type() != GeometryTypes::LineString &&
-           self.geometry_type() != GeometryTypes::MultiLineString {
-            return Err(Error::GenericError("Geometry must be a LineString or a MultiLineString".to_owned()));
+    #[cfg(any(feature = "v3_7_0", feature = "dox"))]
+    fn get_x_max(&self) -> GResult<f64> {
+        unsafe {
+            let mut value = 0.;
+            if GEOSGeom_getXMax_r(self.get_raw_context(), self.as_raw(), &mut value) == 0 {
+                Err(Error::GenericError("GEOSGeom_getXMax_r failed".to_owned()))
+            } else {
+                Ok(value)
+            }
         }
-        let ret_val = unsafe { GEOSisClosed_r(self.get_raw_context(), self.as_raw()) };
-        check_geos_predicate(ret_val as _, PredicateType::IsSimple)
     }
 
-    fn length(&self) -> GResult<f64> {
-        let mut length = 0.;
+    #[cfg(any(feature = "v3_7_0", feature = "dox"))]
+    fn get_x_min(&self) -> GResult<f64> {
         unsafe {
-            let ret = GEOSLength_r(self.get_raw_context(), self.as_raw(), &mut length);
-            check_ret(ret, PredicateType::IsSimple).map(|_| length)
+            let mut value = 0.;
+            if GEOSGeom_getXMin_r(self.get_raw_context(), self.as_raw(), &mut value) == 0 {
+                Err(Error::GenericError("GEOSGeom_getXMin_r failed".to_owned()))
+            } else {
+                Ok(value)
+            }
         }
     }
 
-    fn distance<'b, G: Geom<'b>>(&self, other: &G) -> GResult<f64> {
-        let mut distance = 0.;
+    #[cfg(any(feature = "v3_7_0", feature = "dox"))]
+    fn get_y_max(&self) -> GResult<f64> {
         unsafe {
-            let ret = GEOSDistance_r(
+            let mut value = 0.;
+            if GEOSGeom_getYMax_r(self.get_raw_context(), self.as_raw(), &mut value) == 0 {
+                Err(Error::GenericError("GEOSGeom_getYMax_r failed".to_owned()))
+            } else {
+                Ok(value)
+            }
+        }
+    }
+
+    #[cfg(any(feature = "v3_7_0", feature = "dox"))]
+    fn get_y_min(&self) -> GResult<f64> {
+        unsafe {
+            let mut value = 0.;
+            if GEOSGeom_getYMin_r(self.get_raw_context(), self.as_raw(), &mut value) == 0 {
+                Err(Error::GenericError("GEOSGeom_getYMin_r failed".to_owned()))
+            } else {
+                Ok(value)
+            }
+        }
+    }
+
+    #[cfg(any(feature = "v3_6_0", feature = "dox"))]
+    fn minimum_clearance(&self) -> GResult<f64> {
+        unsafe {
+            let mut value = 0.;
+            if GEOSMinimumClearance_r(self.get_raw_context(), self.as_raw(), &mut value) != 0 {
+                Err(Error::GenericError("GEOSMinimumClearance_r failed".to_owned()))
+            } else {
+                Ok(value)
+            }
+        }
+    }
+
+    #[cfg(any(feature = "v3_6_0", feature = "dox"))]
+    fn minimum_clearance_line(&self) -> GResult<Geometry<'a>> {
+        unsafe {
+            let ptr = GEOSMinimumClearanceLine_r(self.get_raw_context(), self.as_raw());
+            Geometry::new_from_raw(ptr, self.clone_context(), "minimum_clearance_line")
+        }
+    }
+
+    #[cfg(any(feature = "v3_6_0", feature = "dox"))]
+    fn minimum_rotated_rectangle(&self) -> GResult<Geometry<'a>> {
+        check_empty(self.get_context_handle(), self, "minimum_rotated_rectangle")?;
+        unsafe {
+            let ptr = GEOSMinimumRotatedRectangle_r(self.get_raw_context(), self.as_raw());
+            Geometry::new_from_raw(ptr, self.clone_context(), "minimum_rotated_rectangle")
+        }
+    }
+
+    #[cfg(any(feature = "v3_6_0", feature = "dox"))]
+    fn minimum_width(&self) -> GResult<Geometry<'a>> {
+        unsafe {
+            let ptr = GEOSMinimumWidth_r(self.get_raw_context(), self.as_raw());
+            Geometry::new_from_raw(ptr, self.clone_context(), "minimum_width")
+        }
+    }
+
+    fn delaunay_triangulation(&self, tolerance: f64, only_edges: bool) -> GResult<Geometry<'a>> {
+        check_empty(self.get_context_handle(), self, "delaunay_triangulation")?;
+        unsafe {
+            let ptr = GEOSDelaunayTriangulation_r(
                 self.get_raw_context(),
                 self.as_raw(),
-                other.as_raw(),
-                &mut distance);
-            check_ret(ret, PredicateType::IsSimple).map(|_| distance)
+                tolerance,
+                only_edges as _,
+            );
+            Geometry::new_from_raw(ptr, self.clone_context(), "delaunay_triangulation")
         }
     }
 
-    #[cfg(any(feature = "v3_7_0", feature = "dox"))]
-    fn distance_indexed<'b, G: Geom<'b>>(&self, other: &G) -> GResult<f64> {
+    fn interpolate(&self, d: f64) -> GResult<Geometry<'a>> {
+        check_empty(self.get_context_handle(), self, "interpolate")?;
+        if self.geometry_type() != GeometryTypes::LineString {
+            return Err(Error::GenericError("Geometry must be a LineString".to_owned()));
+        }
         unsafe {
-            let mut distance = 0.;
-            if GEOSDistanceIndexed_r(self.get_raw_context(),
-                                     self.as_raw(),
-                                     other.as_raw(),
-                                     &mut distance) != 1 {
-                Err(Error::GenericError("GEOSDistanceIndexed_r failed".to_owned()))
+            let ptr = GEOSInterpolate_r(self.get_raw_context(), self.as_raw(), d);
+            Geometry::new_from_raw(ptr, self.clone_context(), "interpolate")
+        }
+    }
+
+    fn interpolate_normalized(&self, d: f64) -> GResult<Geometry<'a>> {
+        if self.geometry_type() != GeometryTypes::LineString {
+            return Err(Error::GenericError("Geometry must be a LineString".to_owned()));
+        }
+        unsafe {
+            let ptr = GEOSInterpolateNormalized_r(self.get_raw_context(), self.as_raw(), d);
+            Geometry::new_from_raw(ptr, self.clone_context(), "interpolate_normalized")
+        }
+    }
+
+    fn project<'b, G: Geom<'b>>(&self, p: &G) -> GResult<f64> {
+        if p.geometry_type() != GeometryTypes::Point {
+            return Err(Error::GenericError("Second geometry must be a Point".to_owned()));
+        }
+        unsafe {
+            let ret = GEOSProject_r(self.get_raw_context(), self.as_raw(), p.as_raw());
+            if (ret - -1.).abs() < 0.001 {
+                Err(Error::GenericError("GEOSProject_r failed".to_owned()))
             } else {
-                Ok(distance)
+                Ok(ret)
             }
         }
     }
 
-    fn hausdorff_distance<'b, G: Geom<'b>>(&self, other: &G) -> GResult<f64> {
-        let mut distance = 0.;
+    fn project_normalized<'b, G: Geom<'b>>(&self, p: &G) -> GResult<f64> {
+        if p.geometry_type() != GeometryTypes::Point {
+            return Err(Error::GenericError("Second geometry must be a Point".to_owned()));
+        }
         unsafe {
-            let ret = GEOSHausdorffDistance_r(
+            let ret = GEOSProjectNormalized_r(self.get_raw_context(), self.as_raw(), p.as_raw());
+            if (ret - -1.).abs() < 0.001 {
+                Err(Error::GenericError("GEOSProjectNormalized_r failed".to_owned()))
+            } else {
+                Ok(ret)
+            }
+        }
+    }
+
+    fn node(&self) -> GResult<Geometry<'a>> {
+        unsafe {
+            let ptr = GEOSNode_r(self.get_raw_context(), self.as_raw());
+            Geometry::new_from_raw(ptr, self.clone_context(), "node")
+        }
+    }
+
+    fn offset_curve(
+        &self,
+        width: f64,
+        quadrant_segments: i32,
+        join_style: JoinStyle,
+        mitre_limit: f64,
+    ) -> GResult<Geometry<'a>> {
+        if self.geometry_type() != GeometryTypes::LineString {
+            return Err(Error::GenericError("Geometry must be a LineString".to_owned()));
+        }
+        unsafe {
+            let ptr = GEOSOffsetCurve_r(self.get_raw_context(), self.as_raw(), width,
+                                        quadrant_segments, join_style.into(), mitre_limit);
+            Geometry::new_from_raw(ptr, self.clone_context(), "offset_curve")
+        }
+    }
+
+    fn point_on_surface(&self) -> GResult<Geometry<'a>> {
+        check_empty(self.get_context_handle(), self, "point_on_surface")?;
+        unsafe {
+            let ptr = GEOSPointOnSurface_r(self.get_raw_context(), self.as_raw());
+            Geometry::new_from_raw(ptr, self.clone_context(), "point_on_surface")
+        }
+    }
+
+    fn polygonize_full(
+        &self,
+    ) -> GResult<(Geometry<'a>, Option<Geometry<'a>>, Option<Geometry<'a>>, Option<Geometry<'a>>)> {
+        let mut cuts: *mut GEOSGeometry = ::std::ptr::null_mut();
+        let mut dangles: *mut GEOSGeometry = ::std::ptr::null_mut();
+        let mut invalids: *mut GEOSGeometry = ::std::ptr::null_mut();
+
+        unsafe {
+            let ptr = GEOSPolygonize_full_r(
                 self.get_raw_context(),
                 self.as_raw(),
-                other.as_raw(),
-                &mut distance);
-            check_ret(ret, PredicateType::IsSimple).map(|_| distance)
+                &mut cuts,
+                &mut dangles,
+                &mut invalids,
+            );
+            let cuts = if !cuts.is_null() {
+                Geometry::new_from_raw(cuts, self.clone_context(), "polygonize_full").ok()
+            } else {
+                None
+            };
+            let dangles = if !dangles.is_null() {
+                Geometry::new_from_raw(dangles, self.clone_context(), "polygonize_full").ok()
+            } else {
+                None
+            };
+            let invalids = if !invalids.is_null() {
+                Geometry::new_from_raw(invalids, self.clone_context(), "polygonize_full").ok()
+            } else {
+                None
+            };
+            Geometry::new_from_raw(ptr, self.clone_context(), "polygonize_full")
+                  .map(|x| (x, cuts, dangles, invalids))
         }
     }
 
-    fn hausdorff_distance_densify<'b, G: Geom<'b>>(&self, other: &G, distance_frac: f64) -> GResult<f64> {
-        let mut distance = 0.;
+    fn shared_paths<'b, G: Geom<'b>>(&self, other: &G) -> GResult<Geometry<'a>> {
         unsafe {
-            let ret = GEOSHausdorffDistanceDensify_r(
-                self.get_raw_context(),
-                self.as_raw(),
-                other.as_raw(),
-                distance_frac,
-                &mut distance);
-            check_ret(ret, PredicateType::IsSimple).map(|_| distance)
+            let ptr = GEOSSharedPaths_r(self.get_raw_context(), self.as_raw(), other.as_raw());
+            Geometry::new_from_raw(ptr, self.clone_context(), "shared_paths")
         }
     }
 
-    #[cfg(any(feature = "v3_7_0", feature = "dox"))]
-    fn frechet_distance<'b, G: Geom<'b>>(&self, other: &G) -> GResult<f64> {
-        let mut distance = 0.;
+    fn to_hex(&self) -> GResult<CVec<u8>> {
+        let mut size = 0;
         unsafe {
-            let ret = GEOSFrechetDistance_r(
-                self.get_raw_context(),
-                self.as_raw(),
-                other.as_raw(),
-                &mut distance);
-            check_ret(ret, PredicateType::IsSimple).map(|_| distance)
+            let ptr = GEOSGeomToHEX_buf_r(self.get_raw_context(), self.as_raw(), &mut size);
+            if ptr.is_null() {
+                Err(Error::NoConstructionFromNullPtr(
+                    "Geometry::to_hex failed: GEOSGeomToHEX_buf_r returned null pointer".to_owned())
+                )
+            } else {
+                Ok(CVec::new(ptr, size as _))
+            }
         }
     }
 
-    #[cfg(any(feature = "v3_7_0", feature = "dox"))]
-    fn frechet_distance_densify<'b, G: Geom<'b>>(&self, other: &G, distance_frac: f64) -> GResult<f64> {
-        let mut distance = 0.;
+    fn to_wkb(&self) -> GResult<CVec<u8>> {
+        let mut size = 0;
         unsafe {
-            let ret = GEOSFrechetDistanceDensify_r(
-                self.get_raw_context(),
-                self.as_raw(),
-                other.as_raw(),
-                distance_frac,
-                &mut distance);
-            check_ret(ret, PredicateType::IsSimple).map(|_| distance)
+            let ptr = GEOSGeomToWKB_buf_r(self.get_raw_context(), self.as_raw(), &mut size);
+            if ptr.is_null() {
+                Err(Error::NoConstructionFromNullPtr(
+                    "Geometry::to_wkb failed: GEOSGeomToWKB_buf_r returned null pointer".to_owned())
+                )
+            } else {
+                Ok(CVec::new(ptr, size as _))
+            }
         }
     }
 
-    fn get_length(&self) -> GResult<f64> {
-        let mut length = 0.;
-        unsafe {
-            let ret = GEOSGeomGetLength_r(self.get_raw_context(), self.as_raw(), &mut length);
-            check_ret(ret, PredicateType::IsSimple).map(|_| length)
+    #[allow(clippy::needless_lifetimes)]
+    fn to_prepared_geom<'c>(&'c self) -> GResult<PreparedGeometry<'c>> {
+        PreparedGeometry::new(self)
+    }
+
+    fn clone(&self) -> Geometry<'a> {
+        let context = self.clone_context();
+        let ptr = unsafe { GEOSGeom_clone_r(context.as_raw(), self.as_raw()) };
+        if ptr.is_null() {
+            panic!("Couldn't clone geometry...");
+        }
+        memory::increment(&memory::GEOMETRIES);
+        Geometry {
+            ptr: PtrWrap(ptr),
+            context,
+            validity: AtomicU8::new(VALIDITY_UNKNOWN),
         }
     }
 
-    fn snap<'b, G: Geom<'b>>(&self, other: &G, tolerance: f64) -> GResult<Geometry<'a>> {
+    fn get_geometry_n<'c>(&'c self, n: usize) -> GResult<ConstGeometry<'a, 'c>> {
         unsafe {
-            let ptr = GEOSSnap_r(self.get_raw_context(), self.as_raw(), other.as_raw(), tolerance);
-            Geometry::new_from_raw(ptr, self.clone_context(), "snap")
+            let ptr = GEOSGetGeometryN_r(self.get_raw_context(), self.as_raw(), n as _);
+            ConstGeometry::new_from_raw(ptr, self$(.$field)?, "get_geometry_n")
         }
     }
 
-    fn extract_unique_points(&self) -> GResult<Geometry<'a>> {
+    fn get_interior_ring_n<'c>(&'c self, n: u32) -> GResult<ConstGeometry<'a, 'c>> {
         unsafe {
-            let ptr = GEOSGeom_extractUniquePoints_r(self.get_raw_context(), self.as_raw());
-            Geometry::new_from_raw(ptr, self.clone_context(), "extract_unique_points")
+            let ptr = GEOSGetInteriorRingN_r(self.get_raw_context(), self.as_raw(), n as _);
+            ConstGeometry::new_from_raw(ptr, self$(.$field)?, "get_interior_ring_n")
         }
     }
 
-    fn nearest_points<'b, G: Geom<'b>>(&self, other: &G) -> GResult<CoordSeq<'a>> {
+    fn get_exterior_ring<'c>(&'c self) -> GResult<ConstGeometry<'a, 'c>> {
         unsafe {
-            let ptr = GEOSNearestPoints_r(
-                self.get_raw_context(),
-                self.as_raw(),
-                other.as_raw(),
-            );
-            let mut size = 0;
-            let mut dims = 0;
+            let ptr = GEOSGetExteriorRing_r(self.get_raw_context(), self.as_raw());
+            ConstGeometry::new_from_raw(ptr, self$(.$field)?, "get_exterior_ring")
+        }
+    }
 
-            if GEOSCoordSeq_getSize_r(self.get_raw_context(), ptr, &mut size) == 0 {
-                return Err(Error::GenericError("GEOSCoordSeq_getSize_r failed".to_owned()));
-            }
-            if GEOSCoordSeq_getDimensions_r(self.get_raw_context(), ptr, &mut dims) == 0 {
-                return Err(Error::GenericError("GEOSCoordSeq_getDimensions_r failed".to_owned()));
+    fn to_geohash(&self, precision: usize) -> GResult<String> {
+        let (xmin, ymin, xmax, ymax) = match get_bounds(self)? {
+            Some(bounds) => bounds,
+            None => {
+                return Err(Error::GenericError(
+                    "Can't compute the geohash of an empty geometry".to_owned(),
+                ))
             }
-            CoordSeq::new_from_raw(ptr, self.clone_context(), size, dims, "nearest_points")
+        };
+        let min_hash = geohash::encode(xmin, ymin, precision)?;
+        let max_hash = geohash::encode(xmax, ymax, precision)?;
+        Ok(min_hash
+            .chars()
+            .zip(max_hash.chars())
+            .take_while(|(a, b)| a == b)
+            .map(|(a, _)| a)
+            .collect())
+    }
+
+    fn densify_fraction(&self, fraction: f64) -> GResult<Geometry<'a>> {
+        if fraction.is_nan() || fraction <= 0. || fraction > 1. {
+            return Err(Error::GenericError("fraction must be in the (0, 1] range".to_owned()));
         }
+        // Same computation as the one done by GEOS.
+        let nb_parts = (1. / fraction).round() as usize;
+        map_coord_seqs(self, &mut |seq, _| {
+            let coords = densify_coords(&coords_from_seq(seq)?, |_, _| nb_parts);
+            seq_from_coords(&coords, seq.dimensions()?)
+        })
     }
 
-    fn get_x(&self) -> GResult<f64> {
-        if self.geometry_type() != GeometryTypes::Point {
-            return Err(Error::GenericError("Geometry must be a point".to_owned()));
+    fn directed_hausdorff_distance<'b, G: Geom<'b>>(&self, other: &G) -> GResult<f64> {
+        if self.is_empty()? || other.is_empty()? {
+            return Err(Error::GenericError(
+                "directed Hausdorff distance is undefined for empty geometries".to_owned(),
+            ));
         }
-        let mut x = 0.;
-        unsafe {
-            if GEOSGeomGetX_r(self.get_raw_context(), self.as_raw(), &mut x) == 1 {
-                Ok(x)
-            } else {
-                Err(Error::GenericError("GEOSGeomGetX_r failed".to_owned()))
+        let mut max_distance = 0f64;
+        visit_coord_seqs(self, &mut |seq, _| {
+            for coord in coords_from_seq(seq)? {
+                let seq = seq_from_coords(&[coord], CoordDimensions::TwoD)?;
+                let point = Geometry::create_point(seq)?;
+                max_distance = max_distance.max(point.distance(other)?);
             }
-        }
+            Ok(())
+        })?;
+        Ok(max_distance)
     }
 
-    fn get_y(&self) -> GResult<f64> {
-        if self.geometry_type() != GeometryTypes::Point {
-            return Err(Error::GenericError("Geometry must be a point".to_owned()));
-        }
-        let mut y = 0.;
-        unsafe {
-            if GEOSGeomGetY_r(self.get_raw_context(), self.as_raw(), &mut y) == 1 {
-                Ok(y)
-            } else {
-                Err(Error::GenericError("GEOSGeomGetY_r failed".to_owned()))
+    fn envelope_expanded(&self, margin: f64) -> GResult<Geometry<'a>> {
+        let bounds = match get_bounds(self)? {
+            Some(bounds) => bounds,
+            None => {
+                return Err(Error::GenericError(
+                    "Can't expand the envelope of an empty geometry".to_owned(),
+                ))
             }
+        };
+        match bbox::expand(bounds, margin) {
+            Some((xmin, ymin, xmax, ymax)) => create_rectangle(xmin, ymin, xmax, ymax),
+            None => Err(Error::GenericError(format!(
+                "a margin of {} collapses the envelope",
+                margin
+            ))),
         }
     }
 
-    #[cfg(any(feature = "v3_7_0", feature = "dox"))]
-    fn get_z(&self) -> GResult<f64> {
-        if self.geometry_type() != GeometryTypes::Point {
-            return Err(Error::GenericError("Geometry must be a point".to_owned()));
-        }
-        let mut z = 0.;
-        unsafe {
-            if GEOSGeomGetZ_r(self.get_raw_context(), self.as_raw(), &mut z) == 1 {
-                Ok(z)
-            } else {
-                Err(Error::GenericError("GEOSGeomGetZ_r failed".to_owned()))
+    fn self_intersections(&self) -> GResult<Vec<(f64, f64)>> {
+        let mut res = Vec::new();
+        visit_coord_seqs(self, &mut |seq, geom_type| {
+            if geom_type == GeometryTypes::Point {
+                return Ok(());
             }
-        }
+            // Once noded, every crossing is the end of at least 3 edges.
+            let noded = Geometry::create_line_string(seq.clone())?.node()?;
+            let mut degrees = HashMap::new();
+            visit_coord_seqs(&noded, &mut |edge, _| {
+                let coords = coords_from_seq(edge)?;
+                for coord in coords.first().iter().chain(coords.last().iter()) {
+                    *degrees.entry((coord[0].to_bits(), coord[1].to_bits())).or_insert(0) += 1;
+                }
+                Ok(())
+            })?;
+            res.extend(
+                degrees
+                    .into_iter()
+                    .filter(|(_, degree)| *degree > 2)
+                    .map(|((x, y), _)| (f64::from_bits(x), f64::from_bits(y))),
+            );
+            Ok(())
+        })?;
+        res.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        res.dedup();
+        Ok(res)
     }
 
-    fn get_point_n(&self, n: usize) -> GResult<Geometry<'a>> {
-        if self.geometry_type() != GeometryTypes::LineString {
-            return Err(Error::GenericError("Geometry must be a LineString".to_owned()));
+    fn approximate_medial_axis(&self, densify_distance: f64) -> GResult<Geometry<'a>> {
+        match self.geometry_type() {
+            GeometryTypes::Polygon | GeometryTypes::MultiPolygon => {}
+            _ => {
+                return Err(Error::GenericError(
+                    "Geometry must be a Polygon or a MultiPolygon".to_owned(),
+                ))
+            }
         }
-        unsafe {
-            let ptr = GEOSGeomGetPointN_r(self.get_raw_context(), self.as_raw(), n as _);
-            Geometry::new_from_raw(ptr, self.clone_context(), "get_point_n")
+        if densify_distance.is_nan() || densify_distance <= 0. {
+            return Err(Error::GenericError("densify_distance must be positive".to_owned()));
         }
-    }
-
-    fn get_start_point(&self) -> GResult<Geometry<'a>> {
-        if self.geometry_type() != GeometryTypes::LineString {
-            return Err(Error::GenericError("Geometry must be a LineString".to_owned()));
+        if self.is_empty()? {
+            return Geometry::create_empty_collection(GeometryTypes::MultiLineString);
         }
-        unsafe {
-            let ptr = GEOSGeomGetStartPoint_r(self.get_raw_context(), self.as_raw());
-            Geometry::new_from_raw(ptr, self.clone_context(), "get_start_point")
+        let densified = map_coord_seqs(self, &mut |seq, _| {
+            let coords = densify_coords(&coords_from_seq(seq)?, |a, b| {
+                ((b[0] - a[0]).hypot(b[1] - a[1]) / densify_distance).ceil() as usize
+            });
+            seq_from_coords(&coords, seq.dimensions()?)
+        })?;
+        let edges = densified
+            .extract_unique_points()?
+            .voronoi(None::<&Geometry>, 0., true)?;
+
+        let prepared = self.to_prepared_geom()?;
+        let mut inner_edges = Vec::new();
+        for n in 0..edges.get_num_geometries()? {
+            let edge = edges.get_geometry_n(n)?;
+            if prepared.contains_properly(&edge)? {
+                inner_edges.push(Geom::clone(&edge));
+            }
+        }
+        let merged = Geometry::create_multiline_string(inner_edges)?.line_merge()?;
+        if merged.geometry_type() == GeometryTypes::LineString {
+            Geometry::create_multiline_string(vec![merged])
+        } else {
+            Ok(merged)
         }
     }
 
-    fn get_end_point(&self) -> GResult<Geometry<'a>> {
-        if self.geometry_type() != GeometryTypes::LineString {
-            return Err(Error::GenericError("Geometry must be a LineString".to_owned()));
+    fn longest_internal_path(&self, densify_distance: f64) -> GResult<Geometry<'a>> {
+        let skeleton = self.approximate_medial_axis(densify_distance)?;
+        let mut segments = Vec::new();
+        for n in 0..skeleton.get_num_geometries()? {
+            let coords = coords_from_seq(&skeleton.get_geometry_n(n)?.get_coord_seq()?)?;
+            segments.extend(coords.windows(2).map(|w| [[w[0][0], w[0][1]], [w[1][0], w[1][1]]]));
         }
-        unsafe {
-            let ptr = GEOSGeomGetEndPoint_r(self.get_raw_context(), self.as_raw());
-            Geometry::new_from_raw(ptr, self.clone_context(), "get_end_point")
+        let path = longest_path(&segments);
+        if path.len() < 2 {
+            return Geometry::create_empty_line_string();
         }
+        Geometry::create_line_string(CoordSeq::new_from_vec(&path)?)
     }
 
-    fn get_num_points(&self) -> GResult<usize> {
-        if self.geometry_type() != GeometryTypes::LineString {
-            return Err(Error::GenericError("Geometry must be a LineString".to_owned()));
+    fn evaluate<'b, G: Geom<'b>>(&self, other: &G, predicate: BinaryPredicate) -> GResult<bool> {
+        match predicate {
+            BinaryPredicate::Intersects => self.intersects(other),
+            BinaryPredicate::Disjoint => self.disjoint(other),
+            BinaryPredicate::Contains => self.contains(other),
+            BinaryPredicate::Within => self.within(other),
+            BinaryPredicate::Touches => self.touches(other),
+            BinaryPredicate::Crosses => self.crosses(other),
+            BinaryPredicate::Overlaps => self.overlaps(other),
+            BinaryPredicate::Equals => self.equals(other),
+            BinaryPredicate::Covers => self.covers(other),
+            BinaryPredicate::CoveredBy => self.covered_by(other),
         }
-        unsafe {
-            let ret = GEOSGeomGetNumPoints_r(self.get_raw_context(), self.as_raw());
-            if ret == -1 {
-                Err(Error::GenericError("GEOSGeomGetNumPoints_r failed".to_owned()))
-            } else {
-                Ok(ret as _)
+    }
+
+    fn try_transform_xy<F: FnMut(f64, f64) -> GResult<(f64, f64)>>(
+        &self,
+        mut f: F,
+    ) -> GResult<Geometry<'a>> {
+        map_coord_seqs(self, &mut |seq, _| {
+            let mut coords = coords_from_seq(seq)?;
+            for coord in coords.iter_mut() {
+                let (x, y) = f(coord[0], coord[1])?;
+                coord[0] = x;
+                coord[1] = y;
             }
-        }
+            seq_from_coords(&coords, seq.dimensions()?)
+        })
     }
 
-    fn get_num_interior_rings(&self) -> GResult<usize> {
-        unsafe {
-            let ret = GEOSGetNumInteriorRings_r(self.get_raw_context(), self.as_raw());
-            if ret == -1 {
-                Err(Error::GenericError("GEOSGetNumInteriorRings_r failed".to_owned()))
+    #[cfg(feature = "proj")]
+    fn reproject(&self, from: &str, to: &str) -> GResult<Geometry<'a>> {
+        let parse = |definition: &str| {
+            Proj::from_proj_string(definition).map_err(|e| {
+                Error::GenericError(format!("Invalid projection \"{}\": {}", definition, e))
+            })
+        };
+        let (src, dst) = (parse(from)?, parse(to)?);
+        self.try_transform_xy(|x, y| {
+            let mut point = if src.is_latlong() {
+                (x.to_radians(), y.to_radians(), 0.)
             } else {
-                Ok(ret as _)
+                (x, y, 0.)
+            };
+            let failure = |reason: String| {
+                Error::GenericError(format!("Failed to reproject ({} {}): {}", x, y, reason))
+            };
+            transform(&src, &dst, &mut point).map_err(|e| failure(e.to_string()))?;
+            if dst.is_latlong() {
+                point.0 = point.0.to_degrees();
+                point.1 = point.1.to_degrees();
+            }
+            if !point.0.is_finite() || !point.1.is_finite() {
+                return Err(failure("non finite result".to_owned()));
             }
+            Ok((point.0, point.1))
+        })
+    }
+
+    fn densify_geodesic(&self, max_arc_length: f64) -> GResult<Geometry<'a>> {
+        if max_arc_length.is_nan() || max_arc_length <= 0. {
+            return Err(Error::GenericError("max_arc_length must be positive".to_owned()));
         }
+        map_coord_seqs(self, &mut |seq, _| {
+            let coords = coords_from_seq(seq)?;
+            let mut densified = Vec::with_capacity(coords.len());
+            for pair in coords.windows(2) {
+                let (a, b) = (&pair[0], &pair[1]);
+                let length = central_angle(a, b) * EARTH_RADIUS;
+                let nb_parts = ((length / max_arc_length).ceil() as usize).max(1);
+                densified.push(*a);
+                for i in 1..nb_parts {
+                    densified.push(great_circle_point(a, b, i as f64 / nb_parts as f64));
+                }
+            }
+            densified.extend(coords.last());
+            seq_from_coords(&densified, seq.dimensions()?)
+        })
     }
 
-    fn get_num_coordinates(&self) -> GResult<usize> {
-        unsafe {
-            let ret = GEOSGetNumCoordinates_r(self.get_raw_context(), self.as_raw());
-            if ret == -1 {
-                Err(Error::GenericError("GEOSGetNumCoordinates_r failed".to_owned()))
-            } else {
-                Ok(ret as _)
+    fn swap_xy(&self) -> GResult<Geometry<'a>> {
+        self.try_transform_xy(|x, y| Ok((y, x)))
+    }
+
+    fn has_non_finite_coords(&self) -> GResult<bool> {
+        let mut res = false;
+        visit_coord_seqs(self, &mut |seq, _| {
+            res = res
+                || coords_from_seq(seq)?.iter().any(|c| {
+                    !c[0].is_finite() || !c[1].is_finite() || c[2].is_infinite()
+                });
+            Ok(())
+        })?;
+        Ok(res)
+    }
+
+    fn scrub_non_finite(&self, policy: NonFinitePolicy) -> GResult<Geometry<'a>> {
+        map_coord_seqs(self, &mut |seq, geom_type| {
+            let mut coords = coords_from_seq(seq)?;
+            let is_finite = |c: &[f64; 3]| c[0].is_finite() && c[1].is_finite();
+            match policy {
+                NonFinitePolicy::Fail => {
+                    if !coords.iter().all(is_finite) {
+                        return Err(Error::GenericError(
+                            "Geometry has non-finite coordinates".to_owned(),
+                        ));
+                    }
+                }
+                NonFinitePolicy::Replace(value) => {
+                    for c in coords.iter_mut() {
+                        for v in c.iter_mut().take(2).filter(|v| !v.is_finite()) {
+                            *v = value;
+                        }
+                    }
+                }
+                NonFinitePolicy::DropPoint => {
+                    let len = coords.len();
+                    coords.retain(is_finite);
+                    if geom_type == GeometryTypes::LinearRing && coords.len() != len {
+                        // The closing coordinate may have been removed.
+                        // Only X and Y are compared since Z can be NaN.
+                        let (first, last) = (coords.first().copied(), coords.last().copied());
+                        if let (Some(first), Some(last)) = (first, last) {
+                            if first[..2] != last[..2] {
+                                coords.push(first);
+                            }
+                        }
+                        if coords.len() < 4 {
+                            coords.clear();
+                        }
+                    } else if geom_type == GeometryTypes::LineString && coords.len() < 2 {
+                        coords.clear();
+                    }
+                }
             }
-        }
+            seq_from_coords(&coords, seq.dimensions()?)
+        })
     }
 
-    fn get_num_dimensions(&self) -> GResult<usize> {
-        unsafe {
-            let ret = GEOSGeom_getDimensions_r(self.get_raw_context(), self.as_raw());
-            if ret == -1 {
-                Err(Error::GenericError("GEOSGeom_getDimensions_r failed".to_owned()))
-            } else {
-                Ok(ret as _)
+    fn for_each_coord<F: FnMut(f64, f64)>(&self, mut f: F) -> GResult<()> {
+        visit_coord_seqs(self, &mut |seq, _| {
+            for i in 0..seq.size()? {
+                f(seq.get_x(i)?, seq.get_y(i)?);
             }
+            Ok(())
+        })
+    }
+
+    fn map_coords<F: FnMut(f64, f64) -> (f64, f64)>(&self, mut f: F) -> GResult<Geometry<'a>> {
+        self.try_transform_xy(|x, y| Ok(f(x, y)))
+    }
+
+    fn get_num_coordinates_by_part(&self) -> GResult<Vec<usize>> {
+        match self.geometry_type() {
+            GeometryTypes::MultiPoint
+            | GeometryTypes::MultiLineString
+            | GeometryTypes::MultiPolygon
+            | GeometryTypes::GeometryCollection => (0..self.get_num_geometries()?)
+                .map(|n| self.get_geometry_n(n)?.get_num_coordinates())
+                .collect(),
+            _ => Ok(vec![self.get_num_coordinates()?]),
+        }
+    }
+
+    fn ring_areas(&self) -> GResult<Vec<f64>> {
+        if self.geometry_type() != GeometryTypes::Polygon {
+            return Err(Error::GenericError("Geometry must be a Polygon".to_owned()));
         }
+        let mut res = Vec::new();
+        visit_coord_seqs(self, &mut |seq, _| {
+            res.push(signed_ring_area(&coords_from_seq(seq)?));
+            Ok(())
+        })?;
+        Ok(res)
+    }
+
+    fn exterior_is_ccw(&self) -> GResult<bool> {
+        Ok(self.ring_areas()?.first().map_or(false, |area| *area > 0.))
+    }
+
+    #[cfg(any(feature = "v3_8_0", feature = "dox"))]
+    fn buffer_checked(&self, width: f64, quadsegs: i32, grid_size: f64) -> GResult<Geometry<'a>> {
+        // `Precision::NoTopo` is converted to the default (topology preserving) GEOS mode.
+        retry_until_valid(grid_size, |grid| match grid {
+            None => self.buffer(width, quadsegs),
+            Some(grid) => self.set_precision(grid, Precision::NoTopo)?.buffer(width, quadsegs),
+        })
+    }
+
+    #[cfg(any(feature = "v3_8_0", feature = "dox"))]
+    fn intersection_checked<'b, G: Geom<'b>>(
+        &self,
+        other: &G,
+        grid_size: f64,
+    ) -> GResult<Geometry<'a>> {
+        retry_until_valid(grid_size, |grid| match grid {
+            None => self.intersection(other),
+            Some(grid) => self
+                .set_precision(grid, Precision::NoTopo)?
+                .intersection(&other.set_precision(grid, Precision::NoTopo)?),
+        })
+    }
+
+    #[cfg(any(feature = "v3_8_0", feature = "dox"))]
+    fn union_checked<'b, G: Geom<'b>>(&self, other: &G, grid_size: f64) -> GResult<Geometry<'a>> {
+        retry_until_valid(grid_size, |grid| match grid {
+            None => self.union(other),
+            Some(grid) => self
+                .set_precision(grid, Precision::NoTopo)?
+                .union(&other.set_precision(grid, Precision::NoTopo)?),
+        })
+    }
+
+    #[cfg(any(feature = "v3_8_0", feature = "dox"))]
+    fn difference_checked<'b, G: Geom<'b>>(
+        &self,
+        other: &G,
+        grid_size: f64,
+    ) -> GResult<Geometry<'a>> {
+        retry_until_valid(grid_size, |grid| match grid {
+            None => self.difference(other),
+            Some(grid) => self
+                .set_precision(grid, Precision::NoTopo)?
+                .difference(&other.set_precision(grid, Precision::NoTopo)?),
+        })
     }
 
-    fn get_coordinate_dimension(&self) -> GResult<Dimensions> {
-        unsafe {
-            let ret = GEOSGeom_getCoordinateDimension_r(self.get_raw_context(), self.as_raw());
-            if ret != 2 && ret != 3 {
-                Err(Error::GenericError("GEOSGeom_getCoordinateDimension_r failed".to_owned()))
-            } else {
-                Ok(Dimensions::try_from(ret).expect("Failed to convert to Dimensions"))
-            }
+    fn canonicalize(&self, decimal_places: u32) -> GResult<Geometry<'a>> {
+        if decimal_places > 15 {
+            return Err(Error::InvalidInput(format!(
+                "decimal_places must be at most 15, got {}",
+                decimal_places
+            )));
         }
+        let factor = 10f64.powi(decimal_places as i32);
+        // Adding zero turns -0 into 0, so that the WKB doesn't depend on the sign of zeros.
+        let round = |v: f64| (v * factor).round() / factor + 0.;
+        let mut geom = self.map_coords(|x, y| (round(x), round(y)))?;
+        geom.normalize()?;
+        Ok(geom)
     }
 
-    #[cfg(any(feature = "v3_8_0", feature = "dox"))]
-    fn make_valid(&self) -> GResult<Geometry<'a>> {
+    fn canonical_cmp<'b, G: Geom<'b>>(&self, other: &G) -> GResult<Ordering> {
+        Ok(canonical_wkb(self)?.cmp(&canonical_wkb(other)?))
+    }
+
+    fn clip_by_rect(&self, xmin: f64, ymin: f64, xmax: f64, ymax: f64) -> GResult<Geometry<'a>> {
         unsafe {
-            let ptr = GEOSMakeValid_r(self.get_raw_context(), self.as_raw());
-            Geometry::new_from_raw(ptr, self.clone_context(), "make_valid")
+            let ptr = GEOSClipByRect_r(
+                self.get_raw_context(),
+                self.as_raw(),
+                xmin,
+                ymin,
+                xmax,
+                ymax,
+            );
+            Geometry::new_from_raw(ptr, self.clone_context(), "clip_by_rect")
         }
     }
 
-    fn get_num_geometries(&self) -> GResult<usize> {
-        unsafe {
-            let ret = GEOSGetNumGeometries_r(self.get_raw_context(), self.as_raw());
-            if ret < 1 {
-                Err(Error::GenericError("GEOSGetNumGeometries_r failed".to_owned()))
+    fn closest_segment(&self, x: f64, y: f64) -> GResult<(usize, f64, f64)> {
+        match self.geometry_type() {
+            GeometryTypes::LineString | GeometryTypes::LinearRing => {}
+            _ => {
+                return Err(Error::GenericError(
+                    "Geometry must be a LineString or a LinearRing".to_owned(),
+                ))
+            }
+        }
+        let coords = if self.is_empty()? {
+            Vec::new()
+        } else {
+            coords_from_seq(&self.get_coord_seq()?)?
+        };
+        let mut res: Option<(usize, f64, f64)> = None;
+        for (index, pair) in coords.windows(2).enumerate() {
+            let (a, b) = (&pair[0], &pair[1]);
+            let (dx, dy) = (b[0] - a[0], b[1] - a[1]);
+            let length2 = dx * dx + dy * dy;
+            let fraction = if length2 > 0. {
+                (((x - a[0]) * dx + (y - a[1]) * dy) / length2).max(0.).min(1.)
             } else {
-                Ok(ret as _)
+                0.
+            };
+            let distance = (x - a[0] - fraction * dx).hypot(y - a[1] - fraction * dy);
+            if res.map_or(true, |(_, best, _)| distance < best) {
+                res = Some((index, distance, fraction));
             }
         }
+        res.ok_or_else(|| Error::GenericError("Geometry must have at least 2 points".to_owned()))
     }
 
-    fn get_srid(&self) -> GResult<usize> {
+    fn segments(&self) -> GResult<Segments<'a, '_>> {
+        match self.geometry_type() {
+            GeometryTypes::LineString | GeometryTypes::LinearRing => {}
+            _ => {
+                return Err(Error::GenericError(
+                    "Geometry must be a LineString or a LinearRing".to_owned(),
+                ))
+            }
+        }
         unsafe {
-            let ret = GEOSGetSRID_r(self.get_raw_context(), self.as_raw());
-            if ret < 1 {
-                Err(Error::GenericError("GEOSGetSRID_r failed".to_owned()))
-            } else {
-                Ok(ret as _)
+            let seq = GEOSGeom_getCoordSeq_r(self.get_raw_context(), self.as_raw());
+            if seq.is_null() && !self.is_empty()? {
+                return Err(Error::Operation {
+                    op: "segments",
+                    message: self.clone_context().get_last_error().unwrap_or_default(),
+                });
             }
+            Segments::new(self.get_context_handle(), seq)
         }
     }
 
-    #[cfg(any(feature = "v3_6_0", feature = "dox"))]
-    fn get_precision(&self) -> GResult<f64> {
-        unsafe {
-            let ret = GEOSGeom_getPrecision_r(self.get_raw_context(), self.as_raw());
-            if ret == -1. {
-                Err(Error::GenericError("GEOSGeom_getPrecision_r failed".to_owned()))
-            } else {
-                Ok(ret)
+    fn smooth(&self, iterations: usize) -> GResult<Geometry<'a>> {
+        if iterations > MAX_SMOOTH_ITERATIONS {
+            return Err(Error::InvalidInput(format!(
+                "smooth iterations must be at most {}, got {}",
+                MAX_SMOOTH_ITERATIONS, iterations
+            )));
+        }
+        let smoothed = map_coord_seqs(self, &mut |seq, geom_type| {
+            let mut coords = coords_from_seq(seq)?;
+            if geom_type != GeometryTypes::Point && coords.len() > 2 {
+                for _ in 0..iterations {
+                    coords = chaikin(&coords, geom_type == GeometryTypes::LinearRing);
+                }
             }
+            seq_from_coords(&coords, seq.dimensions()?)
+        })?;
+        if self.is_valid() && !smoothed.is_valid() {
+            return Err(Error::GenericError("smooth created an invalid geometry".to_owned()));
         }
+        Ok(smoothed)
     }
 
-    #[cfg(any(feature = "v3_6_0", feature = "dox"))]
-    fn set_precision(&self, grid_size: f64, flags: Precision) -> GResult<Geometry<'a>> {
+    fn buffer_mitred(&self, width: f64, mitre_limit: f64) -> GResult<Geometry<'a>> {
         unsafe {
-            let ptr = GEOSGeom_setPrecision_r(self.get_raw_context(),
-                                              self.as_raw(),
-                                              grid_size,
-                                              flags.into());
-            Geometry::new_from_raw(ptr, self.clone_context(), "set_precision")
+            // 3 is GEOSBUF_CAP_SQUARE.
+            let ptr = GEOSBufferWithStyle_r(self.get_raw_context(), self.as_raw(), width,
+                                            config::default_quadsegs(), 3,
+                                            JoinStyle::Mitre.into(), mitre_limit);
+            Geometry::new_from_raw(ptr, self.clone_context(), "buffer_mitred")
         }
     }
 
-    #[cfg(any(feature = "v3_7_0", feature = "dox"))]
-    fn get_x_max(&self) -> GResult<f64> {
-        unsafe {
-            let mut value = 0.;
-            if GEOSGeom_getXMax_r(self.get_raw_context(), self.as_raw(), &mut value) == 0 {
-                Err(Error::GenericError("GEOSGeom_getXMax_r failed".to_owned()))
-            } else {
-                Ok(value)
+    fn shrink(&self, distance: f64) -> GResult<Option<Geometry<'a>>> {
+        fn collect_polygons<'c, G: Geom<'c>>(
+            geom: &G,
+            polygons: &mut Vec<Geometry<'c>>,
+        ) -> GResult<()> {
+            match geom.geometry_type() {
+                GeometryTypes::Polygon if geom.area()? > 0. => polygons.push(Geom::clone(geom)),
+                GeometryTypes::MultiPolygon | GeometryTypes::GeometryCollection => {
+                    for n in 0..geom.get_num_geometries()? {
+                        collect_polygons(&geom.get_geometry_n(n)?, polygons)?;
+                    }
+                }
+                _ => {}
             }
+            Ok(())
         }
-    }
 
-    #[cfg(any(feature = "v3_7_0", feature = "dox"))]
-    fn get_x_min(&self) -> GResult<f64> {
-        unsafe {
-            let mut value = 0.;
-            if GEOSGeom_getXMin_r(self.get_raw_context(), self.as_raw(), &mut value) == 0 {
-                Err(Error::GenericError("GEOSGeom_getXMin_r failed".to_owned()))
-            } else {
-                Ok(value)
-            }
+        let buffered = self.buffer(-distance.abs(), config::default_quadsegs())?;
+        let mut polygons = Vec::new();
+        collect_polygons(&buffered, &mut polygons)?;
+        if polygons.is_empty() {
+            return Ok(None);
         }
+        Geometry::create_multipolygon(polygons).map(Some)
     }
 
-    #[cfg(any(feature = "v3_7_0", feature = "dox"))]
-    fn get_y_max(&self) -> GResult<f64> {
-        unsafe {
-            let mut value = 0.;
-            if GEOSGeom_getYMax_r(self.get_raw_context(), self.as_raw(), &mut value) == 0 {
-                Err(Error::GenericError("GEOSGeom_getYMax_r failed".to_owned()))
-            } else {
-                Ok(value)
-            }
+    fn ring_buffer(
+        &self,
+        inner_distance: f64,
+        outer_distance: f64,
+        quadsegs: i32,
+    ) -> GResult<Geometry<'a>> {
+        if !(inner_distance >= 0. && inner_distance < outer_distance) {
+            return Err(Error::GenericError(format!(
+                "ring_buffer needs 0 <= inner_distance < outer_distance, got {} and {}",
+                inner_distance, outer_distance
+            )));
         }
+        let outer = self.buffer(outer_distance, quadsegs)?;
+        let inner = self.buffer(inner_distance, quadsegs)?;
+        outer.difference(&inner)
     }
 
-    #[cfg(any(feature = "v3_7_0", feature = "dox"))]
-    fn get_y_min(&self) -> GResult<f64> {
-        unsafe {
-            let mut value = 0.;
-            if GEOSGeom_getYMin_r(self.get_raw_context(), self.as_raw(), &mut value) == 0 {
-                Err(Error::GenericError("GEOSGeom_getYMin_r failed".to_owned()))
-            } else {
-                Ok(value)
+    fn buffer_bands(&self, distances: &[f64], quadsegs: i32) -> GResult<Vec<Geometry<'a>>> {
+        let mut previous_distance = 0.;
+        for &distance in distances {
+            if distance.is_nan() || distance <= previous_distance {
+                return Err(Error::GenericError(
+                    "buffer_bands distances must be positive and increasing".to_owned(),
+                ));
             }
+            previous_distance = distance;
         }
+        let mut bands = Vec::with_capacity(distances.len());
+        let mut inner = self.buffer(0., quadsegs)?;
+        for &distance in distances {
+            let outer = self.buffer(distance, quadsegs)?;
+            bands.push(outer.difference(&inner)?);
+            inner = outer;
+        }
+        Ok(bands)
     }
 
-    #[cfg(any(feature = "v3_6_0", feature = "dox"))]
-    fn minimum_clearance(&self) -> GResult<f64> {
-        unsafe {
-            let mut value = 0.;
-            if GEOSMinimumClearance_r(self.get_raw_context(), self.as_raw(), &mut value) != 0 {
-                Err(Error::GenericError("GEOSMinimumClearance_r failed".to_owned()))
-            } else {
-                Ok(value)
+    fn stable_signature(&self, decimal_places: u32) -> GResult<String> {
+        fn hash_geometry<'c, G: Geom<'c>>(geom: &G, hash: &mut u64) -> GResult<()> {
+            let type_id: i32 = geom.geometry_type().into();
+            fnv1a(hash, &type_id.to_le_bytes());
+            match geom.geometry_type() {
+                GeometryTypes::Point | GeometryTypes::LineString | GeometryTypes::LinearRing => {
+                    let coords = if geom.is_empty()? {
+                        Vec::new()
+                    } else {
+                        coords_from_seq(&geom.get_coord_seq()?)?
+                    };
+                    let has_z = geom.has_z()?;
+                    fnv1a(hash, &(coords.len() as u64).to_le_bytes());
+                    for coord in coords {
+                        let dims = if has_z { 3 } else { 2 };
+                        for v in &coord[..dims] {
+                            fnv1a(hash, &v.to_bits().to_le_bytes());
+                        }
+                    }
+                }
+                GeometryTypes::Polygon => {
+                    if geom.is_empty()? {
+                        fnv1a(hash, &0u64.to_le_bytes());
+                    } else {
+                        let nb_interiors = geom.get_num_interior_rings()?;
+                        fnv1a(hash, &(nb_interiors as u64 + 1).to_le_bytes());
+                        hash_geometry(&geom.get_exterior_ring()?, hash)?;
+                        for n in 0..nb_interiors {
+                            hash_geometry(&geom.get_interior_ring_n(n as _)?, hash)?;
+                        }
+                    }
+                }
+                _ => {
+                    let nb_geoms = geom.get_num_geometries()?;
+                    fnv1a(hash, &(nb_geoms as u64).to_le_bytes());
+                    for n in 0..nb_geoms {
+                        hash_geometry(&geom.get_geometry_n(n)?, hash)?;
+                    }
+                }
             }
+            Ok(())
         }
-    }
 
-    #[cfg(any(feature = "v3_6_0", feature = "dox"))]
-    fn minimum_clearance_line(&self) -> GResult<Geometry<'a>> {
-        unsafe {
-            let ptr = GEOSMinimumClearanceLine_r(self.get_raw_context(), self.as_raw());
-            Geometry::new_from_raw(ptr, self.clone_context(), "minimum_clearance_line")
-        }
+        let canonical = self.canonicalize(decimal_places)?;
+        let mut hash = FNV_OFFSET_BASIS;
+        hash_geometry(&canonical, &mut hash)?;
+        Ok(format!(
+            "{}/{}/{}/{:016x}",
+            canonical.get_type()?,
+            canonical.get_num_geometries()?,
+            canonical.get_num_coordinates()?,
+            hash
+        ))
     }
 
-    #[cfg(any(feature = "v3_6_0", feature = "dox"))]
-    fn minimum_rotated_rectangle(&self) -> GResult<Geometry<'a>> {
-        unsafe {
-            let ptr = GEOSMinimumRotatedRectangle_r(self.get_raw_context(), self.as_raw());
-            Geometry::new_from_raw(ptr, self.clone_context(), "minimum_rotated_rectangle")
-        }
+    fn get_user_data(&self) -> usize {
+        unsafe { GEOSGeom_getUserData_r(self.get_raw_context(), self.as_raw()) as usize }
     }
 
-    #[cfg(any(feature = "v3_6_0", feature = "dox"))]
-    fn minimum_width(&self) -> GResult<Geometry<'a>> {
-        unsafe {
-            let ptr = GEOSMinimumWidth_r(self.get_raw_context(), self.as_raw());
-            Geometry::new_from_raw(ptr, self.clone_context(), "minimum_width")
-        }
+    fn walk<V: GeometryVisitor>(&self, visitor: &mut V) -> GResult<()> {
+        visitor::walk(self, visitor)
     }
 
-    fn delaunay_triangulation(&self, tolerance: f64, only_edges: bool) -> GResult<Geometry<'a>> {
-        unsafe {
-            let ptr = GEOSDelaunayTriangulation_r(
-                self.get_raw_context(),
-                self.as_raw(),
-                tolerance,
-                only_edges as _,
-            );
-            Geometry::new_from_raw(ptr, self.clone_context(), "delaunay_triangulation")
-        }
+    fn brief(&self) -> GResult<String> {
+        let type_name = self.get_type()?.to_uppercase();
+        let rect = match bbox::Rect::from_geom(self)? {
+            Some(rect) => rect,
+            None => return Ok(format!("{} EMPTY", type_name)),
+        };
+        let parts = match self.geometry_type() {
+            GeometryTypes::MultiPoint
+            | GeometryTypes::MultiLineString
+            | GeometryTypes::MultiPolygon
+            | GeometryTypes::GeometryCollection => {
+                format!("{} parts, ", self.get_num_geometries()?)
+            }
+            _ => String::new(),
+        };
+        Ok(format!(
+            "{}({}{} pts, bbox=[{} {}, {} {}])",
+            type_name,
+            parts,
+            self.get_num_coordinates()?,
+            rect.xmin,
+            rect.ymin,
+            rect.xmax,
+            rect.ymax
+        ))
     }
 
-    fn interpolate(&self, d: f64) -> GResult<Geometry<'a>> {
-        if self.geometry_type() != GeometryTypes::LineString {
-            return Err(Error::GenericError("Geometry must be a LineString".to_owned()));
-        }
-        unsafe {
-            let ptr = GEOSInterpolate_r(self.get_raw_context(), self.as_raw(), d);
-            Geometry::new_from_raw(ptr, self.clone_context(), "interpolate")
+    fn linearize(&self) -> GResult<Geometry<'a>> {
+        fn check_linear<'c, G: Geom<'c>>(geom: &G) -> GResult<()> {
+            match geom.geometry_type() {
+                GeometryTypes::GeometryCollection => {
+                    for n in 0..geom.get_num_geometries()? {
+                        check_linear(&geom.get_geometry_n(n)?)?;
+                    }
+                    Ok(())
+                }
+                geom_type if geom_type.is_curved() => unsupported_curve(geom_type),
+                _ => Ok(()),
+            }
         }
+
+        check_linear(self)?;
+        Ok(Geom::clone(self))
     }
 
-    fn interpolate_normalized(&self, d: f64) -> GResult<Geometry<'a>> {
-        if self.geometry_type() != GeometryTypes::LineString {
-            return Err(Error::GenericError("Geometry must be a LineString".to_owned()));
-        }
-        unsafe {
-            let ptr = GEOSInterpolateNormalized_r(self.get_raw_context(), self.as_raw(), d);
-            Geometry::new_from_raw(ptr, self.clone_context(), "interpolate_normalized")
+    fn overlap_ratio<'b, G: Geom<'b>>(&self, other: &G) -> GResult<f64> {
+        let area = self.area()?;
+        if area <= 0. {
+            return Ok(0.);
         }
+        let ratio = self.intersection(other)?.area()? / area;
+        Ok(ratio.min(1.))
     }
 
-    fn project<'b, G: Geom<'b>>(&self, p: &G) -> GResult<f64> {
-        if p.geometry_type() != GeometryTypes::Point {
-            return Err(Error::GenericError("Second geometry must be a Point".to_owned()));
-        }
-        unsafe {
-            let ret = GEOSProject_r(self.get_raw_context(), self.as_raw(), p.as_raw());
-            if (ret - -1.).abs() < 0.001 {
-                Err(Error::GenericError("GEOSProject_r failed".to_owned()))
-            } else {
-                Ok(ret)
-            }
+    fn contains_fraction<'b, G: Geom<'b>>(&self, other: &G, threshold: f64) -> GResult<bool> {
+        if !(0. ..=1.).contains(&threshold) {
+            return Err(Error::InvalidInput(format!(
+                "the threshold must be between 0 and 1, got {}",
+                threshold
+            )));
         }
+        Ok(other.overlap_ratio(self)? >= threshold)
     }
 
-    fn project_normalized<'b, G: Geom<'b>>(&self, p: &G) -> GResult<f64> {
-        if p.geometry_type() != GeometryTypes::Point {
-            return Err(Error::GenericError("Second geometry must be a Point".to_owned()));
+    fn largest_part(&self) -> GResult<Geometry<'a>> {
+        match self.geometry_type() {
+            GeometryTypes::MultiPoint
+            | GeometryTypes::MultiLineString
+            | GeometryTypes::MultiPolygon
+            | GeometryTypes::GeometryCollection => {}
+            _ => return Ok(Geom::clone(self)),
         }
-        unsafe {
-            let ret = GEOSProjectNormalized_r(self.get_raw_context(), self.as_raw(), p.as_raw());
-            if (ret - -1.).abs() < 0.001 {
-                Err(Error::GenericError("GEOSProjectNormalized_r failed".to_owned()))
-            } else {
-                Ok(ret)
+        let mut largest: Option<(usize, usize, f64)> = None;
+        for n in 0..self.get_num_geometries()? {
+            let part = self.get_geometry_n(n)?;
+            let dimension = part.get_num_dimensions()?;
+            let size = match dimension {
+                2 => part.area()?,
+                1 => part.length()?,
+                _ => 0.,
+            };
+            match largest {
+                Some((_, d, s)) if (d, s) >= (dimension, size) => {}
+                _ => largest = Some((n, dimension, size)),
             }
         }
-    }
-
-    fn node(&self) -> GResult<Geometry<'a>> {
-        unsafe {
-            let ptr = GEOSNode_r(self.get_raw_context(), self.as_raw());
-            Geometry::new_from_raw(ptr, self.clone_context(), "node")
+        match largest {
+            Some((n, _, _)) => {
+                let mut part = Geom::clone(&self.get_geometry_n(n)?);
+                part.set_srid(self.get_srid()?);
+                Ok(part)
+            }
+            None => Ok(Geom::clone(self)),
         }
     }
 
-    fn offset_curve(
+    fn filter_parts<F: FnMut(&ConstGeometry<'a, '_>) -> bool>(
         &self,
-        width: f64,
-        quadrant_segments: i32,
-        join_style: JoinStyle,
-        mitre_limit: f64,
+        mut keep: F,
     ) -> GResult<Geometry<'a>> {
-        if self.geometry_type() != GeometryTypes::LineString {
-            return Err(Error::GenericError("Geometry must be a LineString".to_owned()));
-        }
-        unsafe {
-            let ptr = GEOSOffsetCurve_r(self.get_raw_context(), self.as_raw(), width,
-                                        quadrant_segments, join_style.into(), mitre_limit);
-            Geometry::new_from_raw(ptr, self.clone_context(), "offset_curve")
-        }
+        retain_parts(self, |part| Ok(keep(part)))
     }
 
-    fn point_on_surface(&self) -> GResult<Geometry<'a>> {
-        unsafe {
-            let ptr = GEOSPointOnSurface_r(self.get_raw_context(), self.as_raw());
-            Geometry::new_from_raw(ptr, self.clone_context(), "point_on_surface")
-        }
+    fn drop_parts_smaller_than(&self, min_area: f64) -> GResult<Geometry<'a>> {
+        retain_parts(self, |part| match part.get_num_dimensions()? {
+            2 => Ok(part.area()? >= min_area),
+            1 => Ok(part.length()? >= min_area),
+            _ => Ok(true),
+        })
     }
 
-    fn polygonize_full(
-        &self,
-    ) -> GResult<(Geometry<'a>, Option<Geometry<'a>>, Option<Geometry<'a>>, Option<Geometry<'a>>)> {
-        let mut cuts: *mut GEOSGeometry = ::std::ptr::null_mut();
-        let mut dangles: *mut GEOSGeometry = ::std::ptr::null_mut();
-        let mut invalids: *mut GEOSGeometry = ::std::ptr::null_mut();
+    fn change_metrics<'b, G: Geom<'b>>(&self, other: &G) -> GResult<ChangeMetrics> {
+        let parts = overlay::decompose_pair(self, other)?;
+        Ok(ChangeMetrics {
+            added_area: parts.b_only.area()?,
+            removed_area: parts.a_only.area()?,
+            unchanged_area: parts.both.area()?,
+            hausdorff_distance: self.hausdorff_distance(other)?,
+        })
+    }
 
-        unsafe {
-            let ptr = GEOSPolygonize_full_r(
-                self.get_raw_context(),
-                self.as_raw(),
-                &mut cuts,
-                &mut dangles,
-                &mut invalids,
-            );
-            let cuts = if !cuts.is_null() {
-                Geometry::new_from_raw(cuts, self.clone_context(), "polygonize_full").ok()
-            } else {
-                None
-            };
-            let dangles = if !dangles.is_null() {
-                Geometry::new_from_raw(dangles, self.clone_context(), "polygonize_full").ok()
-            } else {
-                None
-            };
-            let invalids = if !invalids.is_null() {
-                Geometry::new_from_raw(invalids, self.clone_context(), "polygonize_full").ok()
-            } else {
-                None
-            };
-            Geometry::new_from_raw(ptr, self.clone_context(), "polygonize_full")
-                  .map(|x| (x, cuts, dangles, invalids))
+    fn interpolate_towards<'b, G: Geom<'b>>(&self, other: &G, t: f64) -> GResult<Geometry<'a>> {
+        if !(0. ..=1.).contains(&t) {
+            return Err(Error::InvalidInput(format!(
+                "t must be between 0 and 1, got {}",
+                t
+            )));
+        }
+        let geom_type = self.geometry_type();
+        if other.geometry_type() != geom_type {
+            return Err(Error::GenericError(format!(
+                "can't interpolate from a {:?} to a {:?}",
+                geom_type,
+                other.geometry_type()
+            )));
+        }
+        if self.is_empty()? || other.is_empty()? {
+            return Err(Error::GenericError(
+                "can't interpolate from or to an empty geometry".to_owned(),
+            ));
         }
+        let dims = if self.has_z()? && other.has_z()? {
+            CoordDimensions::ThreeD
+        } else {
+            CoordDimensions::TwoD
+        };
+        let morph = |a: &CoordSeq, b: &CoordSeq, closed: bool| -> GResult<CoordSeq<'a>> {
+            let coords = morph_coords(&coords_from_seq(a)?, &coords_from_seq(b)?, closed, t);
+            seq_from_coords(&coords, dims)
+        };
+        let mut res = match geom_type {
+            GeometryTypes::Point => {
+                let seq = morph(&self.get_coord_seq()?, &other.get_coord_seq()?, false)?;
+                Geometry::create_point(seq)?
+            }
+            GeometryTypes::LineString => Geometry::create_line_string(morph(
+                &self.get_coord_seq()?,
+                &other.get_coord_seq()?,
+                false,
+            )?)?,
+            GeometryTypes::LinearRing => Geometry::create_linear_ring(morph(
+                &self.get_coord_seq()?,
+                &other.get_coord_seq()?,
+                true,
+            )?)?,
+            GeometryTypes::Polygon => {
+                let ring = morph(
+                    &self.get_exterior_ring()?.get_coord_seq()?,
+                    &other.get_exterior_ring()?.get_coord_seq()?,
+                    true,
+                )?;
+                Geometry::create_polygon(Geometry::create_linear_ring(ring)?, Vec::new())?
+            }
+            _ => {
+                return Err(Error::GenericError(format!(
+                    "interpolate_towards doesn't support {:?} geometries",
+                    geom_type
+                )))
+            }
+        };
+        res.set_srid(self.get_srid()?);
+        Ok(res)
     }
 
-    fn shared_paths<'b, G: Geom<'b>>(&self, other: &G) -> GResult<Geometry<'a>> {
-        unsafe {
-            let ptr = GEOSSharedPaths_r(self.get_raw_context(), self.as_raw(), other.as_raw());
-            Geometry::new_from_raw(ptr, self.clone_context(), "shared_paths")
+    fn resample_exterior(&self, n: usize) -> GResult<Geometry<'a>> {
+        if self.geometry_type() != GeometryTypes::Polygon {
+            return Err(Error::GenericError(format!(
+                "resample_exterior expects a Polygon, got a {:?}",
+                self.geometry_type()
+            )));
+        }
+        if n < 3 {
+            return Err(Error::InvalidInput(format!(
+                "a ring needs at least 3 vertices, got {}",
+                n
+            )));
+        }
+        if self.is_empty()? {
+            return Ok(Geom::clone(self));
         }
+        let seq = self.get_exterior_ring()?.get_coord_seq()?;
+        let coords = resample_ring(&coords_from_seq(&seq)?, n);
+        let exterior = Geometry::create_linear_ring(seq_from_coords(&coords, seq.dimensions()?)?)?;
+        let interiors = (0..self.get_num_interior_rings()?)
+            .map(|i| Ok(Geom::clone(&self.get_interior_ring_n(i as _)?)))
+            .collect::<GResult<Vec<_>>>()?;
+        let mut res = Geometry::create_polygon(exterior, interiors)?;
+        res.set_srid(self.get_srid()?);
+        Ok(res)
     }
 
-    fn to_hex(&self) -> GResult<CVec<u8>> {
-        let mut size = 0;
-        unsafe {
-            let ptr = GEOSGeomToHEX_buf_r(self.get_raw_context(), self.as_raw(), &mut size);
-            if ptr.is_null() {
-                Err(Error::NoConstructionFromNullPtr(
-                    "Geometry::to_hex failed: GEOSGeomToHEX_buf_r returned null pointer".to_owned())
-                )
-            } else {
-                Ok(CVec::new(ptr, size as _))
+    #[cfg(any(feature = "v3_6_0", feature = "dox"))]
+    fn oriented_bbox(&self) -> GResult<(Geometry<'a>, f64)> {
+        let rectangle = self.minimum_rotated_rectangle()?;
+        let coords = match rectangle.geometry_type() {
+            GeometryTypes::Polygon if !rectangle.is_empty()? => {
+                coords_from_seq(&rectangle.get_exterior_ring()?.get_coord_seq()?)?
+            }
+            GeometryTypes::LineString => coords_from_seq(&rectangle.get_coord_seq()?)?,
+            _ => Vec::new(),
+        };
+        let mut longest = 0.;
+        let mut angle = 0.;
+        for w in coords.windows(2) {
+            let (dx, dy) = (w[1][0] - w[0][0], w[1][1] - w[0][1]);
+            if dx.hypot(dy) > longest {
+                longest = dx.hypot(dy);
+                angle = dy.atan2(dx).to_degrees().rem_euclid(180.);
+                // `rem_euclid` rounds tiny negative angles up to 180.
+                if angle >= 180. {
+                    angle = 0.;
+                }
             }
         }
+        Ok((rectangle, angle))
     }
 
-    fn to_wkb(&self) -> GResult<CVec<u8>> {
-        let mut size = 0;
-        unsafe {
-            let ptr = GEOSGeomToWKB_buf_r(self.get_raw_context(), self.as_raw(), &mut size);
-            if ptr.is_null() {
-                Err(Error::NoConstructionFromNullPtr(
-                    "Geometry::to_wkb failed: GEOSGeomToWKB_buf_r returned null pointer".to_owned())
-                )
-            } else {
-                Ok(CVec::new(ptr, size as _))
+    fn with_deadline(&self, timeout: Duration) -> Deadline<'_, Self> {
+        Deadline::new(self, timeout)
+    }
+
+    fn lenient_collections(&self) -> LenientCollections<'_, Self> {
+        LenientCollections::new(self)
+    }
+
+    #[cfg(any(feature = "v3_9_0", feature = "dox"))]
+    fn overlay<'b, G: Geom<'b>>(
+        &self,
+        other: &G,
+        op: OverlayOp,
+        algorithm: OverlayAlgorithm,
+    ) -> GResult<Geometry<'a>> {
+        match (op, algorithm) {
+            (OverlayOp::Intersection, OverlayAlgorithm::Default) => self.intersection(other),
+            (OverlayOp::Union, OverlayAlgorithm::Default) => self.union(other),
+            (OverlayOp::Difference, OverlayAlgorithm::Default) => self.difference(other),
+            (OverlayOp::SymDifference, OverlayAlgorithm::Default) => self.sym_difference(other),
+            (OverlayOp::Intersection, OverlayAlgorithm::Ng { grid_size }) => {
+                self.intersection_prec(other, grid_size)
+            }
+            (OverlayOp::Union, OverlayAlgorithm::Ng { grid_size }) => {
+                self.union_prec(other, grid_size)
+            }
+            (OverlayOp::Difference, OverlayAlgorithm::Ng { grid_size }) => {
+                self.difference_prec(other, grid_size)
+            }
+            (OverlayOp::SymDifference, OverlayAlgorithm::Ng { grid_size }) => {
+                self.sym_difference_prec(other, grid_size)
             }
         }
     }
 
-    #[allow(clippy::needless_lifetimes)]
-    fn to_prepared_geom<'c>(&'c self) -> GResult<PreparedGeometry<'c>> {
-        PreparedGeometry::new(self)
+    #[cfg(any(feature = "v3_9_0", feature = "dox"))]
+    fn intersection_prec<'b, G: Geom<'b>>(
+        &self,
+        other: &G,
+        grid_size: f64,
+    ) -> GResult<Geometry<'a>> {
+        overlay::check_grid_size(grid_size)?;
+        check_empty(self.get_context_handle(), self, "intersection_prec")?;
+        check_empty(self.get_context_handle(), other, "intersection_prec")?;
+        unsafe {
+            let ptr = overlay::ng::GEOSIntersectionPrec_r(
+                self.get_raw_context(), self.as_raw(), other.as_raw(), grid_size);
+            Geometry::new_from_overlay(ptr, self.clone_context(), "intersection_prec")
+        }
     }
 
-    fn clone(&self) -> Geometry<'a> {
-        let context = self.clone_context();
-        let ptr = unsafe { GEOSGeom_clone_r(context.as_raw(), self.as_raw()) };
-        if ptr.is_null() {
-            panic!("Couldn't clone geometry...");
-        }
-        Geometry {
-            ptr: PtrWrap(ptr),
-            context,
+    #[cfg(any(feature = "v3_9_0", feature = "dox"))]
+    fn union_prec<'b, G: Geom<'b>>(&self, other: &G, grid_size: f64) -> GResult<Geometry<'a>> {
+        overlay::check_grid_size(grid_size)?;
+        check_empty(self.get_context_handle(), self, "union_prec")?;
+        check_empty(self.get_context_handle(), other, "union_prec")?;
+        unsafe {
+            let ptr = overlay::ng::GEOSUnionPrec_r(
+                self.get_raw_context(), self.as_raw(), other.as_raw(), grid_size);
+            Geometry::new_from_overlay(ptr, self.clone_context(), "union_prec")
         }
     }
 
-    fn get_geometry_n<'c>(&'c self, n: usize) -> GResult<ConstGeometry<'a, 'c>> {
+    #[cfg(any(feature = "v3_9_0", feature = "dox"))]
+    fn difference_prec<'b, G: Geom<'b>>(
+        &self,
+        other: &G,
+        grid_size: f64,
+    ) -> GResult<Geometry<'a>> {
+        overlay::check_grid_size(grid_size)?;
+        check_empty(self.get_context_handle(), self, "difference_prec")?;
+        check_empty(self.get_context_handle(), other, "difference_prec")?;
         unsafe {
-            let ptr = GEOSGetGeometryN_r(self.get_raw_context(), self.as_raw(), n as _);
-            ConstGeometry::new_from_raw(ptr, self$(.$field)?, "get_geometry_n")
+            let ptr = overlay::ng::GEOSDifferencePrec_r(
+                self.get_raw_context(), self.as_raw(), other.as_raw(), grid_size);
+            Geometry::new_from_overlay(ptr, self.clone_context(), "difference_prec")
         }
     }
 
-    fn get_interior_ring_n<'c>(&'c self, n: u32) -> GResult<ConstGeometry<'a, 'c>> {
+    #[cfg(any(feature = "v3_9_0", feature = "dox"))]
+    fn sym_difference_prec<'b, G: Geom<'b>>(
+        &self,
+        other: &G,
+        grid_size: f64,
+    ) -> GResult<Geometry<'a>> {
+        overlay::check_grid_size(grid_size)?;
+        check_empty(self.get_context_handle(), self, "sym_difference_prec")?;
+        check_empty(self.get_context_handle(), other, "sym_difference_prec")?;
         unsafe {
-            let ptr = GEOSGetInteriorRingN_r(self.get_raw_context(), self.as_raw(), n as _);
-            ConstGeometry::new_from_raw(ptr, self$(.$field)?, "get_interior_ring_n")
+            let ptr = overlay::ng::GEOSSymDifferencePrec_r(
+                self.get_raw_context(), self.as_raw(), other.as_raw(), grid_size);
+            Geometry::new_from_overlay(ptr, self.clone_context(), "sym_difference_prec")
         }
     }
 
-    fn get_exterior_ring<'c>(&'c self) -> GResult<ConstGeometry<'a, 'c>> {
+    #[cfg(any(feature = "v3_9_0", feature = "dox"))]
+    fn unary_union_prec(&self, grid_size: f64) -> GResult<Geometry<'a>> {
+        overlay::check_grid_size(grid_size)?;
+        check_empty(self.get_context_handle(), self, "unary_union_prec")?;
         unsafe {
-            let ptr = GEOSGetExteriorRing_r(self.get_raw_context(), self.as_raw());
-            ConstGeometry::new_from_raw(ptr, self$(.$field)?, "get_exterior_ring")
+            let ptr =
+                overlay::ng::GEOSUnaryUnionPrec_r(self.get_raw_context(), self.as_raw(), grid_size);
+            Geometry::new_from_overlay(ptr, self.clone_context(), "unary_union_prec")
         }
     }
 }
@@ -2235,6 +4436,18 @@ impl<'a, 'b$(, $lt)?, G: Geom<'b>> PartialEq<G> for $ty_name<'a$(, $lt)?> {
     }
 }
 
+impl<'a$(, $lt)?> fmt::Display for $ty_name<'a$(, $lt)?> {
+    /// Writes the WKT representation of the geometry. If a precision is given (like in
+    /// `format!("{:.3}", geom)`), it is used as the WKT rounding precision.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let wkt = match f.precision() {
+            Some(precision) => self.to_wkt_precision(precision as _),
+            None => self.to_wkt(),
+        };
+        f.write_str(&wkt.map_err(|_| fmt::Error)?)
+    }
+}
+
 unsafe impl<'a$(, $lt)?> Send for $ty_name<'a$(, $lt)?> {}
 unsafe impl<'a$(, $lt)?> Sync for $ty_name<'a$(, $lt)?> {}
     )
@@ -2249,28 +4462,55 @@ impl<'a> Geometry<'a> {
     /// # Example
     ///
     /// ```
-    /// use geos::Geometry;
+    /// use geos::{Error, Geometry};
     ///
     /// let point_geom = Geometry::new_from_wkt("POINT (2.5 2.5)").expect("Invalid geometry");
+    ///
+    /// // Nul bytes can't be given to GEOS.
+    /// match Geometry::new_from_wkt("POINT (2.5 2.5)\0") {
+    ///     Err(Error::InvalidInput(_)) => {}
+    ///     _ => panic!("expected an InvalidInput error"),
+    /// }
     /// ```
     pub fn new_from_wkt(wkt: &str) -> GResult<Geometry<'a>> {
-        match ContextHandle::init_e(Some("Geometry::new_from_wkt")) {
-            Ok(context_handle) => match CString::new(wkt) {
-                Ok(c_str) => unsafe {
-                    let reader = GEOSWKTReader_create_r(context_handle.as_raw());
-                    let ptr = GEOSWKTReader_read_r(context_handle.as_raw(), reader, c_str.as_ptr());
-                    GEOSWKTReader_destroy_r(context_handle.as_raw(), reader);
-                    Geometry::new_from_raw(ptr, Arc::new(context_handle), "new_from_wkt")
-                },
-                Err(e) => Err(Error::GenericError(format!(
-                    "Conversion to CString failed: {}",
-                    e
-                ))),
-            },
+        match ContextHandle::init_default("Geometry::new_from_wkt") {
+            Ok(context_handle) => Geometry::new_from_wkt_with_context(wkt, context_handle),
             Err(e) => Err(e),
         }
     }
 
+    /// Same as [`new_from_wkt`](#method.new_from_wkt), but the created geometry uses
+    /// the given `context` instead of a new one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{ContextHandle, ContextHandling, Geometry};
+    /// use std::sync::Arc;
+    ///
+    /// let context = Arc::new(ContextHandle::init().expect("invalid init"));
+    /// let geom = Geometry::new_from_wkt_with_context("POINT (2.5 2.5)", Arc::clone(&context))
+    ///                     .expect("new_from_wkt_with_context failed");
+    /// assert!(Arc::ptr_eq(&geom.clone_context(), &context));
+    /// ```
+    pub fn new_from_wkt_with_context(
+        wkt: &str,
+        context: Arc<ContextHandle<'a>>,
+    ) -> GResult<Geometry<'a>> {
+        match CString::new(wkt) {
+            Ok(c_str) => unsafe {
+                let reader = GEOSWKTReader_create_r(context.as_raw());
+                let ptr = GEOSWKTReader_read_r(context.as_raw(), reader, c_str.as_ptr());
+                GEOSWKTReader_destroy_r(context.as_raw(), reader);
+                Geometry::new_from_parser(ptr, context, "WKT", wkt.as_bytes())
+            },
+            Err(e) => Err(Error::InvalidInput(format!(
+                "Conversion to CString failed: {}",
+                e
+            ))),
+        }
+    }
+
     /// Create a new [`Geometry`] from the HEX format.
     ///
     /// # Example
@@ -2287,15 +4527,37 @@ impl<'a> Geometry<'a> {
     /// assert_eq!(point_geom.equals(&new_geom), Ok(true));
     /// ```
     pub fn new_from_hex(hex: &[u8]) -> GResult<Geometry<'a>> {
-        match ContextHandle::init_e(Some("Geometry::new_from_hex")) {
-            Ok(context) => unsafe {
-                let ptr = GEOSGeomFromHEX_buf_r(context.as_raw(), hex.as_ptr(), hex.len());
-                Geometry::new_from_raw(ptr, Arc::new(context), "new_from_hex")
-            },
+        match ContextHandle::init_default("Geometry::new_from_hex") {
+            Ok(context) => Geometry::new_from_hex_with_context(hex, context),
             Err(e) => Err(e),
         }
     }
 
+    /// Same as [`new_from_hex`](#method.new_from_hex), but the created geometry uses
+    /// the given `context` instead of a new one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{ContextHandle, ContextHandling, Geometry};
+    /// use std::sync::Arc;
+    ///
+    /// let context = Arc::new(ContextHandle::init().expect("invalid init"));
+    /// let hex = b"010100000000000000000004400000000000000440";
+    /// let geom = Geometry::new_from_hex_with_context(hex, Arc::clone(&context))
+    ///                     .expect("new_from_hex_with_context failed");
+    /// assert!(Arc::ptr_eq(&geom.clone_context(), &context));
+    /// ```
+    pub fn new_from_hex_with_context(
+        hex: &[u8],
+        context: Arc<ContextHandle<'a>>,
+    ) -> GResult<Geometry<'a>> {
+        unsafe {
+            let ptr = GEOSGeomFromHEX_buf_r(context.as_raw(), hex.as_ptr(), hex.len());
+            Geometry::new_from_parser(ptr, context, "HEX", hex)
+        }
+    }
+
     /// Create a new [`Geometry`] from the WKB format.
     ///
     /// # Example
@@ -2312,15 +4574,78 @@ impl<'a> Geometry<'a> {
     /// assert_eq!(point_geom.equals(&new_geom), Ok(true));
     /// ```
     pub fn new_from_wkb(wkb: &[u8]) -> GResult<Geometry<'a>> {
-        match ContextHandle::init_e(Some("Geometry::new_from_wkb")) {
-            Ok(context) => unsafe {
-                let ptr = GEOSGeomFromWKB_buf_r(context.as_raw(), wkb.as_ptr(), wkb.len());
-                Geometry::new_from_raw(ptr, Arc::new(context), "new_from_wkb")
-            },
+        match ContextHandle::init_default("Geometry::new_from_wkb") {
+            Ok(context) => Geometry::new_from_wkb_with_context(wkb, context),
             Err(e) => Err(e),
         }
     }
 
+    /// Same as [`new_from_wkb`](#method.new_from_wkb), but the created geometry uses
+    /// the given `context` instead of a new one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{ContextHandle, ContextHandling, Geometry};
+    /// use std::sync::Arc;
+    ///
+    /// let context = Arc::new(ContextHandle::init().expect("invalid init"));
+    /// let wkb = [1u8, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 4, 64, 0, 0, 0, 0, 0, 0, 4, 64];
+    /// let geom = Geometry::new_from_wkb_with_context(&wkb, Arc::clone(&context))
+    ///                     .expect("new_from_wkb_with_context failed");
+    /// assert!(Arc::ptr_eq(&geom.clone_context(), &context));
+    /// ```
+    pub fn new_from_wkb_with_context(
+        wkb: &[u8],
+        context: Arc<ContextHandle<'a>>,
+    ) -> GResult<Geometry<'a>> {
+        unsafe {
+            let ptr = GEOSGeomFromWKB_buf_r(context.as_raw(), wkb.as_ptr(), wkb.len());
+            Geometry::new_from_parser(ptr, context, "WKB", wkb)
+        }
+    }
+
+    /// Creates the polygon of the cell described by the given geohash (case insensitive).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let cell = Geometry::from_geohash("ezs42").expect("Invalid geohash");
+    /// let point = Geometry::new_from_wkt("POINT (-5.6 42.6)").expect("Invalid geometry");
+    /// assert_eq!(cell.contains(&point), Ok(true));
+    /// assert_eq!(point.to_geohash(5), Ok("ezs42".to_owned()));
+    ///
+    /// assert!(Geometry::from_geohash("ezs4a").is_err());
+    /// ```
+    pub fn from_geohash(hash: &str) -> GResult<Geometry<'a>> {
+        let context = ContextHandle::init_default("Geometry::from_geohash")?;
+        Geometry::from_geohash_with_context(hash, context)
+    }
+
+    /// Same as [`from_geohash`](#method.from_geohash), but the created geometry uses the given
+    /// `context` instead of a new one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{ContextHandle, ContextHandling, Geometry};
+    /// use std::sync::Arc;
+    ///
+    /// let context = Arc::new(ContextHandle::init().expect("invalid init"));
+    /// let cell = Geometry::from_geohash_with_context("ezs42", Arc::clone(&context))
+    ///                     .expect("Invalid geohash");
+    /// assert!(Arc::ptr_eq(&cell.clone_context(), &context));
+    /// ```
+    pub fn from_geohash_with_context(
+        hash: &str,
+        context: Arc<ContextHandle<'a>>,
+    ) -> GResult<Geometry<'a>> {
+        let (xmin, ymin, xmax, ymax) = geohash::decode_bbox(hash)?;
+        create_rectangle_with_context(xmin, ymin, xmax, ymax, context)
+    }
+
     /// Creates an areal geometry formed by the constituent linework of given geometry.
     ///
     /// You can find new illustrations on [postgis](https://postgis.net/docs/ST_BuildArea.html)
@@ -2383,7 +4708,7 @@ impl<'a> Geometry<'a> {
     ///                      .expect("Failed to create geometry");
     ///
     /// let polygonized = Geometry::polygonize(&[geom1, geom2]).expect("polygonize failed");
-    /// assert_eq!(polygonized.to_wkt().unwrap(),
+    /// assert_eq!(polygonized.to_wkt_legacy().unwrap(),
     ///            "GEOMETRYCOLLECTION (POLYGON ((-71.0408780000000064 42.2856779999999972, \
     ///                                           -71.0409429999999986 42.2856000000000023, \
     ///                                           -71.0409599999999983 42.2857520000000022, \
@@ -2400,10 +4725,7 @@ impl<'a> Geometry<'a> {
         unsafe {
             let context = match geometries.get(0) {
                 Some(g) => g.borrow().clone_context(),
-                None => match ContextHandle::init_e(Some("Geometry::polygonize")) {
-                    Ok(context) => Arc::new(context),
-                    Err(e) => return Err(e),
-                },
+                None => ContextHandle::init_default("Geometry::polygonize")?,
             };
             let geoms = geometries
                 .iter()
@@ -2421,10 +4743,7 @@ impl<'a> Geometry<'a> {
         unsafe {
             let context = match geometries.get(0) {
                 Some(g) => g.borrow().clone_context(),
-                None => match ContextHandle::init_e(Some("Geometry::polygonizer_get_cut_edges")) {
-                    Ok(context) => Arc::new(context),
-                    Err(e) => return Err(e),
-                },
+                None => ContextHandle::init_default("Geometry::polygonizer_get_cut_edges")?,
             };
             let geoms = geometries
                 .iter()
@@ -2492,20 +4811,372 @@ impl<'a> Geometry<'a> {
 
     /// Returns a simplified version of the given geometry.
     pub fn simplify(&self, tolerance: f64) -> GResult<Geometry<'a>> {
+        check_empty(self.get_context_handle(), self, "simplify")?;
         unsafe {
             let ptr = GEOSSimplify_r(self.get_raw_context(), self.as_raw(), tolerance);
             Geometry::new_from_raw(ptr, self.clone_context(), "simplify")
         }
     }
 
-    /// Returns a simplified version of the given geometry. It will avoid creating invalid derived
-    /// geometries.
-    pub fn topology_preserve_simplify(&self, tolerance: f64) -> GResult<Geometry<'a>> {
-        unsafe {
-            let ptr =
-                GEOSTopologyPreserveSimplify_r(self.get_raw_context(), self.as_raw(), tolerance);
-            Geometry::new_from_raw(ptr, self.clone_context(), "topology_preserve_simplify")
-        }
+    /// Returns a simplified version of the given geometry. It will avoid creating invalid derived
+    /// geometries.
+    pub fn topology_preserve_simplify(&self, tolerance: f64) -> GResult<Geometry<'a>> {
+        check_empty(
+            self.get_context_handle(),
+            self,
+            "topology_preserve_simplify",
+        )?;
+        unsafe {
+            let ptr =
+                GEOSTopologyPreserveSimplify_r(self.get_raw_context(), self.as_raw(), tolerance);
+            Geometry::new_from_raw(ptr, self.clone_context(), "topology_preserve_simplify")
+        }
+    }
+
+    /// Runs the Douglas-Peucker algorithm (like [`simplify`](#method.simplify)) on the vertices
+    /// of a `LineString` or a `LinearRing`, returning for each of them whether it is kept.
+    ///
+    /// This allows to carry along the data attached to the vertices, like the timestamps of a
+    /// GPS trace. The first and last vertices are always kept.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::Geometry;
+    ///
+    /// let trace = Geometry::new_from_wkt("LINESTRING(0 0, 1 0.1, 2 -0.1, 3 5, 4 6, 5 7)")
+    ///                      .expect("Invalid geometry");
+    /// let timestamps = [10, 20, 30, 40, 50, 60];
+    ///
+    /// let mask = trace.simplify_mask(0.5).expect("simplify_mask failed");
+    /// assert_eq!(mask, vec![true, false, true, true, false, true]);
+    ///
+    /// let kept = timestamps.iter().zip(&mask).filter(|(_, keep)| **keep).map(|(t, _)| *t);
+    /// assert_eq!(kept.collect::<Vec<_>>(), vec![10, 30, 40, 60]);
+    /// ```
+    pub fn simplify_mask(&self, tolerance: f64) -> GResult<Vec<bool>> {
+        match self.geometry_type() {
+            GeometryTypes::LineString | GeometryTypes::LinearRing => {}
+            _ => {
+                return Err(Error::GenericError(
+                    "Geometry must be a LineString or a LinearRing".to_owned(),
+                ))
+            }
+        }
+        if self.is_empty()? {
+            return Ok(Vec::new());
+        }
+        let coords = coords_from_seq(&self.get_coord_seq()?)?;
+        Ok(douglas_peucker_mask(&coords, tolerance))
+    }
+
+    /// Returns a version of `self` simplified with the Visvalingam-Whyatt algorithm: vertices are
+    /// removed as long as the triangle they form with their neighbours has an area smaller than
+    /// `tolerance`, starting with the smallest one.
+    ///
+    /// Unlike the distance-based [`simplify`](#method.simplify), it tends to give more natural
+    /// shapes to coastlines and other natural features. Line strings keep at least 2 points and
+    /// rings at least 4, but the result can be invalid: see
+    /// [`topology_preserve_simplify_vw`](#method.topology_preserve_simplify_vw).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let line = Geometry::new_from_wkt("LINESTRING(0 0, 1 0.1, 2 0, 3 3, 4 0)")
+    ///                     .expect("Invalid geometry");
+    /// let simplified = line.simplify_vw(0.5).expect("simplify_vw failed");
+    /// assert_eq!(simplified.to_wkt().unwrap(), "LINESTRING (0 0, 2 0, 3 3, 4 0)");
+    /// ```
+    pub fn simplify_vw(&self, tolerance: f64) -> GResult<Geometry<'a>> {
+        map_coord_seqs(self, &mut |seq, geom_type| {
+            let coords = coords_from_seq(seq)?;
+            let mask = visvalingam_whyatt_mask(
+                &coords,
+                tolerance,
+                vw_min_points(geom_type),
+                |_, _, _, _| true,
+            );
+            seq_from_coords(&apply_mask(&coords, &mask), seq.dimensions()?)
+        })
+    }
+
+    /// Same as [`simplify_vw`](#method.simplify_vw), but a vertex isn't removed if it would
+    /// create an intersection with another segment (of any part or ring of `self`), or move
+    /// another vertex to the other side of the line. Like
+    /// [`topology_preserve_simplify`](#method.topology_preserve_simplify), this avoids creating
+    /// invalid geometries.
+    ///
+    /// Each removal is checked against all the segments of the geometry, so it's a lot slower
+    /// than `simplify_vw` on big geometries.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// // The hole is inside of the spike of the exterior ring.
+    /// let geom = Geometry::new_from_wkt("POLYGON((0 0, 10 0, 10 10, 5 11, 0 10, 0 0),
+    ///                                            (4.5 10.2, 5.5 10.2, 5 10.5, 4.5 10.2))")
+    ///                     .expect("Invalid geometry");
+    ///
+    /// let simplified = geom.simplify_vw(6.).expect("simplify_vw failed");
+    /// assert_eq!(simplified.get_num_coordinates(), Ok(9));
+    /// assert_eq!(simplified.is_valid(), false);
+    ///
+    /// let simplified = geom.topology_preserve_simplify_vw(6.)
+    ///                      .expect("topology_preserve_simplify_vw failed");
+    /// assert_eq!(simplified.get_num_coordinates(), Ok(10));
+    /// assert_eq!(simplified.is_valid(), true);
+    /// ```
+    pub fn topology_preserve_simplify_vw(&self, tolerance: f64) -> GResult<Geometry<'a>> {
+        let mut all = Vec::new();
+        visit_coord_seqs(self, &mut |seq, _| {
+            all.push(coords_from_seq(seq)?);
+            Ok(())
+        })?;
+        let mut current = 0;
+        map_coord_seqs(self, &mut |seq, geom_type| {
+            let coords = all[current].clone();
+            let mask = visvalingam_whyatt_mask(
+                &coords,
+                tolerance,
+                vw_min_points(geom_type),
+                |prev, index, next, mask| vw_keeps_topology(&all, current, mask, prev, index, next),
+            );
+            // The next parts are checked against the simplified version of this one.
+            all[current] = apply_mask(&coords, &mask);
+            current += 1;
+            seq_from_coords(&all[current - 1], seq.dimensions()?)
+        })
+    }
+
+    /// Returns progressively simplified versions of `self`, one for each of the given
+    /// `tolerances` (which must be sorted in increasing order), using
+    /// [`topology_preserve_simplify`](#method.topology_preserve_simplify).
+    ///
+    /// Each level is simplified from the previous one rather than from `self`, which is a lot
+    /// faster for big geometries.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let geom = Geometry::new_from_wkt("POINT (0 0)").expect("Invalid geometry")
+    ///                     .buffer(100., 16).expect("buffer failed");
+    /// let levels = geom.pyramid(&[0.1, 1., 10.]).expect("pyramid failed");
+    ///
+    /// assert_eq!(levels.len(), 3);
+    /// assert!(levels[0].get_num_coordinates().unwrap() <= geom.get_num_coordinates().unwrap());
+    /// assert!(levels[2].get_num_coordinates().unwrap() < levels[0].get_num_coordinates().unwrap());
+    ///
+    /// // Tolerances must be sorted.
+    /// assert!(geom.pyramid(&[10., 1.]).is_err());
+    /// ```
+    pub fn pyramid(&self, tolerances: &[f64]) -> GResult<Vec<Geometry<'a>>> {
+        if tolerances.windows(2).any(|w| w[0] > w[1]) {
+            return Err(Error::GenericError(
+                "tolerances must be sorted in increasing order".to_owned(),
+            ));
+        }
+        let mut levels: Vec<Geometry<'a>> = Vec::with_capacity(tolerances.len());
+        for tolerance in tolerances {
+            let level = match levels.last() {
+                Some(previous) => previous.topology_preserve_simplify(*tolerance)?,
+                None => self.topology_preserve_simplify(*tolerance)?,
+            };
+            levels.push(level);
+        }
+        Ok(levels)
+    }
+
+    /// Same as [`Geom::is_valid`], but the result is remembered until the geometry is modified,
+    /// so checking it again (like before each operation of a pipeline) is free.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::Geometry;
+    ///
+    /// let mut geom = Geometry::new_from_wkt("POLYGON((0 0, 1 1, 1 0, 0 1, 0 0))")
+    ///                         .expect("Invalid geometry");
+    /// assert!(!geom.is_valid_cached());
+    /// assert!(!geom.is_valid_cached());
+    ///
+    /// // Swapping two corners turns the bowtie into a square.
+    /// geom.edit_coords(|coord| match [coord[0], coord[1]] {
+    ///     [1., 1.] => coord[1] = 0.,
+    ///     [1., 0.] => coord[1] = 1.,
+    ///     _ => {}
+    /// }).expect("edit_coords failed");
+    /// assert!(geom.is_valid_cached());
+    /// ```
+    pub fn is_valid_cached(&self) -> bool {
+        match self.validity.load(atomic::Ordering::Relaxed) {
+            VALIDITY_VALID => true,
+            VALIDITY_INVALID => false,
+            _ => {
+                let valid = self.is_valid();
+                let validity = if valid {
+                    VALIDITY_VALID
+                } else {
+                    VALIDITY_INVALID
+                };
+                self.validity.store(validity, atomic::Ordering::Relaxed);
+                valid
+            }
+        }
+    }
+
+    /// Destroys the geometry right away, returning the error GEOS reported while doing so if any
+    /// (dropping it does the same, but silently).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::Geometry;
+    ///
+    /// let geom = Geometry::new_from_wkt("POINT (1 2)").expect("Invalid geometry");
+    /// assert_eq!(geom.destroy(), Ok(()));
+    /// ```
+    pub fn destroy(mut self) -> GResult<()> {
+        // Flushes the errors of the previous calls, so only the ones of the destruction remain.
+        self.context.get_last_error();
+        let ptr = std::mem::replace(&mut self.ptr, PtrWrap(std::ptr::null_mut()));
+        unsafe { GEOSGeom_destroy_r(self.get_raw_context(), *ptr) };
+        match self.context.get_last_error() {
+            Some(error) => Err(Error::GenericError(format!(
+                "GEOSGeom_destroy_r failed: {}",
+                error
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// Moves the geometry to a context created by the crate, so it's not tied to the lifetime
+    /// of the message handlers of its current context anymore. Since geometries are `Send`, the
+    /// result can be stored in long-lived state or moved across threads and async tasks freely.
+    /// No copy of the geometry is made, and its SRID and user data are kept.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{ContextHandle, Geom, Geometry};
+    /// use std::sync::Arc;
+    ///
+    /// fn keep<T: Send + 'static>(value: T) -> T {
+    ///     value
+    /// }
+    ///
+    /// let messages = std::sync::Mutex::new(Vec::new());
+    /// let context = ContextHandle::builder()
+    ///     .error_message_handler(|msg| messages.lock().unwrap().push(msg.to_owned()))
+    ///     .build()
+    ///     .expect("build failed");
+    /// let wkb = [1u8, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 4, 64, 0, 0, 0, 0, 0, 0, 4, 64];
+    /// let geom = Geometry::new_from_wkb_with_context(&wkb, Arc::new(context))
+    ///                     .expect("new_from_wkb_with_context failed");
+    ///
+    /// let geom = keep(geom.into_static().expect("into_static failed"));
+    /// assert_eq!(geom.to_wkt().unwrap(), "POINT (2.5 2.5)");
+    /// ```
+    pub fn into_static(mut self) -> GResult<Geometry<'static>> {
+        let context = Arc::new(ContextHandle::init_e(Some("Geometry::into_static"))?);
+        let ptr = std::mem::replace(&mut self.ptr, PtrWrap(std::ptr::null_mut()));
+        unsafe { Geometry::new_from_raw(*ptr, context, "into_static") }
+    }
+
+    /// Consumes the geometry and returns it as (extended) WKB, in a plain `Vec` which can be
+    /// sent anywhere. Unlike [`to_wkb`](trait.Geom.html#tymethod.to_wkb), the SRID and the Z
+    /// coordinates are included, so [`from_wkb_owned`](#method.from_wkb_owned) gives back the
+    /// same geometry.
+    ///
+    /// The writer is created once per thread and reused by the next calls.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let mut geom = Geometry::new_from_wkt("POINT Z (1 2 3)").expect("Invalid geometry");
+    /// geom.set_srid(4326);
+    /// let wkb = geom.into_wkb_owned().expect("into_wkb_owned failed");
+    ///
+    /// let geom = Geometry::from_wkb_owned(&wkb).expect("from_wkb_owned failed");
+    /// let coords = geom.get_coord_seq().expect("get_coord_seq failed");
+    /// assert_eq!(coords.get_z(0), Ok(3.));
+    /// assert_eq!(geom.get_srid(), Ok(4326));
+    /// ```
+    pub fn into_wkb_owned(self) -> GResult<Vec<u8>> {
+        WKB_OWNED_WRITER.with(|writer| {
+            let mut writer = writer.borrow_mut();
+            if writer.is_none() {
+                let context = ContextHandle::init_e(Some("Geometry::into_wkb_owned"))?;
+                let mut new_writer = WKBWriter::new_with_context(Arc::new(context))?;
+                new_writer.set_output_dimension(OutputDimension::ThreeD);
+                new_writer.set_include_SRID(true);
+                *writer = Some(new_writer);
+            }
+            match *writer {
+                Some(ref mut writer) => Ok(writer.write_wkb(&self)?.as_ref().to_vec()),
+                None => unreachable!(),
+            }
+        })
+    }
+
+    /// Reads a geometry written by [`into_wkb_owned`](#method.into_wkb_owned) (or any WKB or
+    /// extended WKB). The returned geometry is `Send` and `'static`.
+    ///
+    /// Like [`into_wkb_owned`](#method.into_wkb_owned), the reader is created once per thread
+    /// and reused by the next calls. Each geometry still gets its own context, so it can be
+    /// moved to another thread without sharing it.
+    pub fn from_wkb_owned(wkb: &[u8]) -> GResult<Geometry<'static>> {
+        let context = Arc::new(ContextHandle::init_e(Some("Geometry::from_wkb_owned"))?);
+        Geometry::from_wkb_owned_with_context(wkb, context)
+    }
+
+    /// Same as [`from_wkb_owned`](#method.from_wkb_owned), but the created geometry uses the
+    /// given `context` instead of a new one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{ContextHandle, ContextHandling, Geom, Geometry};
+    /// use std::sync::Arc;
+    ///
+    /// let context = Arc::new(ContextHandle::init().expect("invalid init"));
+    /// let wkb = Geometry::new_from_wkt("POINT (1 2)")
+    ///                    .and_then(|geom| geom.into_wkb_owned())
+    ///                    .expect("into_wkb_owned failed");
+    /// let geom = Geometry::from_wkb_owned_with_context(&wkb, Arc::clone(&context))
+    ///                     .expect("from_wkb_owned_with_context failed");
+    /// assert!(Arc::ptr_eq(&geom.clone_context(), &context));
+    /// ```
+    pub fn from_wkb_owned_with_context(
+        wkb: &[u8],
+        context: Arc<ContextHandle<'static>>,
+    ) -> GResult<Geometry<'static>> {
+        WKB_OWNED_READER.with(|reader| {
+            let mut reader = reader.borrow_mut();
+            if reader.is_none() {
+                let reader_context = ContextHandle::init_e(Some("Geometry::from_wkb_owned"))?;
+                *reader = Some(WKBReader::new_with_context(Arc::new(reader_context))?);
+            }
+            match *reader {
+                Some(ref mut reader) => unsafe {
+                    let ptr = GEOSWKBReader_read_r(
+                        reader.get_raw_context(),
+                        reader.as_raw_mut(),
+                        wkb.as_ptr(),
+                        wkb.len(),
+                    );
+                    Geometry::new_from_parser(ptr, context, "WKB", wkb)
+                },
+                None => unreachable!(),
+            }
+        })
     }
 
     pub(crate) unsafe fn new_from_raw(
@@ -2524,12 +5195,49 @@ impl<'a> Geometry<'a> {
                 caller, extra
             )));
         }
+        memory::increment(&memory::GEOMETRIES);
         Ok(Geometry {
             ptr: PtrWrap(ptr),
             context,
+            validity: AtomicU8::new(VALIDITY_UNKNOWN),
         })
     }
 
+    /// Same as `new_from_raw`, but returns an `Error::Overlay` on failure.
+    pub(crate) unsafe fn new_from_overlay(
+        ptr: *mut GEOSGeometry,
+        context: Arc<ContextHandle<'a>>,
+        op: &'static str,
+    ) -> GResult<Geometry<'a>> {
+        if ptr.is_null() {
+            return Err(Error::Overlay {
+                op,
+                message: context.get_last_error().unwrap_or_default(),
+            });
+        }
+        Geometry::new_from_raw(ptr, context, op)
+    }
+
+    /// Same as `new_from_raw`, but returns an `Error::Parse` on failure.
+    pub(crate) unsafe fn new_from_parser(
+        ptr: *mut GEOSGeometry,
+        context: Arc<ContextHandle<'a>>,
+        format: &'static str,
+        input: &[u8],
+    ) -> GResult<Geometry<'a>> {
+        if ptr.is_null() {
+            if let Some(curve_type) = find_curve_type(format, input) {
+                return Err(Error::UnsupportedCurveType(curve_type.to_owned()));
+            }
+            return Err(Error::Parse {
+                format,
+                message: context.get_last_error().unwrap_or_default(),
+                offset: None,
+            });
+        }
+        Geometry::new_from_raw(ptr, context, format)
+    }
+
     /// Set SRID of `self`.
     ///
     /// # Example
@@ -2546,6 +5254,34 @@ impl<'a> Geometry<'a> {
         unsafe { GEOSSetSRID_r(self.get_raw_context(), self.as_raw_mut(), srid as _) }
     }
 
+    /// Attaches an opaque value (like an index in an application array) to `self`, stored in the
+    /// user data slot of the GEOS geometry. It can be read back with
+    /// [`get_user_data`](trait.Geom.html#tymethod.get_user_data), including from the raw
+    /// geometry pointers handed out by GEOS, so the value follows the geometry when it's moved
+    /// around or reordered.
+    ///
+    /// Only `self` gets it: the geometries created from it (clones, results of operations...)
+    /// don't. 0 means "no value".
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let mut geoms = vec![
+    ///     Geometry::new_from_wkt("POINT (3 3)").expect("Invalid geometry"),
+    ///     Geometry::new_from_wkt("POINT (1 1)").expect("Invalid geometry"),
+    /// ];
+    /// for (i, geom) in geoms.iter_mut().enumerate() {
+    ///     geom.set_user_data(i + 1);
+    /// }
+    /// geoms.sort_by(|a, b| a.get_x().unwrap().partial_cmp(&b.get_x().unwrap()).unwrap());
+    /// assert_eq!(geoms.iter().map(|g| g.get_user_data()).collect::<Vec<_>>(), vec![2, 1]);
+    /// ```
+    pub fn set_user_data(&mut self, data: usize) {
+        unsafe { GEOSGeom_setUserData_r(self.get_raw_context(), self.as_raw_mut(), data as *mut _) }
+    }
+
     /// Normalizes `self` in its normalized/canonical form. May reorder vertices in polygon rings,
     /// rings in a polygon, elements in a multi-geometry complex.
     ///
@@ -2573,6 +5309,152 @@ impl<'a> Geometry<'a> {
         }
     }
 
+    /// Calls `f` on every coordinate of `self` (`[x, y]`, or `[x, y, z]` if it has a Z value),
+    /// then rebuilds it from the edited coordinates. SRID and user data are kept.
+    ///
+    /// If the rebuilt geometry can't be created (like a ring which isn't closed anymore) or is
+    /// invalid while `self` was valid, an error is returned and `self` is left unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let mut geom = Geometry::new_from_wkt("LINESTRING(0 0, 1 1, 2 0)").expect("Invalid geometry");
+    /// geom.edit_coords(|coord| if coord[0] == 1. { coord[1] = 5. }).expect("edit_coords failed");
+    /// assert_eq!(geom.to_wkt().unwrap(), "LINESTRING (0 0, 1 5, 2 0)");
+    ///
+    /// let mut square = Geometry::new_from_wkt("POLYGON((0 0, 2 0, 2 2, 0 2, 0 0))")
+    ///                           .expect("Invalid geometry");
+    /// // Moving the upper right corner across the square makes it self-intersect.
+    /// assert!(square.edit_coords(|coord| if coord == [2., 2.] { coord[1] = -1. }).is_err());
+    /// assert_eq!(square.area(), Ok(4.));
+    /// ```
+    pub fn edit_coords<F: FnMut(&mut [f64])>(&mut self, mut f: F) -> GResult<()> {
+        let edited = map_coord_seqs(self, &mut |seq, _| {
+            let dims = seq.dimensions()?;
+            let nb_dims = if dims == CoordDimensions::ThreeD {
+                3
+            } else {
+                2
+            };
+            let mut coords = coords_from_seq(seq)?;
+            for coord in &mut coords {
+                f(&mut coord[..nb_dims]);
+            }
+            seq_from_coords(&coords, dims)
+        })?;
+        self.replace_with_edited(edited, "edit_coords")
+    }
+
+    /// Replaces `self` by `edited` (keeping its context, SRID and user data), unless `edited` is
+    /// invalid while `self` is valid.
+    fn replace_with_edited(&mut self, mut edited: Geometry<'a>, caller: &str) -> GResult<()> {
+        if self.is_valid_cached() && !edited.is_valid() {
+            return Err(Error::GenericError(format!(
+                "{} created an invalid geometry",
+                caller
+            )));
+        }
+        edited.context = self.clone_context();
+        edited.set_srid(self.get_srid()?);
+        edited.set_user_data(self.get_user_data());
+        *self = edited;
+        Ok(())
+    }
+
+    /// Inserts the vertex (`x`, `y`) before the vertex `index` of a `LineString` or a
+    /// `LinearRing` (at the end if `index` is its number of vertices, not counting the closing
+    /// one of a ring). Rings are kept closed. In 3D, the new vertex gets a NaN Z.
+    ///
+    /// Like [`edit_coords`](#method.edit_coords), an invalid result is refused if `self` is
+    /// valid.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let mut line = Geometry::new_from_wkt("LINESTRING(0 0, 2 0)").expect("Invalid geometry");
+    /// line.insert_vertex(1, 1., 1.).expect("insert_vertex failed");
+    /// line.insert_vertex(3, 3., 0.).expect("insert_vertex failed");
+    /// assert_eq!(line.to_wkt().unwrap(), "LINESTRING (0 0, 1 1, 2 0, 3 0)");
+    /// assert!(line.insert_vertex(5, 0., 0.).is_err());
+    /// ```
+    pub fn insert_vertex(&mut self, index: usize, x: f64, y: f64) -> GResult<()> {
+        let edited = edit_line_vertices(self, |coords, closed| {
+            insert_vertex(coords, closed, index, [x, y, f64::NAN])
+        })?;
+        self.replace_with_edited(edited, "insert_vertex")
+    }
+
+    /// Removes the vertex `index` of a `LineString` (which must keep at least 2 vertices) or a
+    /// `LinearRing` (which must keep at least 3 vertices, plus the closing one). Removing the
+    /// first vertex of a ring moves its closing vertex too.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let mut line = Geometry::new_from_wkt("LINESTRING(0 0, 1 1, 2 0)").expect("Invalid geometry");
+    /// line.remove_vertex(1).expect("remove_vertex failed");
+    /// assert_eq!(line.to_wkt().unwrap(), "LINESTRING (0 0, 2 0)");
+    /// assert!(line.remove_vertex(0).is_err());
+    /// ```
+    pub fn remove_vertex(&mut self, index: usize) -> GResult<()> {
+        let edited =
+            edit_line_vertices(self, |coords, closed| remove_vertex(coords, closed, index))?;
+        self.replace_with_edited(edited, "remove_vertex")
+    }
+
+    /// Same as [`insert_vertex`](#method.insert_vertex), on the ring `ring` of a `Polygon` (0
+    /// being its exterior ring, the next ones its interior rings).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let mut polygon = Geometry::new_from_wkt("POLYGON((0 0, 2 0, 2 2, 0 2, 0 0))")
+    ///                            .expect("Invalid geometry");
+    /// polygon.insert_ring_vertex(0, 1, 1., -1.).expect("insert_ring_vertex failed");
+    /// assert_eq!(polygon.to_wkt().unwrap(), "POLYGON ((0 0, 1 -1, 2 0, 2 2, 0 2, 0 0))");
+    ///
+    /// // The new vertex would make the ring self-intersect.
+    /// assert!(polygon.insert_ring_vertex(0, 3, -1., 1.).is_err());
+    /// ```
+    pub fn insert_ring_vertex(&mut self, ring: usize, index: usize, x: f64, y: f64) -> GResult<()> {
+        let edited = edit_polygon_ring(self, ring, |ring| {
+            edit_line_vertices(ring, |coords, closed| {
+                insert_vertex(coords, closed, index, [x, y, f64::NAN])
+            })
+        })?;
+        self.replace_with_edited(edited, "insert_ring_vertex")
+    }
+
+    /// Same as [`remove_vertex`](#method.remove_vertex), on the ring `ring` of a `Polygon` (0
+    /// being its exterior ring, the next ones its interior rings).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let mut polygon = Geometry::new_from_wkt("POLYGON((0 0, 2 0, 2 2, 0 2, 0 0))")
+    ///                            .expect("Invalid geometry");
+    /// polygon.remove_ring_vertex(0, 2).expect("remove_ring_vertex failed");
+    /// assert_eq!(polygon.to_wkt().unwrap(), "POLYGON ((0 0, 2 0, 0 2, 0 0))");
+    /// assert!(polygon.remove_ring_vertex(0, 0).is_err());
+    /// assert!(polygon.remove_ring_vertex(1, 0).is_err());
+    /// ```
+    pub fn remove_ring_vertex(&mut self, ring: usize, index: usize) -> GResult<()> {
+        let edited = edit_polygon_ring(self, ring, |ring| {
+            edit_line_vertices(ring, |coords, closed| remove_vertex(coords, closed, index))
+        })?;
+        self.replace_with_edited(edited, "remove_ring_vertex")
+    }
+
     /// Creates an empty polygon geometry.
     ///
     /// # Example
@@ -2585,15 +5467,35 @@ impl<'a> Geometry<'a> {
     /// assert_eq!(geom.to_wkt().unwrap(), "POLYGON EMPTY");
     /// ```
     pub fn create_empty_polygon() -> GResult<Geometry<'a>> {
-        match ContextHandle::init_e(Some("Geometry::create_empty_polygon")) {
-            Ok(context) => unsafe {
-                let ptr = GEOSGeom_createEmptyPolygon_r(context.as_raw());
-                Geometry::new_from_raw(ptr, Arc::new(context), "create_empty_polygon")
-            },
+        match ContextHandle::init_default("Geometry::create_empty_polygon") {
+            Ok(context) => Geometry::create_empty_polygon_with_context(context),
             Err(e) => Err(e),
         }
     }
 
+    /// Same as [`create_empty_polygon`](#method.create_empty_polygon), but the created
+    /// geometry uses the given `context` instead of a new one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{ContextHandle, ContextHandling, Geometry};
+    /// use std::sync::Arc;
+    ///
+    /// let context = Arc::new(ContextHandle::init().expect("invalid init"));
+    /// let geom = Geometry::create_empty_polygon_with_context(Arc::clone(&context))
+    ///                     .expect("create_empty_polygon_with_context failed");
+    /// assert!(Arc::ptr_eq(&geom.clone_context(), &context));
+    /// ```
+    pub fn create_empty_polygon_with_context(
+        context: Arc<ContextHandle<'a>>,
+    ) -> GResult<Geometry<'a>> {
+        unsafe {
+            let ptr = GEOSGeom_createEmptyPolygon_r(context.as_raw());
+            Geometry::new_from_raw(ptr, context, "create_empty_polygon")
+        }
+    }
+
     /// Creates an empty point geometry.
     ///
     /// # Example
@@ -2606,15 +5508,35 @@ impl<'a> Geometry<'a> {
     /// assert_eq!(geom.to_wkt().unwrap(), "POINT EMPTY");
     /// ```
     pub fn create_empty_point() -> GResult<Geometry<'a>> {
-        match ContextHandle::init_e(Some("Geometry::create_empty_point")) {
-            Ok(context) => unsafe {
-                let ptr = GEOSGeom_createEmptyPoint_r(context.as_raw());
-                Geometry::new_from_raw(ptr, Arc::new(context), "create_empty_point")
-            },
+        match ContextHandle::init_default("Geometry::create_empty_point") {
+            Ok(context) => Geometry::create_empty_point_with_context(context),
             Err(e) => Err(e),
         }
     }
 
+    /// Same as [`create_empty_point`](#method.create_empty_point), but the created
+    /// geometry uses the given `context` instead of a new one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{ContextHandle, ContextHandling, Geometry};
+    /// use std::sync::Arc;
+    ///
+    /// let context = Arc::new(ContextHandle::init().expect("invalid init"));
+    /// let geom = Geometry::create_empty_point_with_context(Arc::clone(&context))
+    ///                     .expect("create_empty_point_with_context failed");
+    /// assert!(Arc::ptr_eq(&geom.clone_context(), &context));
+    /// ```
+    pub fn create_empty_point_with_context(
+        context: Arc<ContextHandle<'a>>,
+    ) -> GResult<Geometry<'a>> {
+        unsafe {
+            let ptr = GEOSGeom_createEmptyPoint_r(context.as_raw());
+            Geometry::new_from_raw(ptr, context, "create_empty_point")
+        }
+    }
+
     /// Creates an empty line string geometry.
     ///
     /// # Example
@@ -2627,15 +5549,35 @@ impl<'a> Geometry<'a> {
     /// assert_eq!(geom.to_wkt().unwrap(), "LINESTRING EMPTY");
     /// ```
     pub fn create_empty_line_string() -> GResult<Geometry<'a>> {
-        match ContextHandle::init_e(Some("Geometry::create_empty_line_string")) {
-            Ok(context) => unsafe {
-                let ptr = GEOSGeom_createEmptyLineString_r(context.as_raw());
-                Geometry::new_from_raw(ptr, Arc::new(context), "create_empty_line_string")
-            },
+        match ContextHandle::init_default("Geometry::create_empty_line_string") {
+            Ok(context) => Geometry::create_empty_line_string_with_context(context),
             Err(e) => Err(e),
         }
     }
 
+    /// Same as [`create_empty_line_string`](#method.create_empty_line_string), but the created
+    /// geometry uses the given `context` instead of a new one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{ContextHandle, ContextHandling, Geometry};
+    /// use std::sync::Arc;
+    ///
+    /// let context = Arc::new(ContextHandle::init().expect("invalid init"));
+    /// let geom = Geometry::create_empty_line_string_with_context(Arc::clone(&context))
+    ///                     .expect("create_empty_line_string_with_context failed");
+    /// assert!(Arc::ptr_eq(&geom.clone_context(), &context));
+    /// ```
+    pub fn create_empty_line_string_with_context(
+        context: Arc<ContextHandle<'a>>,
+    ) -> GResult<Geometry<'a>> {
+        unsafe {
+            let ptr = GEOSGeom_createEmptyLineString_r(context.as_raw());
+            Geometry::new_from_raw(ptr, context, "create_empty_line_string")
+        }
+    }
+
     /// Creates an empty collection.
     ///
     /// The `type_` must be one of:
@@ -2656,6 +5598,31 @@ impl<'a> Geometry<'a> {
     /// assert_eq!(geom.to_wkt().unwrap(), "MULTIPOLYGON EMPTY");
     /// ```
     pub fn create_empty_collection(type_: GeometryTypes) -> GResult<Geometry<'a>> {
+        match ContextHandle::init_default("Geometry::create_empty_collection") {
+            Ok(context) => Geometry::create_empty_collection_with_context(type_, context),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Same as [`create_empty_collection`](#method.create_empty_collection), but the created
+    /// geometry uses the given `context` instead of a new one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{ContextHandle, ContextHandling, Geometry, GeometryTypes};
+    /// use std::sync::Arc;
+    ///
+    /// let context = Arc::new(ContextHandle::init().expect("invalid init"));
+    /// let geom = Geometry::create_empty_collection_with_context(GeometryTypes::MultiPoint,
+    ///                                                           Arc::clone(&context))
+    ///                     .expect("create_empty_collection_with_context failed");
+    /// assert!(Arc::ptr_eq(&geom.clone_context(), &context));
+    /// ```
+    pub fn create_empty_collection_with_context(
+        type_: GeometryTypes,
+        context: Arc<ContextHandle<'a>>,
+    ) -> GResult<Geometry<'a>> {
         match type_ {
             GeometryTypes::GeometryCollection
             | GeometryTypes::MultiPoint
@@ -2663,12 +5630,9 @@ impl<'a> Geometry<'a> {
             | GeometryTypes::MultiPolygon => {}
             _ => return Err(Error::GenericError("Invalid geometry type".to_owned())),
         }
-        match ContextHandle::init_e(Some("Geometry::create_empty_collection")) {
-            Ok(context) => unsafe {
-                let ptr = GEOSGeom_createEmptyCollection_r(context.as_raw(), type_.into());
-                Geometry::new_from_raw(ptr, Arc::new(context), "create_empty_collection")
-            },
-            Err(e) => Err(e),
+        unsafe {
+            let ptr = GEOSGeom_createEmptyCollection_r(context.as_raw(), type_.into());
+            Geometry::new_from_raw(ptr, context, "create_empty_collection")
         }
     }
 
@@ -2748,6 +5712,30 @@ impl<'a> Geometry<'a> {
         create_multi_geom(geoms, GeometryTypes::GeometryCollection)
     }
 
+    /// Same as [`create_geometry_collection`](#method.create_geometry_collection), but clones the
+    /// given geometries instead of taking them, so they can still be used afterwards. The other
+    /// `create_*_cloned` constructors work the same way.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let geoms = vec![
+    ///     Geometry::new_from_wkt("POINT (1 2)").expect("Invalid geometry"),
+    ///     Geometry::new_from_wkt("LINESTRING (0 0, 1 1)").expect("Invalid geometry"),
+    /// ];
+    ///
+    /// let collection = Geometry::create_geometry_collection_cloned(&geoms)
+    ///                            .expect("Failed to build geometry collection");
+    /// assert_eq!(collection.to_wkt_precision(0).unwrap(),
+    ///            "GEOMETRYCOLLECTION (POINT (1 2), LINESTRING (0 0, 1 1))");
+    /// assert_eq!(geoms[1].length(), Ok(2f64.sqrt()));
+    /// ```
+    pub fn create_geometry_collection_cloned(geoms: &[Geometry<'a>]) -> GResult<Geometry<'a>> {
+        Geometry::create_geometry_collection(geoms.to_vec())
+    }
+
     /// Create a multi polygon geometry.
     ///
     /// # Example
@@ -2776,6 +5764,30 @@ impl<'a> Geometry<'a> {
         create_multi_geom(polygons, GeometryTypes::MultiPolygon)
     }
 
+    /// Same as [`create_multipolygon`](#method.create_multipolygon), but clones the given
+    /// polygons instead of taking them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let polygons = vec![
+    ///     Geometry::new_from_wkt("POLYGON((0 0, 1 0, 1 1, 0 1, 0 0))")
+    ///         .expect("Invalid geometry"),
+    ///     Geometry::new_from_wkt("POLYGON((2 0, 4 0, 4 1, 2 1, 2 0))")
+    ///         .expect("Invalid geometry"),
+    /// ];
+    ///
+    /// let multipolygon = Geometry::create_multipolygon_cloned(&polygons)
+    ///                              .expect("Failed to build multipolygon");
+    /// assert_eq!(multipolygon.area(), Ok(3.));
+    /// assert_eq!(polygons[0].area(), Ok(1.));
+    /// ```
+    pub fn create_multipolygon_cloned(polygons: &[Geometry<'a>]) -> GResult<Geometry<'a>> {
+        Geometry::create_multipolygon(polygons.to_vec())
+    }
+
     /// Create a multiline string geometry.
     ///
     /// # Example
@@ -2801,6 +5813,28 @@ impl<'a> Geometry<'a> {
         create_multi_geom(linestrings, GeometryTypes::MultiLineString)
     }
 
+    /// Same as [`create_multiline_string`](#method.create_multiline_string), but clones the given
+    /// line strings instead of taking them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let linestrings = vec![
+    ///     Geometry::new_from_wkt("LINESTRING (0 0, 3 0)").expect("Invalid geometry"),
+    ///     Geometry::new_from_wkt("LINESTRING (0 1, 0 3)").expect("Invalid geometry"),
+    /// ];
+    ///
+    /// let multiline_string = Geometry::create_multiline_string_cloned(&linestrings)
+    ///                                  .expect("Failed to build multiline string");
+    /// assert_eq!(multiline_string.length(), Ok(5.));
+    /// assert_eq!(linestrings[0].length(), Ok(3.));
+    /// ```
+    pub fn create_multiline_string_cloned(linestrings: &[Geometry<'a>]) -> GResult<Geometry<'a>> {
+        Geometry::create_multiline_string(linestrings.to_vec())
+    }
+
     /// Creates a multi point geometry.
     ///
     /// # Example
@@ -2825,6 +5859,28 @@ impl<'a> Geometry<'a> {
         create_multi_geom(points, GeometryTypes::MultiPoint)
     }
 
+    /// Same as [`create_multipoint`](#method.create_multipoint), but clones the given points
+    /// instead of taking them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let points = vec![
+    ///     Geometry::new_from_wkt("POINT (1 2)").expect("Invalid geometry"),
+    ///     Geometry::new_from_wkt("POINT (3 4)").expect("Invalid geometry"),
+    /// ];
+    ///
+    /// let multipoint = Geometry::create_multipoint_cloned(&points)
+    ///                            .expect("Failed to build multipoint");
+    /// assert_eq!(multipoint.to_wkt_precision(0).unwrap(), "MULTIPOINT (1 2, 3 4)");
+    /// assert_eq!(points[1].get_x(), Ok(3.));
+    /// ```
+    pub fn create_multipoint_cloned(points: &[Geometry<'a>]) -> GResult<Geometry<'a>> {
+        Geometry::create_multipoint(points.to_vec())
+    }
+
     /// Creates a point geometry.
     ///
     /// # Example
@@ -2898,6 +5954,146 @@ impl<'a> Geometry<'a> {
             res
         }
     }
+
+    /// Creates a point from its coordinates.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let geom = Geometry::point(1., 2.).expect("Failed to create a point");
+    /// assert_eq!(geom.to_wkt().unwrap(), "POINT (1 2)");
+    /// ```
+    pub fn point(x: f64, y: f64) -> GResult<Geometry<'a>> {
+        let context = ContextHandle::init_default("Geometry::point")?;
+        Geometry::point_with_context(x, y, context)
+    }
+
+    /// Same as [`point`](#method.point), but the created geometry uses the given `context`
+    /// instead of a new one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{ContextHandle, ContextHandling, Geometry};
+    /// use std::sync::Arc;
+    ///
+    /// let context = Arc::new(ContextHandle::init().expect("invalid init"));
+    /// let geom = Geometry::point_with_context(1., 2., Arc::clone(&context))
+    ///                     .expect("Failed to create a point");
+    /// assert!(Arc::ptr_eq(&geom.clone_context(), &context));
+    /// ```
+    pub fn point_with_context(
+        x: f64,
+        y: f64,
+        context: Arc<ContextHandle<'a>>,
+    ) -> GResult<Geometry<'a>> {
+        Geometry::create_point(CoordSeq::new_from_vec_with_context(&[[x, y]], context)?)
+    }
+
+    /// Creates a line string going through the given points. No point gives an empty line
+    /// string.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let geom = Geometry::line(&[(0., 0.), (1., 2.)]).expect("Failed to create a line");
+    /// assert_eq!(geom.to_wkt().unwrap(), "LINESTRING (0 0, 1 2)");
+    /// assert_eq!(Geometry::line(&[]).unwrap().is_empty(), Ok(true));
+    /// ```
+    pub fn line(points: &[(f64, f64)]) -> GResult<Geometry<'a>> {
+        let context = ContextHandle::init_default("Geometry::line")?;
+        Geometry::line_with_context(points, context)
+    }
+
+    /// Same as [`line`](#method.line), but the created geometry uses the given `context` instead
+    /// of a new one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{ContextHandle, ContextHandling, Geometry};
+    /// use std::sync::Arc;
+    ///
+    /// let context = Arc::new(ContextHandle::init().expect("invalid init"));
+    /// let geom = Geometry::line_with_context(&[(0., 0.), (1., 2.)], Arc::clone(&context))
+    ///                     .expect("Failed to create a line");
+    /// assert!(Arc::ptr_eq(&geom.clone_context(), &context));
+    /// ```
+    pub fn line_with_context(
+        points: &[(f64, f64)],
+        context: Arc<ContextHandle<'a>>,
+    ) -> GResult<Geometry<'a>> {
+        if points.is_empty() {
+            return Geometry::create_empty_line_string_with_context(context);
+        }
+        let coords = points.iter().map(|&(x, y)| [x, y]).collect::<Vec<_>>();
+        Geometry::create_line_string(CoordSeq::new_from_vec_with_context(&coords, context)?)
+    }
+
+    /// Creates a polygon from the points of its exterior ring and of its holes. The rings are
+    /// closed if their last point isn't the same as their first one. An empty `exterior` without
+    /// holes gives an empty polygon.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let geom = Geometry::polygon(&[(0., 0.), (4., 0.), (4., 4.), (0., 4.)],
+    ///                              &[vec![(1., 1.), (2., 1.), (2., 2.), (1., 1.)]])
+    ///                     .expect("Failed to create a polygon");
+    /// assert_eq!(geom.to_wkt().unwrap(),
+    ///            "POLYGON ((0 0, 4 0, 4 4, 0 4, 0 0), (1 1, 2 1, 2 2, 1 1))");
+    /// assert_eq!(geom.area(), Ok(15.5));
+    /// ```
+    pub fn polygon(exterior: &[(f64, f64)], holes: &[Vec<(f64, f64)>]) -> GResult<Geometry<'a>> {
+        let context = ContextHandle::init_default("Geometry::polygon")?;
+        Geometry::polygon_with_context(exterior, holes, context)
+    }
+
+    /// Same as [`polygon`](#method.polygon), but the created geometry uses the given `context`
+    /// instead of a new one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{ContextHandle, ContextHandling, Geometry};
+    /// use std::sync::Arc;
+    ///
+    /// let context = Arc::new(ContextHandle::init().expect("invalid init"));
+    /// let geom = Geometry::polygon_with_context(&[(0., 0.), (4., 0.), (4., 4.)], &[],
+    ///                                           Arc::clone(&context))
+    ///                     .expect("Failed to create a polygon");
+    /// assert!(Arc::ptr_eq(&geom.clone_context(), &context));
+    /// ```
+    pub fn polygon_with_context(
+        exterior: &[(f64, f64)],
+        holes: &[Vec<(f64, f64)>],
+        context: Arc<ContextHandle<'a>>,
+    ) -> GResult<Geometry<'a>> {
+        if exterior.is_empty() && holes.is_empty() {
+            return Geometry::create_empty_polygon_with_context(context);
+        }
+        let create_ring = |points: &[(f64, f64)]| {
+            let mut coords = points.iter().map(|&(x, y)| [x, y]).collect::<Vec<_>>();
+            if let (Some(&first), Some(&last)) = (coords.first(), coords.last()) {
+                if first != last {
+                    coords.push(first);
+                }
+            }
+            let seq = CoordSeq::new_from_vec_with_context(&coords, Arc::clone(&context))?;
+            Geometry::create_linear_ring(seq)
+        };
+        let holes = holes
+            .iter()
+            .map(|hole| create_ring(hole))
+            .collect::<GResult<Vec<_>>>()?;
+        Geometry::create_polygon(create_ring(exterior)?, holes)
+    }
 }
 
 impl<'a, 'b> ConstGeometry<'a, 'b> {
@@ -2946,9 +6142,12 @@ impl<'a> Clone for Geometry<'a> {
 
 impl<'a> Drop for Geometry<'a> {
     fn drop(&mut self) {
+        // The pointer is null if the geometry was destroyed or given to another one (like the
+        // rings of a polygon).
         if !self.ptr.is_null() {
             unsafe { GEOSGeom_destroy_r(self.get_raw_context(), self.as_raw_mut()) }
         }
+        memory::decrement(&memory::GEOMETRIES);
     }
 }
 
@@ -2985,6 +6184,10 @@ impl<'a> AsRaw for Geometry<'a> {
     type RawType = GEOSGeometry;
 
     fn as_raw(&self) -> *const Self::RawType {
+        debug_assert!(
+            !self.ptr.is_null(),
+            "Geometry used after its ownership was transferred to GEOS"
+        );
         *self.ptr
     }
 }
@@ -2992,6 +6195,13 @@ impl<'a> AsRaw for Geometry<'a> {
 impl<'a> AsRawMut for Geometry<'a> {
     type RawType = GEOSGeometry;
 
+    fn as_raw_mut(&mut self) -> *mut Self::RawType {
+        // The geometry may be modified through the returned pointer.
+        self.validity
+            .store(VALIDITY_UNKNOWN, atomic::Ordering::Relaxed);
+        unsafe { self.as_raw_mut_override() }
+    }
+
     unsafe fn as_raw_mut_override(&self) -> *mut Self::RawType {
         *self.ptr
     }
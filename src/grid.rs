@@ -0,0 +1,237 @@
+//! Generation of regular grids of square or hexagonal cells, for binning analyses.
+//!
+//! Grids are anchored on the origin: a cell is always at the same place, whatever the extent it
+//! is generated for, so cells computed for different extents can be matched.
+
+use crate::{ContextHandle, CoordSeq, GResult, Geom, Geometry};
+use bbox::Rect;
+use error::Error;
+use std::sync::Arc;
+
+/// The maximum number of cells a grid can be generated with.
+pub const MAX_CELLS: i64 = 1_000_000;
+
+fn check_size(size: f64) -> GResult<()> {
+    if !size.is_finite() || size <= 0. {
+        return Err(Error::InvalidInput(format!(
+            "the cell size must be a positive number, got {}",
+            size
+        )));
+    }
+    Ok(())
+}
+
+fn check_cell_count(cols: i64, rows: i64) -> GResult<()> {
+    match cols.checked_mul(rows) {
+        Some(count) if count <= MAX_CELLS => Ok(()),
+        _ => Err(Error::InvalidInput(format!(
+            "the grid would have more than {} cells",
+            MAX_CELLS
+        ))),
+    }
+}
+
+/// Creates the polygon of the cell, clipped to `bbox` if `clip` is `true`. Returns `None` if
+/// the cell doesn't overlap `bbox` (when clipping, sharing some boundary isn't enough).
+fn create_cell(
+    coords: &[[f64; 2]],
+    bbox: &Rect,
+    clip: bool,
+    context: &Arc<ContextHandle<'static>>,
+) -> GResult<Option<Geometry<'static>>> {
+    let cell_rect = coords.iter().fold(
+        Rect::new(coords[0][0], coords[0][1], coords[0][0], coords[0][1]),
+        |r, c| r.union(&Rect::new(c[0], c[1], c[0], c[1])),
+    );
+    match cell_rect.intersection(bbox) {
+        Some(overlap) if !clip || overlap.area() > 0. => {}
+        _ => return Ok(None),
+    }
+    let ring = CoordSeq::new_from_vec_with_context(coords, Arc::clone(context))?;
+    let cell = Geometry::create_polygon(Geometry::create_linear_ring(ring)?, vec![])?;
+    if !clip || bbox.contains(&cell_rect) {
+        return Ok(Some(cell));
+    }
+    let clipped = cell.clip_by_rect(bbox.xmin, bbox.ymin, bbox.xmax, bbox.ymax)?;
+    if clipped.area()? > 0. {
+        Ok(Some(clipped))
+    } else {
+        Ok(None)
+    }
+}
+
+fn keep_intersecting<'a, G: Geom<'a>>(
+    cells: Vec<Geometry<'static>>,
+    geom: &G,
+) -> GResult<Vec<Geometry<'static>>> {
+    if cells.is_empty() {
+        return Ok(cells);
+    }
+    let prepared = geom.to_prepared_geom()?;
+    let mut res = Vec::with_capacity(cells.len());
+    for cell in cells {
+        if prepared.intersects(&cell)? {
+            res.push(cell);
+        }
+    }
+    Ok(res)
+}
+
+/// Returns the square cells of side `cell_size` overlapping `bbox`, clipped to it. The cells are
+/// ordered by row (from the bottom) then by column (from the left).
+///
+/// Returns an [`Error::InvalidInput`](crate::Error::InvalidInput) if there would be more than
+/// [`MAX_CELLS`] cells to generate.
+///
+/// # Example
+///
+/// ```
+/// use geos::Geom;
+/// use geos::bbox::Rect;
+/// use geos::grid::squares;
+///
+/// let cells = squares(Rect::new(0., 0., 2., 1.5), 1.).expect("squares failed");
+/// assert_eq!(cells.len(), 4);
+/// assert_eq!(cells[0].to_wkt().unwrap(), "POLYGON ((0 0, 1 0, 1 1, 0 1, 0 0))");
+/// assert_eq!(cells[3].area(), Ok(0.5));
+/// ```
+pub fn squares(bbox: Rect, cell_size: f64) -> GResult<Vec<Geometry<'static>>> {
+    square_cells(bbox, cell_size, true)
+}
+
+fn square_cells(bbox: Rect, cell_size: f64, clip: bool) -> GResult<Vec<Geometry<'static>>> {
+    check_size(cell_size)?;
+    // The cells only touching the edges of `bbox` are included too (and dropped by
+    // `create_cell` when clipping), so that a `bbox` on the grid lines still gets its cells.
+    let (col_min, col_max) = (
+        (bbox.xmin / cell_size).ceil() as i64 - 1,
+        (bbox.xmax / cell_size).floor() as i64 + 1,
+    );
+    let (row_min, row_max) = (
+        (bbox.ymin / cell_size).ceil() as i64 - 1,
+        (bbox.ymax / cell_size).floor() as i64 + 1,
+    );
+    check_cell_count(
+        col_max.saturating_sub(col_min),
+        row_max.saturating_sub(row_min),
+    )?;
+    let context = ContextHandle::init_default("grid::squares")?;
+    let mut cells = Vec::new();
+    for row in row_min..row_max {
+        let (y0, y1) = (row as f64 * cell_size, (row + 1) as f64 * cell_size);
+        for col in col_min..col_max {
+            let (x0, x1) = (col as f64 * cell_size, (col + 1) as f64 * cell_size);
+            let coords = [[x0, y0], [x1, y0], [x1, y1], [x0, y1], [x0, y0]];
+            if let Some(cell) = create_cell(&coords, &bbox, clip, &context)? {
+                cells.push(cell);
+            }
+        }
+    }
+    Ok(cells)
+}
+
+/// Returns the hexagonal cells with sides of length `size` overlapping `bbox`, clipped to it.
+///
+/// The hexagons have flat tops: they are `2 * size` wide and `sqrt(3) * size` high, and the
+/// ones of odd columns are shifted upwards by half their height. The cells are ordered by column
+/// (from the left) then by row (from the bottom).
+///
+/// Returns an [`Error::InvalidInput`](crate::Error::InvalidInput) if there would be more than
+/// [`MAX_CELLS`] cells to generate.
+///
+/// # Example
+///
+/// ```
+/// use geos::Geom;
+/// use geos::bbox::Rect;
+/// use geos::grid::hexagons;
+///
+/// let bbox = Rect::new(0., 0., 10., 10.);
+/// let cells = hexagons(bbox, 2.).expect("hexagons failed");
+/// // The cells cover the whole extent, without overlapping.
+/// let area: f64 = cells.iter().map(|c| c.area().unwrap()).sum();
+/// assert!((area - 100.).abs() < 1e-9);
+/// ```
+pub fn hexagons(bbox: Rect, size: f64) -> GResult<Vec<Geometry<'static>>> {
+    hexagon_cells(bbox, size, true)
+}
+
+fn hexagon_cells(bbox: Rect, size: f64, clip: bool) -> GResult<Vec<Geometry<'static>>> {
+    check_size(size)?;
+    let width = 1.5 * size;
+    let height = 3f64.sqrt() * size;
+    let (col_min, col_max) = (
+        ((bbox.xmin - size) / width).floor() as i64,
+        ((bbox.xmax + size) / width).ceil() as i64,
+    );
+    let (row_min, row_max) = (
+        (bbox.ymin / height).floor() as i64 - 1,
+        (bbox.ymax / height).ceil() as i64 + 1,
+    );
+    check_cell_count(
+        col_max.saturating_sub(col_min).saturating_add(1),
+        row_max.saturating_sub(row_min).saturating_add(1),
+    )?;
+    let context = ContextHandle::init_default("grid::hexagons")?;
+    let mut cells = Vec::new();
+    for col in col_min..=col_max {
+        let cx = col as f64 * width;
+        let shift = if col % 2 != 0 { height / 2. } else { 0. };
+        for row in row_min..=row_max {
+            let cy = row as f64 * height + shift;
+            let coords = [
+                [cx - size, cy],
+                [cx - size / 2., cy - height / 2.],
+                [cx + size / 2., cy - height / 2.],
+                [cx + size, cy],
+                [cx + size / 2., cy + height / 2.],
+                [cx - size / 2., cy + height / 2.],
+                [cx - size, cy],
+            ];
+            if let Some(cell) = create_cell(&coords, &bbox, clip, &context)? {
+                cells.push(cell);
+            }
+        }
+    }
+    Ok(cells)
+}
+
+/// Returns the square cells of side `cell_size` intersecting `geom`. Unlike [`squares`], the
+/// cells aren't clipped (to the envelope of `geom`).
+///
+/// # Example
+///
+/// ```
+/// use geos::{Geom, Geometry};
+/// use geos::grid::squares_intersecting;
+///
+/// let line = Geometry::new_from_wkt("LINESTRING(0.5 0.5, 2.5 0.5)").expect("Invalid geometry");
+/// let cells = squares_intersecting(&line, 1.).expect("squares_intersecting failed");
+/// assert_eq!(cells.len(), 3);
+/// assert_eq!(cells[2].to_wkt().unwrap(), "POLYGON ((2 0, 3 0, 3 1, 2 1, 2 0))");
+///
+/// // A point on a corner touches four cells.
+/// let point = Geometry::new_from_wkt("POINT(1 1)").expect("Invalid geometry");
+/// assert_eq!(squares_intersecting(&point, 1.).map(|cells| cells.len()), Ok(4));
+/// ```
+pub fn squares_intersecting<'a, G: Geom<'a>>(
+    geom: &G,
+    cell_size: f64,
+) -> GResult<Vec<Geometry<'static>>> {
+    match Rect::from_geom(geom)? {
+        Some(bbox) => keep_intersecting(square_cells(bbox, cell_size, false)?, geom),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Returns the hexagonal cells with sides of length `size` intersecting `geom`. Unlike
+/// [`hexagons`], the cells aren't clipped.
+pub fn hexagons_intersecting<'a, G: Geom<'a>>(
+    geom: &G,
+    size: f64,
+) -> GResult<Vec<Geometry<'static>>> {
+    match Rect::from_geom(geom)? {
+        Some(bbox) => keep_intersecting(hexagon_cells(bbox, size, false)?, geom),
+        None => Ok(Vec::new()),
+    }
+}
@@ -0,0 +1,104 @@
+use crate::{ByteOrder, GResult, Geometry, OutputDimension, WKBWriter};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+/// Deduplicates identical geometries and hands out shared handles to them.
+///
+/// Two geometries are considered identical if their normalized forms have the same WKB
+/// representation (Z coordinates and SRID included). The interned geometries are stored
+/// normalized (see [`Geometry::normalize`]), so the returned handle may have a different
+/// vertex order than the geometry given to [`intern`](#method.intern).
+///
+/// # Example
+///
+/// ```
+/// use geos::{Geom, Geometry, GeometryInterner};
+/// use std::sync::Arc;
+///
+/// let mut interner = GeometryInterner::new().expect("Failed to create GeometryInterner");
+///
+/// // The same square, starting from a different corner.
+/// let a = Geometry::new_from_wkt("POLYGON((0 0, 1 0, 1 1, 0 1, 0 0))").expect("Invalid geometry");
+/// let b = Geometry::new_from_wkt("POLYGON((1 1, 0 1, 0 0, 1 0, 1 1))").expect("Invalid geometry");
+/// let c = Geometry::new_from_wkt("POINT(1 1)").expect("Invalid geometry");
+///
+/// let a = interner.intern(a).expect("intern failed");
+/// let b = interner.intern(b).expect("intern failed");
+/// let c = interner.intern(c).expect("intern failed");
+/// assert!(Arc::ptr_eq(&a, &b));
+/// assert!(!Arc::ptr_eq(&a, &c));
+/// assert_eq!(interner.len(), 2);
+///
+/// drop(c);
+/// assert_eq!(interner.purge(), 1);
+/// assert_eq!(interner.len(), 1);
+/// ```
+pub struct GeometryInterner<'a> {
+    writer: WKBWriter<'a>,
+    geoms: HashMap<u64, Vec<Arc<Geometry<'a>>>>,
+    len: usize,
+}
+
+impl<'a> GeometryInterner<'a> {
+    /// Creates an empty `GeometryInterner`.
+    pub fn new() -> GResult<GeometryInterner<'a>> {
+        let mut writer = WKBWriter::new()?;
+        writer.set_output_dimension(OutputDimension::ThreeD);
+        writer.set_wkb_byte_order(ByteOrder::LittleEndian);
+        writer.set_include_SRID(true);
+        Ok(GeometryInterner {
+            writer,
+            geoms: HashMap::new(),
+            len: 0,
+        })
+    }
+
+    /// Returns a shared handle to the interned geometry identical to `geom`, interning it first
+    /// if there is none yet.
+    pub fn intern(&mut self, mut geom: Geometry<'a>) -> GResult<Arc<Geometry<'a>>> {
+        geom.normalize()?;
+        let wkb = self.writer.write_wkb(&geom)?;
+        let mut hasher = DefaultHasher::new();
+        wkb.as_ref().hash(&mut hasher);
+        let hash = hasher.finish();
+
+        if let Some(bucket) = self.geoms.get(&hash) {
+            for interned in bucket {
+                if self.writer.write_wkb(interned.as_ref())?.as_ref() == wkb.as_ref() {
+                    return Ok(Arc::clone(interned));
+                }
+            }
+        }
+        let interned = Arc::new(geom);
+        self.geoms
+            .entry(hash)
+            .or_default()
+            .push(Arc::clone(&interned));
+        self.len += 1;
+        Ok(interned)
+    }
+
+    /// Returns the number of interned geometries.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no geometry is interned.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Forgets the interned geometries which aren't used outside of the interner anymore, and
+    /// returns how many were dropped.
+    pub fn purge(&mut self) -> usize {
+        let before = self.len;
+        for bucket in self.geoms.values_mut() {
+            bucket.retain(|geom| Arc::strong_count(geom) > 1);
+        }
+        self.geoms.retain(|_, bucket| !bucket.is_empty());
+        self.len = self.geoms.values().map(Vec::len).sum();
+        before - self.len
+    }
+}
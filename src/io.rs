@@ -0,0 +1,142 @@
+//! Reading and writing geometries in various formats.
+//!
+//! [`parse_wkt_batch`] and [`parse_wkb_batch`] parse many geometries at once: the readers are
+//! reused across the inputs instead of being created for each geometry. With the `parallel`
+//! feature, the inputs are spread over the [rayon](https://docs.rs/rayon) thread pool, each
+//! thread using its own reader and context. WebAssembly targets have no thread pool, so they
+//! always parse the inputs sequentially.
+//!
+//! The [`gml`] and [`kml`] modules convert geometries from and to XML fragments, and [`mvt`]
+//! encodes them as vector tile feature geometries. [`geoparquet`] writes WKB columns as expected
+//! by GeoParquet.
+
+use crate::{
+    CoordDimensions, CoordSeq, GResult, Geom, Geometry, GeometryTypes, WKBReader, WKTReader,
+};
+use functions::{coords_from_seq, seq_from_coords};
+#[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+use rayon::prelude::*;
+
+pub mod geoparquet;
+pub mod gml;
+pub mod kml;
+pub mod mvt;
+mod xml;
+
+/// Parses each WKT string of `inputs`, keeping the errors of the invalid ones in place.
+///
+/// The geometries parsed by the same thread share the same context.
+///
+/// # Example
+///
+/// ```
+/// use geos::Geom;
+/// use geos::io::parse_wkt_batch;
+///
+/// let geoms = parse_wkt_batch(&["POINT (1 2)", "POINT (1", "LINESTRING (0 0, 1 1)"]);
+/// assert_eq!(geoms.len(), 3);
+/// assert_eq!(geoms[0].as_ref().map(|g| g.get_x()), Ok(Ok(1.)));
+/// assert!(geoms[1].is_err());
+/// assert!(geoms[2].is_ok());
+/// ```
+pub fn parse_wkt_batch(inputs: &[&str]) -> Vec<GResult<Geometry<'static>>> {
+    parse_batch(inputs, WKTReader::new, |reader, wkt| reader.read_wkt(wkt))
+}
+
+/// Parses each WKB buffer of `inputs`, keeping the errors of the invalid ones in place.
+///
+/// The geometries parsed by the same thread share the same context.
+///
+/// # Example
+///
+/// ```
+/// use geos::{Geom, Geometry};
+/// use geos::io::parse_wkb_batch;
+///
+/// let point = Geometry::new_from_wkt("POINT (1 2)").expect("Invalid geometry");
+/// let wkb = point.to_wkb().expect("conversion to WKB failed");
+///
+/// let geoms = parse_wkb_batch(&[wkb.as_ref(), &wkb.as_ref()[..5]]);
+/// assert_eq!(geoms[0].as_ref().map(|g| g.equals(&point)), Ok(Ok(true)));
+/// assert!(geoms[1].is_err());
+/// ```
+pub fn parse_wkb_batch(inputs: &[&[u8]]) -> Vec<GResult<Geometry<'static>>> {
+    parse_batch(inputs, WKBReader::new, |reader, wkb| reader.read_wkb(wkb))
+}
+
+#[cfg(any(not(feature = "parallel"), target_arch = "wasm32"))]
+fn parse_batch<I, R, N, P>(inputs: &[I], new_reader: N, parse: P) -> Vec<GResult<Geometry<'static>>>
+where
+    N: Fn() -> GResult<R>,
+    P: Fn(&mut R, &I) -> GResult<Geometry<'static>>,
+{
+    let mut reader = new_reader();
+    inputs
+        .iter()
+        .map(|input| match reader {
+            Ok(ref mut reader) => parse(reader, input),
+            Err(ref e) => Err(e.clone()),
+        })
+        .collect()
+}
+
+#[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+fn parse_batch<I, R, N, P>(inputs: &[I], new_reader: N, parse: P) -> Vec<GResult<Geometry<'static>>>
+where
+    I: Sync,
+    N: Fn() -> GResult<R> + Sync + Send,
+    P: Fn(&mut R, &I) -> GResult<Geometry<'static>> + Sync + Send,
+{
+    inputs
+        .par_iter()
+        .map_init(new_reader, |reader, input| match reader {
+            Ok(ref mut reader) => parse(reader, input),
+            Err(ref e) => Err(e.clone()),
+        })
+        .collect()
+}
+
+/// Creates a `Point`, `LineString` or `LinearRing` from `coords` (empty if there is none).
+fn create_simple_geom<'a>(
+    type_: GeometryTypes,
+    coords: &[[f64; 3]],
+    has_z: bool,
+) -> GResult<Geometry<'a>> {
+    let dims = if has_z {
+        CoordDimensions::ThreeD
+    } else {
+        CoordDimensions::TwoD
+    };
+    match type_ {
+        GeometryTypes::Point if coords.is_empty() => Geometry::create_empty_point(),
+        GeometryTypes::Point => Geometry::create_point(seq_from_coords(coords, dims)?),
+        GeometryTypes::LineString if coords.is_empty() => Geometry::create_empty_line_string(),
+        GeometryTypes::LineString => Geometry::create_line_string(seq_from_coords(coords, dims)?),
+        GeometryTypes::LinearRing if coords.is_empty() => {
+            Geometry::create_linear_ring(CoordSeq::new(0, dims)?)
+        }
+        _ => Geometry::create_linear_ring(seq_from_coords(coords, dims)?),
+    }
+}
+
+/// Creates a collection of the given type (`MultiPoint`, `MultiLineString`, `MultiPolygon` or
+/// `GeometryCollection`).
+fn create_collection<'a>(type_: GeometryTypes, geoms: Vec<Geometry<'a>>) -> GResult<Geometry<'a>> {
+    if geoms.is_empty() {
+        return Geometry::create_empty_collection(type_);
+    }
+    match type_ {
+        GeometryTypes::MultiPoint => Geometry::create_multipoint(geoms),
+        GeometryTypes::MultiLineString => Geometry::create_multiline_string(geoms),
+        GeometryTypes::MultiPolygon => Geometry::create_multipolygon(geoms),
+        _ => Geometry::create_geometry_collection(geoms),
+    }
+}
+
+/// Returns the coordinates of a `Point`, `LineString` or `LinearRing`.
+fn simple_geom_coords<'a, G: Geom<'a>>(geom: &G) -> GResult<Vec<[f64; 3]>> {
+    if geom.is_empty()? {
+        return Ok(Vec::new());
+    }
+    coords_from_seq(&geom.get_coord_seq()?)
+}
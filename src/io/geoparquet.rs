@@ -0,0 +1,158 @@
+//! Helpers to write geometries in [GeoParquet](https://geoparquet.org) columns.
+//!
+//! GeoParquet expects ISO WKB: Z coordinates are flagged by adding 1000 to the geometry type
+//! (instead of the `0x80000000` flag of the extended WKB written by GEOS) and no SRID is
+//! embedded, the CRS being stored in the file metadata. The buffers written here are always
+//! little endian.
+
+use crate::{GResult, Geom, WKBWriter};
+use bbox::Rect;
+use enums::{ByteOrder, OutputDimension};
+use error::Error;
+
+const EWKB_Z: u32 = 0x8000_0000;
+
+fn new_writer<'a>() -> GResult<WKBWriter<'a>> {
+    let mut writer = WKBWriter::new()?;
+    writer.set_wkb_byte_order(ByteOrder::LittleEndian);
+    writer.set_include_SRID(false);
+    writer.set_output_dimension(OutputDimension::ThreeD);
+    Ok(writer)
+}
+
+fn write<'a, G: Geom<'a>>(writer: &mut WKBWriter, geom: &G) -> GResult<Vec<u8>> {
+    let mut wkb: Vec<u8> = writer.write_wkb(geom)?.into();
+    let mut pos = 0;
+    ewkb_to_iso(&mut wkb, &mut pos)?;
+    Ok(wkb)
+}
+
+/// Rewrites in place the geometry types of the little endian extended WKB geometry starting at
+/// `pos` to their ISO equivalent.
+fn ewkb_to_iso(wkb: &mut [u8], pos: &mut usize) -> GResult<()> {
+    fn read_u32(wkb: &[u8], pos: &mut usize) -> GResult<u32> {
+        match wkb.get(*pos..*pos + 4) {
+            Some(bytes) => {
+                *pos += 4;
+                let mut buf = [0; 4];
+                buf.copy_from_slice(bytes);
+                Ok(u32::from_le_bytes(buf))
+            }
+            None => Err(Error::ConversionError("truncated WKB".to_owned())),
+        }
+    }
+
+    if wkb.get(*pos) != Some(&1) {
+        return Err(Error::ConversionError(
+            "expected little endian WKB".to_owned(),
+        ));
+    }
+    *pos += 1;
+    let type_pos = *pos;
+    let raw_type = read_u32(wkb, pos)?;
+    let has_z = raw_type & EWKB_Z != 0;
+    let base_type = raw_type & !EWKB_Z;
+    if has_z {
+        wkb[type_pos..type_pos + 4].copy_from_slice(&(base_type + 1000).to_le_bytes());
+    }
+    let coord_size = if has_z { 24 } else { 16 };
+    match base_type {
+        1 => *pos += coord_size,
+        2 => *pos += read_u32(wkb, pos)? as usize * coord_size,
+        3 => {
+            for _ in 0..read_u32(wkb, pos)? {
+                *pos += read_u32(wkb, pos)? as usize * coord_size;
+            }
+        }
+        4..=7 => {
+            for _ in 0..read_u32(wkb, pos)? {
+                ewkb_to_iso(wkb, pos)?;
+            }
+        }
+        _ => {
+            return Err(Error::ConversionError(format!(
+                "unexpected WKB geometry type {}",
+                raw_type
+            )))
+        }
+    }
+    Ok(())
+}
+
+/// Writes `geom` as little endian ISO WKB, without SRID.
+///
+/// # Example
+///
+/// ```
+/// use geos::{Geom, Geometry};
+/// use geos::io::geoparquet::to_wkb;
+///
+/// let mut geom = Geometry::new_from_wkt("POINT Z (1 2 3)").expect("Invalid geometry");
+/// geom.set_srid(4326);
+/// let wkb = to_wkb(&geom).expect("to_wkb failed");
+/// // Byte order, then type 1001 (Point Z), then the coordinates.
+/// assert_eq!(&wkb[..5], &[1, 0xe9, 3, 0, 0]);
+/// assert_eq!(wkb.len(), 5 + 3 * 8);
+///
+/// let geom = Geometry::new_from_wkt("MULTILINESTRING Z ((0 0 0, 1 1 1))")
+///                     .expect("Invalid geometry");
+/// let wkb = to_wkb(&geom).expect("to_wkb failed");
+/// assert_eq!(&wkb[..5], &[1, 0xed, 3, 0, 0]);
+/// // The line string inside of the collection.
+/// assert_eq!(&wkb[9..14], &[1, 0xea, 3, 0, 0]);
+/// ```
+pub fn to_wkb<'a, G: Geom<'a>>(geom: &G) -> GResult<Vec<u8>> {
+    write(&mut new_writer()?, geom)
+}
+
+/// Same as [`to_wkb`] for each geometry of `geoms`, reusing the same writer.
+///
+/// # Example
+///
+/// ```
+/// use geos::Geometry;
+/// use geos::io::geoparquet::to_wkb_batch;
+///
+/// let geoms = vec![
+///     Geometry::new_from_wkt("POINT(1 2)").expect("Invalid geometry"),
+///     Geometry::new_from_wkt("LINESTRING(0 0, 1 1)").expect("Invalid geometry"),
+/// ];
+/// let buffers = to_wkb_batch(&geoms).expect("to_wkb_batch failed");
+/// assert_eq!(buffers.len(), 2);
+/// assert_eq!(&buffers[1][..5], &[1, 2, 0, 0, 0]);
+/// ```
+pub fn to_wkb_batch<'a, G: Geom<'a>>(geoms: &[G]) -> GResult<Vec<Vec<u8>>> {
+    let mut writer = new_writer()?;
+    geoms.iter().map(|geom| write(&mut writer, geom)).collect()
+}
+
+/// Returns the bounding box of `geoms`, as stored in the `bbox` column metadata (the empty
+/// geometries are ignored). Returns `None` if all of them are empty.
+///
+/// # Example
+///
+/// ```
+/// use geos::Geometry;
+/// use geos::bbox::Rect;
+/// use geos::io::geoparquet::bbox;
+///
+/// let geoms = vec![
+///     Geometry::new_from_wkt("POINT(1 2)").expect("Invalid geometry"),
+///     Geometry::new_from_wkt("POINT EMPTY").expect("Invalid geometry"),
+///     Geometry::new_from_wkt("LINESTRING(-1 0, 3 1)").expect("Invalid geometry"),
+/// ];
+/// assert_eq!(bbox(&geoms), Ok(Some(Rect::new(-1., 0., 3., 2.))));
+/// assert_eq!(bbox(&geoms[1..2]), Ok(None));
+/// ```
+pub fn bbox<'a, G: Geom<'a>>(geoms: &[G]) -> GResult<Option<Rect>> {
+    let mut res: Option<Rect> = None;
+    for geom in geoms {
+        if let Some(rect) = Rect::from_geom(geom)? {
+            res = Some(match res {
+                Some(res) => res.union(&rect),
+                None => rect,
+            });
+        }
+    }
+    Ok(res)
+}
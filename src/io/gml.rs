@@ -0,0 +1,324 @@
+//! Conversion between geometries and GML 3 geometry fragments.
+//!
+//! Reading also accepts the GML 2 elements (`coordinates`, `outerBoundaryIs`...) and the
+//! `MultiLineString`/`MultiPolygon` aliases of `MultiCurve`/`MultiSurface`. Only the linear
+//! geometries are supported: curves and surfaces made of patches aren't.
+
+use super::xml::{self, Element};
+use super::{create_collection, create_simple_geom, simple_geom_coords};
+use crate::{GResult, Geom, Geometry, GeometryTypes};
+use error::Error;
+use functions::unsupported_curve;
+
+const FORMAT: &str = "GML";
+
+const GEOMETRY_NAMES: [&str; 10] = [
+    "Point",
+    "LineString",
+    "LinearRing",
+    "Polygon",
+    "MultiPoint",
+    "MultiCurve",
+    "MultiLineString",
+    "MultiSurface",
+    "MultiPolygon",
+    "MultiGeometry",
+];
+
+fn parse_error<T>(message: String) -> GResult<T> {
+    Err(Error::Parse {
+        format: FORMAT,
+        message,
+        offset: None,
+    })
+}
+
+/// Reads the first geometry found in the given GML document or fragment. If it has a `srsName`
+/// ending with an EPSG code (like `EPSG:4326` or `urn:ogc:def:crs:EPSG::4326`), it's used as
+/// SRID.
+///
+/// # Example
+///
+/// ```
+/// use geos::Geom;
+/// use geos::io::gml::from_gml;
+///
+/// let geom = from_gml(r#"<gml:Polygon srsName="EPSG:4326">
+///                          <gml:exterior><gml:LinearRing>
+///                            <gml:posList>0 0 1 0 1 1 0 0</gml:posList>
+///                          </gml:LinearRing></gml:exterior>
+///                        </gml:Polygon>"#).expect("from_gml failed");
+/// assert_eq!(geom.to_wkt().unwrap(), "POLYGON ((0 0, 1 0, 1 1, 0 0))");
+/// assert_eq!(geom.get_srid(), Ok(4326));
+///
+/// assert!(from_gml("<gml:Point><gml:pos>1</gml:pos></gml:Point>").is_err());
+/// ```
+pub fn from_gml<'a>(gml: &str) -> GResult<Geometry<'a>> {
+    let root = xml::parse(gml, FORMAT)?;
+    let element = match root.find(|e| GEOMETRY_NAMES.contains(&e.name.as_str())) {
+        Some(element) => element,
+        None => return parse_error("no geometry found".to_owned()),
+    };
+    let mut geom = read_geometry(element, 2)?;
+    let srid = element
+        .attribute("srsName")
+        .and_then(|name| name.rsplit(&[':', '/'][..]).next())
+        .and_then(|code| code.parse().ok());
+    if let Some(srid) = srid {
+        geom.set_srid(srid);
+    }
+    Ok(geom)
+}
+
+fn srs_dimension(element: &Element, inherited: usize) -> GResult<usize> {
+    match element.attribute("srsDimension") {
+        None => Ok(inherited),
+        Some("2") => Ok(2),
+        Some("3") => Ok(3),
+        Some(dims) => parse_error(format!("unsupported srsDimension \"{}\"", dims)),
+    }
+}
+
+fn to_coord(values: &[f64]) -> [f64; 3] {
+    [
+        values[0],
+        values[1],
+        values.get(2).cloned().unwrap_or(f64::NAN),
+    ]
+}
+
+/// Reads the coordinates of a `Point`, `LineString` or `LinearRing` element.
+fn read_coords(element: &Element, dims: usize) -> GResult<Vec<[f64; 3]>> {
+    let mut coords = Vec::new();
+    for child in &element.children {
+        match child.name.as_str() {
+            "pos" => {
+                let values = xml::parse_numbers(&child.text, FORMAT)?;
+                if values.len() != srs_dimension(child, dims)? {
+                    return parse_error(format!("invalid pos \"{}\"", child.text.trim()));
+                }
+                coords.push(to_coord(&values));
+            }
+            "posList" => {
+                let dims = srs_dimension(child, dims)?;
+                let values = xml::parse_numbers(&child.text, FORMAT)?;
+                if values.len() % dims != 0 {
+                    return parse_error(format!(
+                        "posList of {} values doesn't match srsDimension {}",
+                        values.len(),
+                        dims
+                    ));
+                }
+                coords.extend(values.chunks(dims).map(to_coord));
+            }
+            "coordinates" => {
+                for tuple in child.text.split_whitespace() {
+                    let values = xml::parse_numbers(&tuple.replace(',', " "), FORMAT)?;
+                    if values.len() < 2 || values.len() > 3 {
+                        return parse_error(format!("invalid coordinates \"{}\"", tuple));
+                    }
+                    coords.push(to_coord(&values));
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(coords)
+}
+
+fn read_simple_geom<'a>(
+    type_: GeometryTypes,
+    element: &Element,
+    dims: usize,
+) -> GResult<Geometry<'a>> {
+    let coords = read_coords(element, dims)?;
+    let has_z = coords.iter().any(|c| !c[2].is_nan());
+    create_simple_geom(type_, &coords, has_z)
+}
+
+fn read_ring<'a>(boundary: &Element, dims: usize) -> GResult<Geometry<'a>> {
+    match boundary.child("LinearRing") {
+        Some(ring) => {
+            let dims = srs_dimension(boundary, dims)?;
+            read_simple_geom(GeometryTypes::LinearRing, ring, srs_dimension(ring, dims)?)
+        }
+        None => parse_error(format!("{} without LinearRing", boundary.name)),
+    }
+}
+
+fn read_members<'a>(
+    type_: GeometryTypes,
+    element: &Element,
+    member_names: &[&str],
+    dims: usize,
+) -> GResult<Geometry<'a>> {
+    let mut geoms = Vec::new();
+    for member in &element.children {
+        if member_names.contains(&member.name.as_str()) {
+            for child in &member.children {
+                geoms.push(read_geometry(child, dims)?);
+            }
+        }
+    }
+    create_collection(type_, geoms)
+}
+
+fn read_geometry<'a>(element: &Element, dims: usize) -> GResult<Geometry<'a>> {
+    let dims = srs_dimension(element, dims)?;
+    match element.name.as_str() {
+        "Point" => read_simple_geom(GeometryTypes::Point, element, dims),
+        "LineString" => read_simple_geom(GeometryTypes::LineString, element, dims),
+        "LinearRing" => read_simple_geom(GeometryTypes::LinearRing, element, dims),
+        "Polygon" => {
+            let exterior = element
+                .children
+                .iter()
+                .find(|c| c.name == "exterior" || c.name == "outerBoundaryIs");
+            let exterior = match exterior {
+                Some(exterior) => read_ring(exterior, dims)?,
+                None => return Geometry::create_empty_polygon(),
+            };
+            let interiors = element
+                .children
+                .iter()
+                .filter(|c| c.name == "interior" || c.name == "innerBoundaryIs")
+                .map(|c| read_ring(c, dims))
+                .collect::<GResult<Vec<_>>>()?;
+            Geometry::create_polygon(exterior, interiors)
+        }
+        "MultiPoint" => read_members(
+            GeometryTypes::MultiPoint,
+            element,
+            &["pointMember", "pointMembers"],
+            dims,
+        ),
+        "MultiCurve" | "MultiLineString" => read_members(
+            GeometryTypes::MultiLineString,
+            element,
+            &["curveMember", "curveMembers", "lineStringMember"],
+            dims,
+        ),
+        "MultiSurface" | "MultiPolygon" => read_members(
+            GeometryTypes::MultiPolygon,
+            element,
+            &["surfaceMember", "surfaceMembers", "polygonMember"],
+            dims,
+        ),
+        "MultiGeometry" => read_members(
+            GeometryTypes::GeometryCollection,
+            element,
+            &["geometryMember", "geometryMembers"],
+            dims,
+        ),
+        name => parse_error(format!("unsupported GML geometry \"{}\"", name)),
+    }
+}
+
+/// Writes `geom` as a GML 3 fragment, using the `gml` namespace prefix. Multi line strings and
+/// multi polygons are written as `MultiCurve` and `MultiSurface`. A non-zero SRID is written as
+/// `srsName="EPSG:<srid>"`.
+///
+/// # Example
+///
+/// ```
+/// use geos::{Geom, Geometry};
+/// use geos::io::gml::{from_gml, to_gml};
+///
+/// let geom = Geometry::new_from_wkt("MULTIPOINT(1 2, 3 4)").expect("Invalid geometry");
+/// let gml = to_gml(&geom).expect("to_gml failed");
+/// assert_eq!(gml,
+///            "<gml:MultiPoint>\
+///               <gml:pointMember><gml:Point><gml:pos>1 2</gml:pos></gml:Point></gml:pointMember>\
+///               <gml:pointMember><gml:Point><gml:pos>3 4</gml:pos></gml:Point></gml:pointMember>\
+///             </gml:MultiPoint>");
+/// assert_eq!(from_gml(&gml).expect("from_gml failed").equals(&geom), Ok(true));
+/// ```
+pub fn to_gml<'a, G: Geom<'a>>(geom: &G) -> GResult<String> {
+    let srid = geom.get_srid()?;
+    let attributes = if srid != 0 {
+        format!(" srsName=\"EPSG:{}\"", srid)
+    } else {
+        String::new()
+    };
+    let mut out = String::new();
+    write_geometry(geom, &attributes, &mut out)?;
+    Ok(out)
+}
+
+fn write_pos_list<'a, G: Geom<'a>>(geom: &G, tag: &str, out: &mut String) -> GResult<()> {
+    let has_z = geom.has_z()?;
+    let coords = simple_geom_coords(geom)?;
+    out.push_str(&format!("<gml:{}", tag));
+    if has_z {
+        out.push_str(" srsDimension=\"3\"");
+    }
+    out.push('>');
+    for (i, coord) in coords.iter().enumerate() {
+        if i > 0 {
+            out.push(' ');
+        }
+        out.push_str(&format!("{} {}", coord[0], coord[1]));
+        if has_z {
+            out.push_str(&format!(" {}", coord[2]));
+        }
+    }
+    out.push_str(&format!("</gml:{}>", tag));
+    Ok(())
+}
+
+fn write_ring<'a, G: Geom<'a>>(ring: &G, boundary: &str, out: &mut String) -> GResult<()> {
+    out.push_str(&format!("<gml:{}><gml:LinearRing>", boundary));
+    write_pos_list(ring, "posList", out)?;
+    out.push_str(&format!("</gml:LinearRing></gml:{}>", boundary));
+    Ok(())
+}
+
+fn write_geometry<'a, G: Geom<'a>>(geom: &G, attributes: &str, out: &mut String) -> GResult<()> {
+    let (tag, member) = match geom.geometry_type() {
+        GeometryTypes::Point => ("Point", ""),
+        GeometryTypes::LineString => ("LineString", ""),
+        GeometryTypes::LinearRing => ("LinearRing", ""),
+        GeometryTypes::Polygon => ("Polygon", ""),
+        GeometryTypes::MultiPoint => ("MultiPoint", "pointMember"),
+        GeometryTypes::MultiLineString => ("MultiCurve", "curveMember"),
+        GeometryTypes::MultiPolygon => ("MultiSurface", "surfaceMember"),
+        GeometryTypes::GeometryCollection => ("MultiGeometry", "geometryMember"),
+        GeometryTypes::CircularString
+        | GeometryTypes::CompoundCurve
+        | GeometryTypes::CurvePolygon
+        | GeometryTypes::MultiCurve
+        | GeometryTypes::MultiSurface => return unsupported_curve(geom.geometry_type()),
+        GeometryTypes::__Unknown(x) => {
+            return Err(Error::ConversionError(format!(
+                "unknown geometry type {} can't be written in GML",
+                x
+            )))
+        }
+    };
+    if geom.is_empty()? {
+        out.push_str(&format!("<gml:{}{}/>", tag, attributes));
+        return Ok(());
+    }
+    out.push_str(&format!("<gml:{}{}>", tag, attributes));
+    match geom.geometry_type() {
+        GeometryTypes::Point => write_pos_list(geom, "pos", out)?,
+        GeometryTypes::LineString | GeometryTypes::LinearRing => {
+            write_pos_list(geom, "posList", out)?
+        }
+        GeometryTypes::Polygon => {
+            write_ring(&geom.get_exterior_ring()?, "exterior", out)?;
+            for i in 0..geom.get_num_interior_rings()? {
+                write_ring(&geom.get_interior_ring_n(i as _)?, "interior", out)?;
+            }
+        }
+        _ => {
+            for i in 0..geom.get_num_geometries()? {
+                out.push_str(&format!("<gml:{}>", member));
+                write_geometry(&geom.get_geometry_n(i)?, "", out)?;
+                out.push_str(&format!("</gml:{}>", member));
+            }
+        }
+    }
+    out.push_str(&format!("</gml:{}>", tag));
+    Ok(())
+}
@@ -0,0 +1,234 @@
+//! Conversion between geometries and KML geometry fragments.
+//!
+//! KML coordinates are always longitude/latitude (and optionally altitude) on WGS84, so the read
+//! geometries get the 4326 SRID.
+
+use super::xml::{self, Element};
+use super::{create_collection, create_simple_geom, simple_geom_coords};
+use crate::{GResult, Geom, Geometry, GeometryTypes};
+use error::Error;
+use functions::unsupported_curve;
+
+const FORMAT: &str = "KML";
+
+const GEOMETRY_NAMES: [&str; 5] = [
+    "Point",
+    "LineString",
+    "LinearRing",
+    "Polygon",
+    "MultiGeometry",
+];
+
+fn parse_error<T>(message: String) -> GResult<T> {
+    Err(Error::Parse {
+        format: FORMAT,
+        message,
+        offset: None,
+    })
+}
+
+/// Reads the first geometry found in the given KML document or fragment.
+///
+/// A `MultiGeometry` made of geometries of the same type (points, line strings or polygons) is
+/// read as the matching multi geometry, otherwise it's read as a `GeometryCollection`.
+///
+/// # Example
+///
+/// ```
+/// use geos::Geom;
+/// use geos::io::kml::from_kml;
+///
+/// let geom = from_kml("<Placemark><name>A</name><MultiGeometry>\
+///                        <Point><coordinates>1,2</coordinates></Point>\
+///                        <Point><coordinates>3,4,5</coordinates></Point>\
+///                      </MultiGeometry></Placemark>").expect("from_kml failed");
+/// assert_eq!(geom.to_wkt().unwrap(), "MULTIPOINT (1 2, 3 4)");
+/// assert_eq!(geom.get_srid(), Ok(4326));
+/// ```
+pub fn from_kml<'a>(kml: &str) -> GResult<Geometry<'a>> {
+    let root = xml::parse(kml, FORMAT)?;
+    let mut geom = match root.find(|e| GEOMETRY_NAMES.contains(&e.name.as_str())) {
+        Some(element) => read_geometry(element)?,
+        None => return parse_error("no geometry found".to_owned()),
+    };
+    geom.set_srid(4326);
+    Ok(geom)
+}
+
+fn read_simple_geom<'a>(type_: GeometryTypes, element: &Element) -> GResult<Geometry<'a>> {
+    let mut coords = Vec::new();
+    let mut has_z = false;
+    if let Some(coordinates) = element.child("coordinates") {
+        for tuple in coordinates.text.split_whitespace() {
+            let values = xml::parse_numbers(&tuple.replace(',', " "), FORMAT)?;
+            match values.len() {
+                2 => coords.push([values[0], values[1], f64::NAN]),
+                3 => {
+                    has_z = true;
+                    coords.push([values[0], values[1], values[2]]);
+                }
+                _ => return parse_error(format!("invalid coordinates \"{}\"", tuple)),
+            }
+        }
+    }
+    create_simple_geom(type_, &coords, has_z)
+}
+
+fn read_rings<'a>(boundary: &Element) -> GResult<Vec<Geometry<'a>>> {
+    boundary
+        .children
+        .iter()
+        .filter(|c| c.name == "LinearRing")
+        .map(|ring| read_simple_geom(GeometryTypes::LinearRing, ring))
+        .collect()
+}
+
+fn read_geometry<'a>(element: &Element) -> GResult<Geometry<'a>> {
+    match element.name.as_str() {
+        "Point" => read_simple_geom(GeometryTypes::Point, element),
+        "LineString" => read_simple_geom(GeometryTypes::LineString, element),
+        "LinearRing" => read_simple_geom(GeometryTypes::LinearRing, element),
+        "Polygon" => {
+            let exterior = match element.child("outerBoundaryIs") {
+                Some(boundary) => read_rings(boundary)?.into_iter().next(),
+                None => None,
+            };
+            let exterior = match exterior {
+                Some(exterior) => exterior,
+                None => return parse_error("Polygon without outerBoundaryIs".to_owned()),
+            };
+            let mut interiors = Vec::new();
+            for boundary in &element.children {
+                if boundary.name == "innerBoundaryIs" {
+                    interiors.extend(read_rings(boundary)?);
+                }
+            }
+            Geometry::create_polygon(exterior, interiors)
+        }
+        "MultiGeometry" => {
+            let geoms = element
+                .children
+                .iter()
+                .filter(|c| GEOMETRY_NAMES.contains(&c.name.as_str()))
+                .map(read_geometry)
+                .collect::<GResult<Vec<_>>>()?;
+            let types = geoms.iter().map(|g| g.geometry_type()).collect::<Vec<_>>();
+            let type_ = match types.first() {
+                Some(first) if types.iter().any(|t| t != first) => {
+                    GeometryTypes::GeometryCollection
+                }
+                Some(GeometryTypes::Point) => GeometryTypes::MultiPoint,
+                Some(GeometryTypes::LineString) => GeometryTypes::MultiLineString,
+                Some(GeometryTypes::Polygon) => GeometryTypes::MultiPolygon,
+                _ => GeometryTypes::GeometryCollection,
+            };
+            create_collection(type_, geoms)
+        }
+        name => parse_error(format!("unsupported KML geometry \"{}\"", name)),
+    }
+}
+
+/// Writes `geom` as a KML fragment. All the collections are written as `MultiGeometry`.
+///
+/// KML has no empty geometries, so they (and collections containing them) can't be written.
+///
+/// # Example
+///
+/// ```
+/// use geos::{Geom, Geometry};
+/// use geos::io::kml::{from_kml, to_kml};
+///
+/// let geom = Geometry::new_from_wkt("POLYGON((0 0, 1 0, 1 1, 0 0))").expect("Invalid geometry");
+/// let kml = to_kml(&geom).expect("to_kml failed");
+/// assert_eq!(kml,
+///            "<Polygon><outerBoundaryIs><LinearRing>\
+///               <coordinates>0,0 1,0 1,1 0,0</coordinates>\
+///             </LinearRing></outerBoundaryIs></Polygon>");
+/// assert_eq!(from_kml(&kml).expect("from_kml failed").equals(&geom), Ok(true));
+///
+/// let empty = Geometry::new_from_wkt("POINT EMPTY").expect("Invalid geometry");
+/// assert!(to_kml(&empty).is_err());
+/// ```
+pub fn to_kml<'a, G: Geom<'a>>(geom: &G) -> GResult<String> {
+    let mut out = String::new();
+    write_geometry(geom, &mut out)?;
+    Ok(out)
+}
+
+fn write_coordinates<'a, G: Geom<'a>>(geom: &G, out: &mut String) -> GResult<()> {
+    let has_z = geom.has_z()?;
+    out.push_str("<coordinates>");
+    for (i, coord) in simple_geom_coords(geom)?.iter().enumerate() {
+        if i > 0 {
+            out.push(' ');
+        }
+        out.push_str(&format!("{},{}", coord[0], coord[1]));
+        if has_z {
+            out.push_str(&format!(",{}", coord[2]));
+        }
+    }
+    out.push_str("</coordinates>");
+    Ok(())
+}
+
+fn write_ring<'a, G: Geom<'a>>(ring: &G, boundary: &str, out: &mut String) -> GResult<()> {
+    out.push_str(&format!("<{}><LinearRing>", boundary));
+    write_coordinates(ring, out)?;
+    out.push_str(&format!("</LinearRing></{}>", boundary));
+    Ok(())
+}
+
+fn write_geometry<'a, G: Geom<'a>>(geom: &G, out: &mut String) -> GResult<()> {
+    if geom.is_empty()? {
+        return Err(Error::ConversionError(
+            "empty geometries can't be written in KML".to_owned(),
+        ));
+    }
+    match geom.geometry_type() {
+        GeometryTypes::Point => {
+            out.push_str("<Point>");
+            write_coordinates(geom, out)?;
+            out.push_str("</Point>");
+        }
+        GeometryTypes::LineString => {
+            out.push_str("<LineString>");
+            write_coordinates(geom, out)?;
+            out.push_str("</LineString>");
+        }
+        GeometryTypes::LinearRing => {
+            out.push_str("<LinearRing>");
+            write_coordinates(geom, out)?;
+            out.push_str("</LinearRing>");
+        }
+        GeometryTypes::Polygon => {
+            out.push_str("<Polygon>");
+            write_ring(&geom.get_exterior_ring()?, "outerBoundaryIs", out)?;
+            for i in 0..geom.get_num_interior_rings()? {
+                write_ring(&geom.get_interior_ring_n(i as _)?, "innerBoundaryIs", out)?;
+            }
+            out.push_str("</Polygon>");
+        }
+        GeometryTypes::MultiPoint
+        | GeometryTypes::MultiLineString
+        | GeometryTypes::MultiPolygon
+        | GeometryTypes::GeometryCollection => {
+            out.push_str("<MultiGeometry>");
+            for i in 0..geom.get_num_geometries()? {
+                write_geometry(&geom.get_geometry_n(i)?, out)?;
+            }
+            out.push_str("</MultiGeometry>");
+        }
+        GeometryTypes::CircularString
+        | GeometryTypes::CompoundCurve
+        | GeometryTypes::CurvePolygon
+        | GeometryTypes::MultiCurve
+        | GeometryTypes::MultiSurface => return unsupported_curve(geom.geometry_type()),
+        GeometryTypes::__Unknown(x) => {
+            return Err(Error::ConversionError(format!(
+                "unknown geometry type {} can't be written in KML",
+                x
+            )))
+        }
+    }
+    Ok(())
+}
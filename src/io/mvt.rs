@@ -0,0 +1,315 @@
+//! Encoding of geometries as [Mapbox Vector Tile](https://github.com/mapbox/vector-tile-spec)
+//! feature geometries.
+
+use crate::{GResult, Geom, GeometryTypes};
+use bbox::Rect;
+use error::Error;
+use functions::{coords_from_seq, unsupported_curve};
+
+const MOVE_TO: u32 = 1;
+const LINE_TO: u32 = 2;
+const CLOSE_PATH: u32 = 7;
+
+/// Type of an encoded feature geometry. The values are the ones of the `GeomType` enum of the
+/// specification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GeomType {
+    Point = 1,
+    LineString = 2,
+    Polygon = 3,
+}
+
+/// A feature geometry, as stored in the `type` and `geometry` fields of a tile feature.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TileGeometry {
+    pub geom_type: GeomType,
+    pub commands: Vec<u32>,
+}
+
+/// Encodes `geom`, expressed in the same coordinate system as `tile_bounds`, as the geometry of a
+/// tile feature using a grid of `extent` by `extent` (usually 4096).
+///
+/// The geometry is clipped to the tile, its coordinates are snapped to the grid (with the Y axis
+/// pointing down), repeated points are removed, as well as the lines and rings collapsed by the
+/// snapping. Rings are reoriented as required by the specification: exterior rings have a
+/// positive area in tile coordinates, interior rings a negative one.
+///
+/// Returns `None` if nothing is left. Geometry collections mixing different dimensions can't be
+/// encoded in a single feature.
+///
+/// # Example
+///
+/// ```
+/// use geos::Geometry;
+/// use geos::bbox::Rect;
+/// use geos::io::mvt::{encode, GeomType};
+///
+/// let tile = Rect::new(0., 0., 100., 100.);
+///
+/// let point = Geometry::new_from_wkt("POINT(25 83)").expect("Invalid geometry");
+/// let encoded = encode(&point, tile, 100).expect("encode failed").unwrap();
+/// assert_eq!(encoded.geom_type, GeomType::Point);
+/// assert_eq!(encoded.commands, vec![9, 50, 34]);
+///
+/// let line = Geometry::new_from_wkt("LINESTRING(2 98, 2 90, 10 90)").expect("Invalid geometry");
+/// let encoded = encode(&line, tile, 100).expect("encode failed").unwrap();
+/// assert_eq!(encoded.commands, vec![9, 4, 4, 18, 0, 16, 16, 0]);
+///
+/// // The ring is reversed to get a positive area in tile coordinates.
+/// let polygon = Geometry::new_from_wkt("POLYGON((3 94, 20 66, 8 88, 3 94))")
+///                         .expect("Invalid geometry");
+/// let encoded = encode(&polygon, tile, 100).expect("encode failed").unwrap();
+/// assert_eq!(encoded.geom_type, GeomType::Polygon);
+/// assert_eq!(encoded.commands, vec![9, 6, 12, 18, 10, 12, 24, 44, 15]);
+///
+/// // Outside of the tile.
+/// let point = Geometry::new_from_wkt("POINT(125 83)").expect("Invalid geometry");
+/// assert_eq!(encode(&point, tile, 100), Ok(None));
+/// ```
+pub fn encode<'a, G: Geom<'a>>(
+    geom: &G,
+    tile_bounds: Rect,
+    extent: u32,
+) -> GResult<Option<TileGeometry>> {
+    let geom_type = match geom_type(geom)? {
+        Some(geom_type) => geom_type,
+        None => return Ok(None),
+    };
+    if tile_bounds.width() <= 0. || tile_bounds.height() <= 0. {
+        return Err(Error::InvalidInput(
+            "encode: the tile bounds have no area".to_owned(),
+        ));
+    }
+    let mut encoder = Encoder {
+        bounds: tile_bounds,
+        extent: f64::from(extent),
+        cursor: (0, 0),
+        commands: Vec::new(),
+    };
+    let inside = match Rect::from_geom(geom)? {
+        Some(rect) => tile_bounds.contains(&rect),
+        None => return Ok(None),
+    };
+    if inside {
+        encoder.encode(geom, geom_type)?;
+    } else {
+        let clipped = geom.clip_by_rect(
+            tile_bounds.xmin,
+            tile_bounds.ymin,
+            tile_bounds.xmax,
+            tile_bounds.ymax,
+        )?;
+        encoder.encode(&clipped, geom_type)?;
+    }
+    if encoder.commands.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(TileGeometry {
+        geom_type,
+        commands: encoder.commands,
+    }))
+}
+
+/// Returns the feature type of `geom`, or `None` if it's empty.
+fn geom_type<'a, G: Geom<'a>>(geom: &G) -> GResult<Option<GeomType>> {
+    if geom.is_empty()? {
+        return Ok(None);
+    }
+    match geom.geometry_type() {
+        GeometryTypes::Point | GeometryTypes::MultiPoint => Ok(Some(GeomType::Point)),
+        GeometryTypes::LineString | GeometryTypes::LinearRing | GeometryTypes::MultiLineString => {
+            Ok(Some(GeomType::LineString))
+        }
+        GeometryTypes::Polygon | GeometryTypes::MultiPolygon => Ok(Some(GeomType::Polygon)),
+        GeometryTypes::GeometryCollection => {
+            let mut res = None;
+            for i in 0..geom.get_num_geometries()? {
+                match (res, geom_type(&geom.get_geometry_n(i)?)?) {
+                    (_, None) => {}
+                    (None, part) => res = part,
+                    (Some(a), Some(b)) if a != b => {
+                        return Err(Error::ConversionError(
+                            "geometry collections mixing dimensions can't be encoded in MVT"
+                                .to_owned(),
+                        ))
+                    }
+                    _ => {}
+                }
+            }
+            Ok(res)
+        }
+        GeometryTypes::CircularString
+        | GeometryTypes::CompoundCurve
+        | GeometryTypes::CurvePolygon
+        | GeometryTypes::MultiCurve
+        | GeometryTypes::MultiSurface => unsupported_curve(geom.geometry_type()),
+        GeometryTypes::__Unknown(x) => Err(Error::ConversionError(format!(
+            "unknown geometry type {} can't be encoded in MVT",
+            x
+        ))),
+    }
+}
+
+/// Zigzag encoding of a command parameter.
+fn zigzag(n: i64) -> u32 {
+    ((n << 1) ^ (n >> 63)) as u32
+}
+
+/// Twice the signed area of the ring (without its closing point), Y axis pointing down.
+fn ring_area(ring: &[(i64, i64)]) -> i64 {
+    (0..ring.len())
+        .map(|i| {
+            let (x1, y1) = ring[i];
+            let (x2, y2) = ring[(i + 1) % ring.len()];
+            x1 * y2 - x2 * y1
+        })
+        .sum()
+}
+
+struct Encoder {
+    bounds: Rect,
+    extent: f64,
+    cursor: (i64, i64),
+    commands: Vec<u32>,
+}
+
+impl Encoder {
+    /// Returns the snapped coordinates of a `Point`, `LineString` or `LinearRing`, without the
+    /// repeated points.
+    fn snapped_coords<'a, G: Geom<'a>>(&self, geom: &G) -> GResult<Vec<(i64, i64)>> {
+        let mut res: Vec<(i64, i64)> = Vec::new();
+        if geom.is_empty()? {
+            return Ok(res);
+        }
+        for coord in coords_from_seq(&geom.get_coord_seq()?)? {
+            let x = (coord[0] - self.bounds.xmin) * self.extent / self.bounds.width();
+            let y = (self.bounds.ymax - coord[1]) * self.extent / self.bounds.height();
+            let point = (x.round() as i64, y.round() as i64);
+            if res.last() != Some(&point) {
+                res.push(point);
+            }
+        }
+        Ok(res)
+    }
+
+    fn command(&mut self, id: u32, count: usize) {
+        self.commands.push((id & 0x7) | ((count as u32) << 3));
+    }
+
+    fn push_points(&mut self, points: &[(i64, i64)]) {
+        for &(x, y) in points {
+            self.commands.push(zigzag(x - self.cursor.0));
+            self.commands.push(zigzag(y - self.cursor.1));
+            self.cursor = (x, y);
+        }
+    }
+
+    fn push_path(&mut self, points: &[(i64, i64)], close: bool) {
+        self.command(MOVE_TO, 1);
+        self.push_points(&points[..1]);
+        self.command(LINE_TO, points.len() - 1);
+        self.push_points(&points[1..]);
+        if close {
+            self.command(CLOSE_PATH, 1);
+        }
+    }
+
+    /// Returns the snapped ring without its closing point, oriented as asked, or `None` if it
+    /// collapsed.
+    fn snapped_ring<'a, G: Geom<'a>>(
+        &self,
+        ring: &G,
+        exterior: bool,
+    ) -> GResult<Option<Vec<(i64, i64)>>> {
+        let mut points = self.snapped_coords(ring)?;
+        if points.len() > 1 && points.first() == points.last() {
+            points.pop();
+        }
+        if points.len() < 3 {
+            return Ok(None);
+        }
+        let area = ring_area(&points);
+        if area == 0 {
+            return Ok(None);
+        }
+        if (area > 0) != exterior {
+            points[1..].reverse();
+        }
+        Ok(Some(points))
+    }
+
+    fn collect_points<'a, G: Geom<'a>>(
+        &self,
+        geom: &G,
+        points: &mut Vec<(i64, i64)>,
+    ) -> GResult<()> {
+        match geom.geometry_type() {
+            GeometryTypes::Point => points.extend(self.snapped_coords(geom)?),
+            GeometryTypes::MultiPoint | GeometryTypes::GeometryCollection => {
+                for i in 0..geom.get_num_geometries()? {
+                    self.collect_points(&geom.get_geometry_n(i)?, points)?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn encode<'a, G: Geom<'a>>(&mut self, geom: &G, geom_type: GeomType) -> GResult<()> {
+        match geom.geometry_type() {
+            GeometryTypes::MultiPoint
+            | GeometryTypes::MultiLineString
+            | GeometryTypes::MultiPolygon
+            | GeometryTypes::GeometryCollection => {
+                if geom_type == GeomType::Point {
+                    // All the points go in a single MoveTo command.
+                    let mut points = Vec::new();
+                    self.collect_points(geom, &mut points)?;
+                    if !points.is_empty() {
+                        self.command(MOVE_TO, points.len());
+                        self.push_points(&points);
+                    }
+                } else {
+                    for i in 0..geom.get_num_geometries()? {
+                        self.encode(&geom.get_geometry_n(i)?, geom_type)?;
+                    }
+                }
+            }
+            GeometryTypes::Point if geom_type == GeomType::Point => {
+                let points = self.snapped_coords(geom)?;
+                if !points.is_empty() {
+                    self.command(MOVE_TO, points.len());
+                    self.push_points(&points);
+                }
+            }
+            GeometryTypes::LineString | GeometryTypes::LinearRing
+                if geom_type == GeomType::LineString =>
+            {
+                let points = self.snapped_coords(geom)?;
+                if points.len() > 1 {
+                    self.push_path(&points, false);
+                }
+            }
+            GeometryTypes::Polygon if geom_type == GeomType::Polygon => {
+                if geom.is_empty()? {
+                    return Ok(());
+                }
+                let exterior = match self.snapped_ring(&geom.get_exterior_ring()?, true)? {
+                    Some(exterior) => exterior,
+                    None => return Ok(()),
+                };
+                self.push_path(&exterior, true);
+                for i in 0..geom.get_num_interior_rings()? {
+                    if let Some(interior) =
+                        self.snapped_ring(&geom.get_interior_ring_n(i as _)?, false)?
+                    {
+                        self.push_path(&interior, true);
+                    }
+                }
+            }
+            // Parts of another dimension, created by the clipping.
+            _ => {}
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,267 @@
+//! A minimal XML parser, only meant to read geometry fragments (no DTD, no namespace resolution).
+
+use error::{Error, GResult};
+
+/// Maximum nesting of the elements, to bound the recursion of the parser (and of the functions
+/// walking the parsed tree) on untrusted input.
+const MAX_DEPTH: usize = 256;
+
+/// An XML element. Names are stored without their namespace prefix.
+pub(crate) struct Element {
+    pub name: String,
+    pub attributes: Vec<(String, String)>,
+    pub children: Vec<Element>,
+    pub text: String,
+}
+
+impl Element {
+    /// Returns the value of the attribute with the given (local) name.
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Returns the first child with the given name.
+    pub fn child(&self, name: &str) -> Option<&Element> {
+        self.children.iter().find(|c| c.name == name)
+    }
+
+    /// Returns the first element (`self` included, depth first) for which `f` returns `true`.
+    pub fn find<F: Fn(&Element) -> bool + Copy>(&self, f: F) -> Option<&Element> {
+        if f(self) {
+            return Some(self);
+        }
+        self.children.iter().find_map(|c| c.find(f))
+    }
+}
+
+fn local_name(name: &str) -> String {
+    match name.rfind(':') {
+        Some(pos) => name[pos + 1..].to_owned(),
+        None => name.to_owned(),
+    }
+}
+
+fn decode_entities(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+    format: &'static str,
+}
+
+impl<'a> Parser<'a> {
+    fn error<T>(&self, message: &str) -> GResult<T> {
+        Err(Error::Parse {
+            format: self.format,
+            message: message.to_owned(),
+            offset: Some(self.pos),
+        })
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    /// Skips everything up to (and including) `end`.
+    fn skip_past(&mut self, end: &str) -> GResult<()> {
+        match self.rest().find(end) {
+            Some(pos) => {
+                self.pos += pos + end.len();
+                Ok(())
+            }
+            None => self.error(&format!("missing \"{}\"", end)),
+        }
+    }
+
+    fn expect(&mut self, s: &str) -> GResult<()> {
+        if self.rest().starts_with(s) {
+            self.pos += s.len();
+            Ok(())
+        } else {
+            self.error(&format!("expected \"{}\"", s))
+        }
+    }
+
+    fn read_name(&mut self) -> GResult<&'a str> {
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| c.is_whitespace() || c == '/' || c == '>' || c == '=')
+            .unwrap_or(rest.len());
+        if len == 0 {
+            return self.error("expected a name");
+        }
+        self.pos += len;
+        Ok(&rest[..len])
+    }
+
+    /// Skips the XML declaration, processing instructions, comments and doctype.
+    fn skip_misc(&mut self) -> GResult<()> {
+        loop {
+            self.skip_whitespace();
+            if self.rest().starts_with("<?") {
+                self.skip_past("?>")?;
+            } else if self.rest().starts_with("<!--") {
+                self.skip_past("-->")?;
+            } else if self.rest().starts_with("<!") {
+                self.skip_past(">")?;
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Parses an element nested in `depth` others.
+    fn parse_element(&mut self, depth: usize) -> GResult<Element> {
+        if depth >= MAX_DEPTH {
+            return Err(Error::InvalidInput(format!(
+                "{} elements nested more than {} levels deep at offset {}",
+                self.format, MAX_DEPTH, self.pos
+            )));
+        }
+        self.expect("<")?;
+        let raw_name = self.read_name()?;
+        let mut element = Element {
+            name: local_name(raw_name),
+            attributes: Vec::new(),
+            children: Vec::new(),
+            text: String::new(),
+        };
+        loop {
+            self.skip_whitespace();
+            if self.rest().starts_with("/>") {
+                self.pos += 2;
+                return Ok(element);
+            }
+            if self.rest().starts_with('>') {
+                self.pos += 1;
+                break;
+            }
+            let name = local_name(self.read_name()?);
+            self.skip_whitespace();
+            self.expect("=")?;
+            self.skip_whitespace();
+            let quote = match self.rest().chars().next() {
+                Some(c @ '"') | Some(c @ '\'') => c,
+                _ => return self.error("expected a quoted attribute value"),
+            };
+            self.pos += 1;
+            let len = match self.rest().find(quote) {
+                Some(len) => len,
+                None => return self.error("unterminated attribute value"),
+            };
+            let value = decode_entities(&self.rest()[..len]);
+            self.pos += len + 1;
+            element.attributes.push((name, value));
+        }
+        loop {
+            let rest = self.rest();
+            if rest.starts_with("</") {
+                self.pos += 2;
+                if self.read_name()? != raw_name {
+                    return self.error(&format!("expected \"</{}>\"", raw_name));
+                }
+                self.skip_whitespace();
+                self.expect(">")?;
+                return Ok(element);
+            } else if rest.starts_with("<!--") {
+                self.skip_past("-->")?;
+            } else if rest.starts_with("<![CDATA[") {
+                self.pos += 9;
+                let start = self.pos;
+                self.skip_past("]]>")?;
+                element.text.push_str(&self.input[start..self.pos - 3]);
+            } else if rest.starts_with("<?") {
+                self.skip_past("?>")?;
+            } else if rest.starts_with('<') {
+                let child = self.parse_element(depth + 1)?;
+                element.children.push(child);
+            } else if rest.is_empty() {
+                return self.error(&format!("missing \"</{}>\"", raw_name));
+            } else {
+                let len = rest.find('<').unwrap_or(rest.len());
+                element.text.push_str(&decode_entities(&rest[..len]));
+                self.pos += len;
+            }
+        }
+    }
+}
+
+/// Parses the root element of `input`. `format` is used in the returned errors.
+pub(crate) fn parse(input: &str, format: &'static str) -> GResult<Element> {
+    let mut parser = Parser {
+        input,
+        pos: 0,
+        format,
+    };
+    parser.skip_misc()?;
+    let root = parser.parse_element(0)?;
+    parser.skip_misc()?;
+    if !parser.rest().is_empty() {
+        return parser.error("unexpected content after the root element");
+    }
+    Ok(root)
+}
+
+/// Parses whitespace-separated numbers.
+pub(crate) fn parse_numbers(s: &str, format: &'static str) -> GResult<Vec<f64>> {
+    s.split_whitespace()
+        .map(|n| {
+            n.parse().map_err(|_| Error::Parse {
+                format,
+                message: format!("invalid number \"{}\"", n),
+                offset: None,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse, MAX_DEPTH};
+    use error::Error;
+
+    #[test]
+    fn parse_fragment() {
+        let root = parse(
+            "<?xml version=\"1.0\"?><!-- comment --><a:root x:id='1'>\
+             <child>1 &amp; 2</child><empty/></a:root>",
+            "XML",
+        )
+        .unwrap();
+        assert_eq!(root.name, "root");
+        assert_eq!(root.attribute("id"), Some("1"));
+        assert_eq!(root.child("child").map(|c| c.text.as_str()), Some("1 & 2"));
+        assert!(root.child("empty").is_some());
+        assert!(parse("<root><child></root>", "XML").is_err());
+        assert!(parse("<root/><other/>", "XML").is_err());
+    }
+
+    #[test]
+    fn parse_deeply_nested() {
+        let nested = |depth: usize| format!("{}{}", "<a>".repeat(depth), "</a>".repeat(depth));
+        assert!(parse(&nested(MAX_DEPTH), "XML").is_ok());
+        match parse(&nested(MAX_DEPTH + 1), "XML") {
+            Err(Error::InvalidInput(_)) => {}
+            _ => panic!("expected an InvalidInput error"),
+        }
+        // Unclosed tags too, without overflowing the stack.
+        match parse(&"<a>".repeat(100_000), "XML") {
+            Err(Error::InvalidInput(_)) => {}
+            _ => panic!("expected an InvalidInput error"),
+        }
+    }
+}
@@ -0,0 +1,230 @@
+use crate::{Geom, Geometry, GeometryTypes};
+use error::GResult;
+
+/// Returns the non-empty parts of `g`, the members of the geometry collections being flattened.
+fn parts<'a, G: Geom<'a>>(g: &G, res: &mut Vec<Geometry<'a>>) -> GResult<()> {
+    if g.is_empty()? {
+        return Ok(());
+    }
+    if g.geometry_type() == GeometryTypes::GeometryCollection {
+        for i in 0..g.get_num_geometries()? {
+            parts(&g.get_geometry_n(i)?, res)?;
+        }
+    } else {
+        res.push(Geom::clone(g));
+    }
+    Ok(())
+}
+
+/// Same as [`parts`], but the parts of a collection are merged first: what's left is at most a
+/// polygonal, a lineal and a puntal part, which don't overlap.
+fn merged_parts<'a, G: Geom<'a>>(g: &G) -> GResult<Vec<Geometry<'a>>> {
+    let mut res = Vec::new();
+    if g.geometry_type() == GeometryTypes::GeometryCollection && !g.is_empty()? {
+        parts(&g.unary_union()?, &mut res)?;
+    } else {
+        parts(g, &mut res)?;
+    }
+    Ok(res)
+}
+
+fn is_collection<'a, G: Geom<'a>>(g: &G) -> bool {
+    g.geometry_type() == GeometryTypes::GeometryCollection
+}
+
+fn intersects<'a, 'b, A: Geom<'a>, B: Geom<'b>>(a: &[A], b: &[B]) -> GResult<bool> {
+    for x in a {
+        for y in b {
+            if x.intersects(y)? {
+                return Ok(true);
+            }
+        }
+    }
+    Ok(false)
+}
+
+/// Whether the interior of a part of `a` intersects the interior of a part of `b`.
+fn interiors_intersect<'a, 'b>(a: &[Geometry<'a>], b: &[Geometry<'b>]) -> GResult<bool> {
+    for x in a {
+        for y in b {
+            if x.intersects(y)? && !x.touches(y)? {
+                return Ok(true);
+            }
+        }
+    }
+    Ok(false)
+}
+
+/// Whether every part of `b` is covered by a part of `a` (`a` being merged parts).
+fn covers<'a, 'b>(a: &[Geometry<'a>], b: &[Geometry<'b>]) -> GResult<bool> {
+    if a.is_empty() || b.is_empty() {
+        return Ok(false);
+    }
+    for y in b {
+        let mut covered = false;
+        for x in a {
+            if x.covers(y)? {
+                covered = true;
+                break;
+            }
+        }
+        if !covered {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// Predicates on `GeometryCollection`s which GEOS can't evaluate, returned by
+/// [`Geom::lenient_collections`](trait.Geom.html#tymethod.lenient_collections).
+///
+/// Each predicate is first evaluated by GEOS. If it fails and one of the geometries is a
+/// `GeometryCollection` (like the mixed results of an overlay), it's evaluated again on the parts
+/// of the collections instead:
+///
+/// * `intersects` is true if any part of `self` intersects any part of `other`, and `disjoint`
+///   is its opposite.
+/// * `covers` is true if each part of `other` is covered by a part of `self`, once the parts of
+///   `self` are merged (with [`Geom::unary_union`](trait.Geom.html#tymethod.unary_union)). A part
+///   of `other` covered by the union of a polygon and a line of `self`, but by neither of them
+///   alone, is therefore not considered covered.
+/// * `contains` is `covers` and the interiors of the merged parts intersect, `touches` is
+///   `intersects` and they don't, and `equals` is `covers` both ways.
+/// * `covered_by` and `within` are `covers` and `contains` with the arguments swapped.
+///
+/// The other errors are returned unchanged.
+///
+/// # Example
+///
+/// ```
+/// use geos::{Geom, Geometry};
+///
+/// let mixed = Geometry::new_from_wkt("GEOMETRYCOLLECTION(POLYGON((0 0, 2 0, 2 2, 0 2, 0 0)), \
+///                                                        LINESTRING(2 0, 4 0))")
+///                      .expect("Invalid geometry");
+/// let point = Geometry::new_from_wkt("POINT(3 0)").expect("Invalid geometry");
+/// let square = Geometry::new_from_wkt("POLYGON((0.5 0.5, 1 0.5, 1 1, 0.5 1, 0.5 0.5))")
+///                       .expect("Invalid geometry");
+///
+/// let lenient = mixed.lenient_collections();
+/// assert_eq!(lenient.intersects(&point), Ok(true));
+/// assert_eq!(lenient.contains(&square), Ok(true));
+/// assert_eq!(lenient.covers(&point), Ok(true));
+/// assert_eq!(square.lenient_collections().within(&mixed), Ok(true));
+/// ```
+pub struct LenientCollections<'g, G> {
+    geom: &'g G,
+}
+
+impl<'g, G> LenientCollections<'g, G> {
+    pub(crate) fn new(geom: &'g G) -> LenientCollections<'g, G> {
+        LenientCollections { geom }
+    }
+}
+
+impl<'a, 'g, G: Geom<'a>> LenientCollections<'g, G> {
+    fn has_collection<'b, O: Geom<'b>>(&self, other: &O) -> bool {
+        is_collection(self.geom) || is_collection(other)
+    }
+
+    /// [`Geom::intersects`](trait.Geom.html#tymethod.intersects), any part of `self` intersecting
+    /// any part of `other` for collections.
+    pub fn intersects<'b, O: Geom<'b>>(&self, other: &O) -> GResult<bool> {
+        match self.geom.intersects(other) {
+            Err(_) if self.has_collection(other) => {
+                let (mut a, mut b) = (Vec::new(), Vec::new());
+                parts(self.geom, &mut a)?;
+                parts(other, &mut b)?;
+                intersects(&a, &b)
+            }
+            res => res,
+        }
+    }
+
+    /// [`Geom::disjoint`](trait.Geom.html#tymethod.disjoint), the opposite of
+    /// [`intersects`](#method.intersects) for collections.
+    pub fn disjoint<'b, O: Geom<'b>>(&self, other: &O) -> GResult<bool> {
+        match self.geom.disjoint(other) {
+            Err(_) if self.has_collection(other) => self.intersects(other).map(|res| !res),
+            res => res,
+        }
+    }
+
+    /// [`Geom::touches`](trait.Geom.html#tymethod.touches), for collections the parts intersect
+    /// but none of their interiors do.
+    pub fn touches<'b, O: Geom<'b>>(&self, other: &O) -> GResult<bool> {
+        match self.geom.touches(other) {
+            Err(_) if self.has_collection(other) => {
+                let (a, b) = (merged_parts(self.geom)?, merged_parts(other)?);
+                Ok(intersects(&a, &b)? && !interiors_intersect(&a, &b)?)
+            }
+            res => res,
+        }
+    }
+
+    /// [`Geom::covers`](trait.Geom.html#tymethod.covers), each part of `other` being covered by
+    /// a merged part of `self` for collections.
+    pub fn covers<'b, O: Geom<'b>>(&self, other: &O) -> GResult<bool> {
+        match self.geom.covers(other) {
+            Err(_) if self.has_collection(other) => {
+                let mut b = Vec::new();
+                parts(other, &mut b)?;
+                covers(&merged_parts(self.geom)?, &b)
+            }
+            res => res,
+        }
+    }
+
+    /// [`Geom::covered_by`](trait.Geom.html#tymethod.covered_by), [`covers`](#method.covers)
+    /// with the arguments swapped for collections.
+    pub fn covered_by<'b, O: Geom<'b>>(&self, other: &O) -> GResult<bool> {
+        match self.geom.covered_by(other) {
+            Err(_) if self.has_collection(other) => {
+                let mut a = Vec::new();
+                parts(self.geom, &mut a)?;
+                covers(&merged_parts(other)?, &a)
+            }
+            res => res,
+        }
+    }
+
+    /// [`Geom::contains`](trait.Geom.html#tymethod.contains), [`covers`](#method.covers) with
+    /// intersecting interiors for collections.
+    pub fn contains<'b, O: Geom<'b>>(&self, other: &O) -> GResult<bool> {
+        match self.geom.contains(other) {
+            Err(_) if self.has_collection(other) => {
+                let (a, b) = (merged_parts(self.geom)?, merged_parts(other)?);
+                let mut parts_b = Vec::new();
+                parts(other, &mut parts_b)?;
+                Ok(covers(&a, &parts_b)? && interiors_intersect(&a, &b)?)
+            }
+            res => res,
+        }
+    }
+
+    /// [`Geom::within`](trait.Geom.html#tymethod.within), [`contains`](#method.contains) with
+    /// the arguments swapped for collections.
+    pub fn within<'b, O: Geom<'b>>(&self, other: &O) -> GResult<bool> {
+        match self.geom.within(other) {
+            Err(_) if self.has_collection(other) => {
+                let (a, b) = (merged_parts(self.geom)?, merged_parts(other)?);
+                let mut parts_a = Vec::new();
+                parts(self.geom, &mut parts_a)?;
+                Ok(covers(&b, &parts_a)? && interiors_intersect(&a, &b)?)
+            }
+            res => res,
+        }
+    }
+
+    /// [`Geom::equals`](trait.Geom.html#tymethod.equals), [`covers`](#method.covers) both ways
+    /// for collections.
+    pub fn equals<'b, O: Geom<'b>>(&self, other: &O) -> GResult<bool> {
+        match self.geom.equals(other) {
+            Err(_) if self.has_collection(other) => {
+                let (a, b) = (merged_parts(self.geom)?, merged_parts(other)?);
+                Ok(covers(&a, &b)? && covers(&b, &a)?)
+            }
+            res => res,
+        }
+    }
+}
@@ -9,6 +9,10 @@ extern crate geojson;
 extern crate geos_sys;
 extern crate libc;
 extern crate num;
+#[cfg(feature = "proj")]
+extern crate proj4rs;
+#[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+extern crate rayon;
 #[cfg(any(feature = "geo", feature = "dox"))]
 extern crate wkt;
 
@@ -19,38 +23,90 @@ extern crate doc_comment;
 #[cfg(all(feature = "geo", test))]
 doctest!("../README.md");
 
+#[macro_use]
+mod macros;
+
 pub(crate) mod functions;
 
-pub use context_handle::ContextHandle;
+pub use arena::GeomArena;
+pub use collection_builder::CollectionBuilder;
+pub use config::{configure, global_config, GlobalConfig};
+pub use context_handle::{ContextHandle, ContextHandleBuilder};
 pub use coord_seq::CoordSeq;
+pub use deadline::Deadline;
 #[cfg(any(feature = "v3_6_0", feature = "dox"))]
 pub use enums::Precision;
 pub use enums::{
-    ByteOrder, CoordDimensions, Dimensions, GeometryTypes, Ordinate, Orientation, OutputDimension,
+    BinaryPredicate, ByteOrder, CoordDimensions, Dimensions, EmptyCheck, GeometryTypes,
+    NonFinitePolicy, Ordinate, Orientation, OutputDimension,
 };
+#[cfg(any(feature = "v3_9_0", feature = "dox"))]
+pub use enums::{OverlayAlgorithm, OverlayOp};
 #[cfg(any(feature = "v3_7_0", feature = "dox"))]
 pub use functions::segment_intersection;
-pub use functions::{orientation_index, version};
+pub use functions::{orientation_index, sort_canonical, version, wkb_byte_order};
 pub use geometry::{ConstGeometry, Geom, Geometry};
+pub use interner::GeometryInterner;
+pub use lenient::LenientCollections;
+pub use memory::{memory_stats, MemoryStats};
 pub use prepared_geometry::PreparedGeometry;
+pub use segments::Segments;
 pub use spatial_index::{STRtree, SpatialIndex};
+pub use tolerance::ToleranceEq;
+pub use visitor::GeometryVisitor;
+pub use wkb_reader::WKBReader;
 pub use wkb_writer::WKBWriter;
+pub use wkt_reader::WKTReader;
 pub use wkt_writer::WKTWriter;
 
+pub mod analysis;
+mod arena;
+#[cfg(any(feature = "asynch", feature = "dox"))]
+pub mod asynch;
+pub mod batch;
+pub mod bbox;
+pub mod cluster;
+mod collection_builder;
+#[cfg(any(feature = "compat", feature = "dox"))]
+pub mod compat;
+mod config;
+pub mod conflate;
 mod context_handle;
 mod coord_seq;
+mod deadline;
 mod error;
+pub mod fast;
 #[cfg(any(feature = "geo", feature = "dox"))]
 pub mod from_geo;
 #[cfg(all(feature = "json"))]
 pub mod from_geojson;
+pub mod geofence;
+mod geohash;
 mod geometry;
+pub mod grid;
+mod interner;
+pub mod io;
+mod lenient;
+mod memory;
+pub mod overlay;
 mod prepared_geometry;
+pub mod raster;
+#[cfg(any(feature = "v3_6_0", feature = "dox"))]
+pub mod regularize;
+#[cfg(any(feature = "s2", feature = "dox"))]
+pub mod s2;
+mod segments;
+#[cfg(any(feature = "v3_6_0", feature = "dox"))]
+pub mod shape;
+pub mod similarity;
 mod spatial_index;
+pub mod testing;
 #[cfg(any(feature = "geo", feature = "dox"))]
 pub mod to_geo;
 #[cfg(all(feature = "json"))]
 pub mod to_geojson;
+mod tolerance;
+mod visitor;
 pub use error::{Error, GResult};
 #[cfg(any(feature = "geo", feature = "dox"))]
 mod voronoi;
@@ -58,7 +114,9 @@ mod voronoi;
 pub use voronoi::compute_voronoi;
 mod enums;
 mod traits;
+mod wkb_reader;
 mod wkb_writer;
+mod wkt_reader;
 mod wkt_writer;
 
 pub(crate) use traits::{AsRaw, AsRawMut};
@@ -0,0 +1,50 @@
+/// Creates a [`Geometry`](struct.Geometry.html) from a WKT string, panicking with the invalid
+/// input and the parsing error if it fails.
+///
+/// It's meant for tests and examples, where an invalid WKT is a bug: use
+/// [`Geometry::new_from_wkt`](struct.Geometry.html#method.new_from_wkt) to handle errors. Like
+/// `format!`, it accepts extra arguments to build the WKT string.
+///
+/// # Example
+///
+/// ```
+/// #[macro_use]
+/// extern crate geos;
+///
+/// use geos::Geom;
+///
+/// # fn main() {
+/// let polygon = geom!("POLYGON((0 0, 2 0, 2 2, 0 2, 0 0))");
+/// assert_eq!(polygon.area(), Ok(4.));
+///
+/// let (x, y) = (1.5, 2.);
+/// let point = geom!("POINT({} {})", x, y);
+/// assert_eq!(point.get_x(), Ok(1.5));
+/// # }
+/// ```
+///
+/// ```should_panic
+/// #[macro_use]
+/// extern crate geos;
+///
+/// # fn main() {
+/// let _ = geom!("POLYGON((0 0, 2 0");
+/// # }
+/// ```
+#[macro_export]
+macro_rules! geom {
+    ($wkt:expr) => {{
+        let wkt: &str = $wkt;
+        match $crate::Geometry::new_from_wkt(wkt) {
+            Ok(geom) => geom,
+            Err(e) => panic!("geom!: invalid WKT {:?}: {}", wkt, e),
+        }
+    }};
+    ($fmt:expr, $($arg:tt)+) => {{
+        let wkt = format!($fmt, $($arg)+);
+        match $crate::Geometry::new_from_wkt(&wkt) {
+            Ok(geom) => geom,
+            Err(e) => panic!("geom!: invalid WKT {:?}: {}", wkt, e),
+        }
+    }};
+}
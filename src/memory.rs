@@ -0,0 +1,59 @@
+//! Counters of the GEOS objects owned by this crate.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+pub(crate) static CONTEXTS: AtomicUsize = AtomicUsize::new(0);
+pub(crate) static GEOMETRIES: AtomicUsize = AtomicUsize::new(0);
+pub(crate) static PREPARED_GEOMETRIES: AtomicUsize = AtomicUsize::new(0);
+
+pub(crate) fn increment(counter: &AtomicUsize) {
+    counter.fetch_add(1, Ordering::Relaxed);
+}
+
+pub(crate) fn decrement(counter: &AtomicUsize) {
+    counter.fetch_sub(1, Ordering::Relaxed);
+}
+
+/// Numbers of GEOS objects currently alive in the process, returned by [`memory_stats`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryStats {
+    /// Number of [`ContextHandle`](crate::ContextHandle).
+    pub contexts: usize,
+    /// Number of [`Geometry`](crate::Geometry) (the [`ConstGeometry`](crate::ConstGeometry)
+    /// borrowing them aren't counted).
+    pub geometries: usize,
+    /// Number of [`PreparedGeometry`](crate::PreparedGeometry).
+    pub prepared_geometries: usize,
+}
+
+/// Returns the numbers of GEOS objects currently owned by this crate.
+///
+/// The GEOS C API doesn't allow to override its allocator (GEOS allocates with the C++ `new`
+/// operator and `malloc`), so the memory it uses can't be reported in bytes. Since it goes through
+/// the process allocator, a `malloc` implementation providing statistics (like jemalloc) can
+/// measure it; these counters tell how many GEOS objects are kept alive from Rust.
+///
+/// # Example
+///
+/// ```
+/// use geos::{memory_stats, Geom, Geometry};
+///
+/// let before = memory_stats();
+/// let geom = Geometry::new_from_wkt("POINT (1 2)").expect("Invalid geometry");
+/// let prepared = geom.to_prepared_geom().expect("to_prepared_geom failed");
+///
+/// let stats = memory_stats();
+/// assert_eq!(stats.geometries, before.geometries + 1);
+/// assert_eq!(stats.prepared_geometries, before.prepared_geometries + 1);
+///
+/// drop(prepared);
+/// drop(geom);
+/// assert_eq!(memory_stats(), before);
+/// ```
+pub fn memory_stats() -> MemoryStats {
+    MemoryStats {
+        contexts: CONTEXTS.load(Ordering::Relaxed),
+        geometries: GEOMETRIES.load(Ordering::Relaxed),
+        prepared_geometries: PREPARED_GEOMETRIES.load(Ordering::Relaxed),
+    }
+}
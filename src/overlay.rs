@@ -0,0 +1,246 @@
+//! Overlay operations working on several geometries at once.
+
+use crate::{Geom, Geometry, GeometryTypes};
+use error::{Error, GResult};
+
+/// The three parts of the overlay of two polygonal geometries, returned by [`decompose_pair`].
+pub struct PairDecomposition<'a> {
+    /// The part of `a` which isn't covered by `b` (`a.difference(b)`).
+    pub a_only: Geometry<'a>,
+    /// The part of `b` which isn't covered by `a` (`b.difference(a)`).
+    pub b_only: Geometry<'a>,
+    /// The part covered by both `a` and `b` (`a.intersection(b)`).
+    pub both: Geometry<'a>,
+}
+
+/// What changed between two versions of a polygonal geometry, returned by
+/// [`Geom::change_metrics`](../trait.Geom.html#tymethod.change_metrics).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChangeMetrics {
+    /// Area only covered by the new version.
+    pub added_area: f64,
+    /// Area only covered by the old version.
+    pub removed_area: f64,
+    /// Area covered by both versions.
+    pub unchanged_area: f64,
+    /// Hausdorff distance between the two versions.
+    pub hausdorff_distance: f64,
+}
+
+fn check_polygonal<'a, G: Geom<'a>>(g: &G) -> GResult<()> {
+    match g.geometry_type() {
+        GeometryTypes::Polygon | GeometryTypes::MultiPolygon => Ok(()),
+        _ => Err(Error::GenericError(
+            "Geometry must be a Polygon or a MultiPolygon".to_owned(),
+        )),
+    }
+}
+
+/// Checks the grid size given to the OverlayNG operations.
+#[cfg(any(feature = "v3_9_0", feature = "dox"))]
+pub(crate) fn check_grid_size(grid_size: f64) -> GResult<()> {
+    if !grid_size.is_finite() || grid_size < 0. {
+        return Err(Error::InvalidInput(format!(
+            "the grid size must be a non-negative number, got {}",
+            grid_size
+        )));
+    }
+    Ok(())
+}
+
+fn dissolve(faces: Vec<Geometry>) -> GResult<Geometry> {
+    if faces.is_empty() {
+        Geometry::create_empty_collection(GeometryTypes::MultiPolygon)
+    } else {
+        Geometry::create_multipolygon(faces)?.unary_union()
+    }
+}
+
+/// Splits two polygonal geometries into the parts belonging only to `a`, only to `b`, and to
+/// both of them.
+///
+/// Instead of running three overlay operations, the boundaries of `a` and `b` are noded
+/// together once and the faces they delimit are sorted into the three parts.
+///
+/// # Example
+///
+/// ```
+/// use geos::{Geom, Geometry};
+/// use geos::overlay::decompose_pair;
+///
+/// let a = Geometry::new_from_wkt("POLYGON((0 0, 2 0, 2 2, 0 2, 0 0))").expect("Invalid geometry");
+/// let b = Geometry::new_from_wkt("POLYGON((1 1, 3 1, 3 3, 1 3, 1 1))").expect("Invalid geometry");
+///
+/// let parts = decompose_pair(&a, &b).expect("decompose_pair failed");
+/// assert_eq!(parts.a_only.area(), Ok(3.));
+/// assert_eq!(parts.b_only.area(), Ok(3.));
+/// assert_eq!(parts.both.area(), Ok(1.));
+/// assert_eq!(parts.both.equals(&a.intersection(&b).unwrap()), Ok(true));
+/// ```
+pub fn decompose_pair<'a, 'b, A: Geom<'a>, B: Geom<'b>>(
+    a: &A,
+    b: &B,
+) -> GResult<PairDecomposition<'a>> {
+    check_polygonal(a)?;
+    check_polygonal(b)?;
+
+    let edges = a.boundary()?.union(&b.boundary()?)?;
+    let faces = Geometry::polygonize(&[edges])?;
+    let (prepared_a, prepared_b) = (a.to_prepared_geom()?, b.to_prepared_geom()?);
+
+    let (mut a_only, mut b_only, mut both) = (Vec::new(), Vec::new(), Vec::new());
+    for n in 0..faces.get_num_geometries()? {
+        let face = Geom::clone(&faces.get_geometry_n(n)?);
+        let point = face.point_on_surface()?;
+        match (prepared_a.contains(&point)?, prepared_b.contains(&point)?) {
+            (true, true) => both.push(face),
+            (true, false) => a_only.push(face),
+            (false, true) => b_only.push(face),
+            // A hole of both geometries.
+            (false, false) => {}
+        }
+    }
+    Ok(PairDecomposition {
+        a_only: dissolve(a_only)?,
+        b_only: dissolve(b_only)?,
+        both: dissolve(both)?,
+    })
+}
+
+/// Unions a stream of geometries, with a memory usage growing logarithmically with the number of
+/// inputs.
+///
+/// Folding the inputs with [`Geom::union`] is quadratic since the accumulated result keeps
+/// growing. Instead, the inputs are gathered in batches which are unioned all at once, and the
+/// batch results are unioned two by two, like the nodes of a balanced binary tree.
+///
+/// # Example
+///
+/// ```
+/// use geos::{Geom, Geometry};
+/// use geos::overlay::UnionAccumulator;
+///
+/// let mut acc = UnionAccumulator::with_batch_size(2);
+/// for i in 0..10 {
+///     let wkt = format!("POLYGON(({0} 0, {1} 0, {1} 1, {0} 1, {0} 0))", i, i + 1);
+///     acc.add(Geometry::new_from_wkt(&wkt).expect("Invalid geometry")).expect("add failed");
+/// }
+/// let union = acc.finish().expect("finish failed");
+/// assert_eq!(union.area(), Ok(10.));
+/// assert_eq!(union.get_num_geometries(), Ok(1));
+/// ```
+pub struct UnionAccumulator<'a> {
+    batch_size: usize,
+    batch: Vec<Geometry<'a>>,
+    // Partial unions, with the number of times they've been merged. The levels are decreasing.
+    partials: Vec<(usize, Geometry<'a>)>,
+}
+
+impl<'a> Default for UnionAccumulator<'a> {
+    fn default() -> UnionAccumulator<'a> {
+        UnionAccumulator::new()
+    }
+}
+
+impl<'a> UnionAccumulator<'a> {
+    /// Creates a `UnionAccumulator` with batches of 64 geometries.
+    pub fn new() -> UnionAccumulator<'a> {
+        UnionAccumulator::with_batch_size(64)
+    }
+
+    /// Creates a `UnionAccumulator` with batches of `batch_size` geometries (at least 1).
+    pub fn with_batch_size(batch_size: usize) -> UnionAccumulator<'a> {
+        let batch_size = batch_size.max(1);
+        UnionAccumulator {
+            batch_size,
+            batch: Vec::with_capacity(batch_size),
+            partials: Vec::new(),
+        }
+    }
+
+    fn union_batch(&mut self) -> GResult<Option<Geometry<'a>>> {
+        if self.batch.is_empty() {
+            return Ok(None);
+        }
+        let batch = std::mem::replace(&mut self.batch, Vec::with_capacity(self.batch_size));
+        Ok(Some(
+            Geometry::create_geometry_collection(batch)?.unary_union()?,
+        ))
+    }
+
+    /// Adds a geometry to the union.
+    pub fn add(&mut self, geom: Geometry<'a>) -> GResult<()> {
+        self.batch.push(geom);
+        if self.batch.len() < self.batch_size {
+            return Ok(());
+        }
+        let mut level = 0;
+        let mut partial = match self.union_batch()? {
+            Some(partial) => partial,
+            None => return Ok(()),
+        };
+        while self.partials.last().map(|(l, _)| *l) == Some(level) {
+            if let Some((_, previous)) = self.partials.pop() {
+                partial = previous.union(&partial)?;
+                level += 1;
+            }
+        }
+        self.partials.push((level, partial));
+        Ok(())
+    }
+
+    /// Returns the union of all the added geometries, or an empty `GeometryCollection` if none
+    /// was added.
+    pub fn finish(mut self) -> GResult<Geometry<'a>> {
+        let mut res = self.union_batch()?;
+        while let Some((_, partial)) = self.partials.pop() {
+            res = Some(match res {
+                Some(res) => partial.union(&res)?,
+                None => partial,
+            });
+        }
+        match res {
+            Some(res) => Ok(res),
+            None => Geometry::create_empty_collection(GeometryTypes::GeometryCollection),
+        }
+    }
+}
+
+// Added in GEOS 3.9, which the geos-sys bindings don't cover yet.
+#[cfg(any(feature = "v3_9_0", feature = "dox"))]
+pub(crate) mod ng {
+    use geos_sys::{GEOSContextHandle_t, GEOSGeometry};
+    use libc::c_double;
+
+    extern "C" {
+        pub fn GEOSIntersectionPrec_r(
+            handle: GEOSContextHandle_t,
+            g1: *const GEOSGeometry,
+            g2: *const GEOSGeometry,
+            grid_size: c_double,
+        ) -> *mut GEOSGeometry;
+        pub fn GEOSUnionPrec_r(
+            handle: GEOSContextHandle_t,
+            g1: *const GEOSGeometry,
+            g2: *const GEOSGeometry,
+            grid_size: c_double,
+        ) -> *mut GEOSGeometry;
+        pub fn GEOSDifferencePrec_r(
+            handle: GEOSContextHandle_t,
+            g1: *const GEOSGeometry,
+            g2: *const GEOSGeometry,
+            grid_size: c_double,
+        ) -> *mut GEOSGeometry;
+        pub fn GEOSSymDifferencePrec_r(
+            handle: GEOSContextHandle_t,
+            g1: *const GEOSGeometry,
+            g2: *const GEOSGeometry,
+            grid_size: c_double,
+        ) -> *mut GEOSGeometry;
+        pub fn GEOSUnaryUnionPrec_r(
+            handle: GEOSContextHandle_t,
+            g: *const GEOSGeometry,
+            grid_size: c_double,
+        ) -> *mut GEOSGeometry;
+    }
+}
@@ -4,6 +4,7 @@ use error::Error;
 use error::PredicateType;
 use functions::*;
 use geos_sys::*;
+use memory;
 
 use std::mem::transmute;
 use std::sync::Arc;
@@ -65,6 +66,7 @@ impl<'a> PreparedGeometry<'a> {
                 caller, extra
             )));
         }
+        memory::increment(&memory::PREPARED_GEOMETRIES);
         Ok(PreparedGeometry {
             ptr: PtrWrap(ptr),
             context,
@@ -92,7 +94,7 @@ impl<'a> PreparedGeometry<'a> {
         let ret_val = unsafe {
             GEOSPreparedContains_r(self.get_raw_context(), self.as_raw(), other.as_raw())
         };
-        check_geos_predicate(ret_val, PredicateType::PreparedContains)
+        check_geos_predicate_in(ret_val, PredicateType::PreparedContains, self)
     }
 
     /// Returns `true` if every point of the `other` geometry is inside self's interior.
@@ -116,7 +118,7 @@ impl<'a> PreparedGeometry<'a> {
         let ret_val = unsafe {
             GEOSPreparedContainsProperly_r(self.get_raw_context(), self.as_raw(), other.as_raw())
         };
-        check_geos_predicate(ret_val, PredicateType::PreparedContainsProperly)
+        check_geos_predicate_in(ret_val, PredicateType::PreparedContainsProperly, self)
     }
 
     /// Returns `true` if no point of `self` is outside of `other`.
@@ -145,7 +147,7 @@ impl<'a> PreparedGeometry<'a> {
         let ret_val = unsafe {
             GEOSPreparedCoveredBy_r(self.get_raw_context(), self.as_raw(), other.as_raw())
         };
-        check_geos_predicate(ret_val, PredicateType::PreparedCoveredBy)
+        check_geos_predicate_in(ret_val, PredicateType::PreparedCoveredBy, self)
     }
 
     /// Returns `true` if no point of `other` is outside of `self`.
@@ -173,7 +175,7 @@ impl<'a> PreparedGeometry<'a> {
     pub fn covers<'b, G: Geom<'b>>(&self, other: &G) -> GResult<bool> {
         let ret_val =
             unsafe { GEOSPreparedCovers_r(self.get_raw_context(), self.as_raw(), other.as_raw()) };
-        check_geos_predicate(ret_val, PredicateType::PreparedCovers)
+        check_geos_predicate_in(ret_val, PredicateType::PreparedCovers, self)
     }
 
     /// Returns `true` if `self` and `other` have at least one interior into each other.
@@ -194,7 +196,7 @@ impl<'a> PreparedGeometry<'a> {
     pub fn crosses<'b, G: Geom<'b>>(&self, other: &G) -> GResult<bool> {
         let ret_val =
             unsafe { GEOSPreparedCrosses_r(self.get_raw_context(), self.as_raw(), other.as_raw()) };
-        check_geos_predicate(ret_val, PredicateType::PreparedCrosses)
+        check_geos_predicate_in(ret_val, PredicateType::PreparedCrosses, self)
     }
 
     /// Returns `true` if `self` doesn't:
@@ -225,7 +227,7 @@ impl<'a> PreparedGeometry<'a> {
         let ret_val = unsafe {
             GEOSPreparedDisjoint_r(self.get_raw_context(), self.as_raw(), other.as_raw())
         };
-        check_geos_predicate(ret_val, PredicateType::PreparedDisjoint)
+        check_geos_predicate_in(ret_val, PredicateType::PreparedDisjoint, self)
     }
 
     /// Returns `true` if `self` shares any portion of space with `other`. So if any of this is
@@ -259,7 +261,7 @@ impl<'a> PreparedGeometry<'a> {
         let ret_val = unsafe {
             GEOSPreparedIntersects_r(self.get_raw_context(), self.as_raw(), other.as_raw())
         };
-        check_geos_predicate(ret_val, PredicateType::PreparedIntersects)
+        check_geos_predicate_in(ret_val, PredicateType::PreparedIntersects, self)
     }
 
     /// Returns `true` if `self` spatially overlaps `other`.
@@ -291,7 +293,7 @@ impl<'a> PreparedGeometry<'a> {
         let ret_val = unsafe {
             GEOSPreparedOverlaps_r(self.get_raw_context(), self.as_raw(), other.as_raw())
         };
-        check_geos_predicate(ret_val, PredicateType::PreparedOverlaps)
+        check_geos_predicate_in(ret_val, PredicateType::PreparedOverlaps, self)
     }
 
     /// Returns `true` if the only points in common between `self` and `other` lie in the union of
@@ -318,7 +320,7 @@ impl<'a> PreparedGeometry<'a> {
     pub fn touches<'b, G: Geom<'b>>(&self, other: &G) -> GResult<bool> {
         let ret_val =
             unsafe { GEOSPreparedTouches_r(self.get_raw_context(), self.as_raw(), other.as_raw()) };
-        check_geos_predicate(ret_val, PredicateType::PreparedTouches)
+        check_geos_predicate_in(ret_val, PredicateType::PreparedTouches, self)
     }
 
     /// Returns `true` if `self` is completely inside `other`.
@@ -347,7 +349,95 @@ impl<'a> PreparedGeometry<'a> {
     pub fn within<'b, G: Geom<'b>>(&self, other: &G) -> GResult<bool> {
         let ret_val =
             unsafe { GEOSPreparedWithin_r(self.get_raw_context(), self.as_raw(), other.as_raw()) };
-        check_geos_predicate(ret_val, PredicateType::PreparedWithin)
+        check_geos_predicate_in(ret_val, PredicateType::PreparedWithin, self)
+    }
+
+    /// Returns `true` if `self` contains the `(x, y)` point (see
+    /// [`contains`](#method.contains)), without creating a point geometry.
+    ///
+    /// Available using the `v3_12_0` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let geom = Geometry::new_from_wkt("POLYGON((0 0, 10 0, 10 6, 0 6, 0 0))")
+    ///                     .expect("Invalid geometry");
+    /// let prepared_geom = geom.to_prepared_geom().expect("failed to create prepared geom");
+    ///
+    /// assert_eq!(prepared_geom.contains_xy(2.5, 2.5), Ok(true));
+    /// assert_eq!(prepared_geom.contains_xy(10., 2.5), Ok(false));
+    /// ```
+    #[cfg(any(feature = "v3_12_0", feature = "dox"))]
+    pub fn contains_xy(&self, x: f64, y: f64) -> GResult<bool> {
+        let ret_val =
+            unsafe { xy::GEOSPreparedContainsXY_r(self.get_raw_context(), self.as_raw(), x, y) };
+        check_geos_predicate_in(ret_val, PredicateType::PreparedContains, self)
+    }
+
+    /// Returns `true` if `self` intersects the `(x, y)` point (see
+    /// [`intersects`](#method.intersects)), without creating a point geometry.
+    ///
+    /// Available using the `v3_12_0` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    ///
+    /// let geom = Geometry::new_from_wkt("POLYGON((0 0, 10 0, 10 6, 0 6, 0 0))")
+    ///                     .expect("Invalid geometry");
+    /// let prepared_geom = geom.to_prepared_geom().expect("failed to create prepared geom");
+    ///
+    /// assert_eq!(prepared_geom.intersects_xy(10., 2.5), Ok(true));
+    /// assert_eq!(prepared_geom.intersects_xy(11., 2.5), Ok(false));
+    /// ```
+    #[cfg(any(feature = "v3_12_0", feature = "dox"))]
+    pub fn intersects_xy(&self, x: f64, y: f64) -> GResult<bool> {
+        let ret_val =
+            unsafe { xy::GEOSPreparedIntersectsXY_r(self.get_raw_context(), self.as_raw(), x, y) };
+        check_geos_predicate_in(ret_val, PredicateType::PreparedIntersects, self)
+    }
+
+    /// Same as `contains_xy`, creating the point when it isn't available.
+    pub(crate) fn contains_point(&self, x: f64, y: f64) -> GResult<bool> {
+        #[cfg(any(feature = "v3_12_0", feature = "dox"))]
+        {
+            self.contains_xy(x, y)
+        }
+        #[cfg(not(any(feature = "v3_12_0", feature = "dox")))]
+        {
+            use crate::{CoordSeq, Geometry};
+
+            let point = Geometry::create_point(CoordSeq::new_from_vec_with_context(
+                &[[x, y]],
+                self.clone_context(),
+            )?)?;
+            self.contains(&point)
+        }
+    }
+}
+
+// Added in GEOS 3.12, which the geos-sys bindings don't cover yet.
+#[cfg(any(feature = "v3_12_0", feature = "dox"))]
+mod xy {
+    use geos_sys::{GEOSContextHandle_t, GEOSPreparedGeometry};
+    use libc::{c_char, c_double};
+
+    extern "C" {
+        pub fn GEOSPreparedContainsXY_r(
+            handle: GEOSContextHandle_t,
+            pg1: *const GEOSPreparedGeometry,
+            x: c_double,
+            y: c_double,
+        ) -> c_char;
+        pub fn GEOSPreparedIntersectsXY_r(
+            handle: GEOSContextHandle_t,
+            pg1: *const GEOSPreparedGeometry,
+            x: c_double,
+            y: c_double,
+        ) -> c_char;
     }
 }
 
@@ -357,6 +447,7 @@ unsafe impl<'a> Sync for PreparedGeometry<'a> {}
 impl<'a> Drop for PreparedGeometry<'a> {
     fn drop(&mut self) {
         unsafe { GEOSPreparedGeom_destroy_r(self.get_raw_context(), self.as_raw()) };
+        memory::decrement(&memory::PREPARED_GEOMETRIES);
     }
 }
 
@@ -0,0 +1,385 @@
+//! Conversions between geometries and regular grids of cells, for masking or contouring without
+//! a raster library.
+
+use crate::{CoordSeq, Geom, Geometry, GeometryTypes};
+use bbox::Rect;
+use error::{Error, GResult};
+use functions::{create_rectangle, signed_ring_area};
+
+/// Returns the number of cells of a `width` by `height` grid, or an error if it overflows.
+fn cell_count(width: usize, height: usize) -> GResult<usize> {
+    width.checked_mul(height).ok_or_else(|| {
+        Error::InvalidInput(format!("a {}x{} grid has too many cells", width, height))
+    })
+}
+
+/// A grid of booleans, stored as bits. The cell `(0, 0)` is the top-left one, like in most
+/// raster formats.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BitGrid {
+    width: usize,
+    height: usize,
+    bits: Vec<u64>,
+}
+
+impl BitGrid {
+    /// Creates a grid of `width` columns and `height` rows, all `false`. Returns an
+    /// [`Error::InvalidInput`](crate::Error::InvalidInput) if the number of cells overflows.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::raster::BitGrid;
+    ///
+    /// let grid = BitGrid::new(3, 2).expect("BitGrid::new failed");
+    /// assert_eq!(grid.count_ones(), 0);
+    /// assert!(BitGrid::new(usize::MAX, 2).is_err());
+    /// ```
+    pub fn new(width: usize, height: usize) -> GResult<BitGrid> {
+        Ok(BitGrid {
+            width,
+            height,
+            bits: vec![0; cell_count(width, height)?.div_ceil(64)],
+        })
+    }
+
+    /// Returns the number of columns.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the number of rows.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn index(&self, col: usize, row: usize) -> usize {
+        assert!(
+            col < self.width && row < self.height,
+            "cell ({}, {}) out of a {}x{} grid",
+            col,
+            row,
+            self.width,
+            self.height
+        );
+        row * self.width + col
+    }
+
+    /// Returns the value of the cell at column `col` and row `row`.
+    ///
+    /// # Panics
+    ///
+    /// If the cell is out of the grid.
+    pub fn get(&self, col: usize, row: usize) -> bool {
+        let i = self.index(col, row);
+        self.bits[i / 64] & (1 << (i % 64)) != 0
+    }
+
+    /// Sets the value of the cell at column `col` and row `row`.
+    ///
+    /// # Panics
+    ///
+    /// If the cell is out of the grid.
+    pub fn set(&mut self, col: usize, row: usize, value: bool) {
+        let i = self.index(col, row);
+        if value {
+            self.bits[i / 64] |= 1 << (i % 64);
+        } else {
+            self.bits[i / 64] &= !(1 << (i % 64));
+        }
+    }
+
+    /// Returns the number of `true` cells.
+    pub fn count_ones(&self) -> usize {
+        self.bits.iter().map(|b| b.count_ones() as usize).sum()
+    }
+}
+
+fn burn<'a, G: Geom<'a>>(
+    geom: &G,
+    bbox: Rect,
+    nx: usize,
+    ny: usize,
+    all_touched: bool,
+) -> GResult<BitGrid> {
+    if nx == 0 || ny == 0 || bbox.area() <= 0. {
+        return Err(Error::InvalidInput(
+            "the grid needs at least one cell and a non-empty bbox".to_owned(),
+        ));
+    }
+    let mut grid = BitGrid::new(nx, ny)?;
+    let envelope = match Rect::from_geom(geom)? {
+        Some(envelope) => envelope,
+        None => return Ok(grid),
+    };
+    let prepared = geom.to_prepared_geom()?;
+    let (cell_width, cell_height) = (bbox.width() / nx as f64, bbox.height() / ny as f64);
+    for row in 0..ny {
+        let y1 = bbox.ymax - row as f64 * cell_height;
+        let y0 = y1 - cell_height;
+        for col in 0..nx {
+            let x0 = bbox.xmin + col as f64 * cell_width;
+            let x1 = x0 + cell_width;
+            if !envelope.intersects(&Rect::new(x0, y0, x1, y1)) {
+                continue;
+            }
+            let burnt = if all_touched {
+                prepared.intersects(&create_rectangle(x0, y0, x1, y1)?)?
+            } else {
+                prepared.contains_point((x0 + x1) / 2., (y0 + y1) / 2.)?
+            };
+            if burnt {
+                grid.set(col, row, true);
+            }
+        }
+    }
+    Ok(grid)
+}
+
+/// Rasterizes `geom` on a grid of `nx` columns and `ny` rows covering `bbox`: a cell is `true`
+/// if its center is within `geom`.
+///
+/// # Example
+///
+/// ```
+/// use geos::Geometry;
+/// use geos::bbox::Rect;
+/// use geos::raster::rasterize;
+///
+/// let triangle = Geometry::new_from_wkt("POLYGON((0 0, 4 0, 0 4, 0 0))").expect("Invalid geometry");
+/// let grid = rasterize(&triangle, Rect::new(0., 0., 4., 4.), 4, 4).expect("rasterize failed");
+/// assert_eq!(grid.count_ones(), 6);
+/// assert!(grid.get(0, 3));
+/// assert!(!grid.get(3, 0));
+/// ```
+pub fn rasterize<'a, G: Geom<'a>>(geom: &G, bbox: Rect, nx: usize, ny: usize) -> GResult<BitGrid> {
+    burn(geom, bbox, nx, ny, false)
+}
+
+/// Same as [`rasterize`](fn.rasterize.html), but a cell is `true` if it intersects `geom` at
+/// all, even only by its boundary (the "all touched" mode of GDAL).
+///
+/// # Example
+///
+/// ```
+/// use geos::Geometry;
+/// use geos::bbox::Rect;
+/// use geos::raster::rasterize_all_touched;
+///
+/// let line = Geometry::new_from_wkt("LINESTRING(0.5 0.5, 3.5 0.5)").expect("Invalid geometry");
+/// let grid = rasterize_all_touched(&line, Rect::new(0., 0., 4., 4.), 4, 4)
+///     .expect("rasterize_all_touched failed");
+/// assert_eq!(grid.count_ones(), 4);
+/// assert!(grid.get(2, 3));
+/// ```
+pub fn rasterize_all_touched<'a, G: Geom<'a>>(
+    geom: &G,
+    bbox: Rect,
+    nx: usize,
+    ny: usize,
+) -> GResult<BitGrid> {
+    burn(geom, bbox, nx, ny, true)
+}
+
+/// A grid of values sampled at regularly spaced points, the first and last columns and rows
+/// being on the sides of its bbox. The point `(0, 0)` is the top-left one. `NaN` values mark
+/// missing data.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValueGrid {
+    bbox: Rect,
+    width: usize,
+    height: usize,
+    values: Vec<f64>,
+}
+
+impl ValueGrid {
+    /// Creates a grid of `width` columns and `height` rows (at least 2 of each) covering
+    /// `bbox`, from its `values` given row by row, starting from the top.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::bbox::Rect;
+    /// use geos::raster::ValueGrid;
+    ///
+    /// let grid = ValueGrid::new(Rect::new(0., 0., 2., 1.), 3, 2, vec![0., 1., 2., 3., 4., 5.])
+    ///     .expect("ValueGrid::new failed");
+    /// assert_eq!(grid.get(2, 0), 2.);
+    /// assert!(ValueGrid::new(Rect::new(0., 0., 2., 1.), 3, 2, vec![0.]).is_err());
+    /// ```
+    pub fn new(bbox: Rect, width: usize, height: usize, values: Vec<f64>) -> GResult<ValueGrid> {
+        if width < 2 || height < 2 || bbox.area() <= 0. {
+            return Err(Error::InvalidInput(
+                "the grid needs at least 2 columns and 2 rows, and a non-empty bbox".to_owned(),
+            ));
+        }
+        let count = cell_count(width, height)?;
+        if values.len() != count {
+            return Err(Error::InvalidInput(format!(
+                "a {}x{} grid needs {} values, got {}",
+                width,
+                height,
+                count,
+                values.len()
+            )));
+        }
+        Ok(ValueGrid {
+            bbox,
+            width,
+            height,
+            values,
+        })
+    }
+
+    /// Returns the number of columns.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the number of rows.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the area covered by the grid.
+    pub fn bbox(&self) -> Rect {
+        self.bbox
+    }
+
+    /// Returns the value at column `col` and row `row`.
+    ///
+    /// # Panics
+    ///
+    /// If the point is out of the grid.
+    pub fn get(&self, col: usize, row: usize) -> f64 {
+        assert!(
+            col < self.width && row < self.height,
+            "point ({}, {}) out of a {}x{} grid",
+            col,
+            row,
+            self.width,
+            self.height
+        );
+        self.values[row * self.width + col]
+    }
+
+    fn point(&self, col: usize, row: usize) -> [f64; 3] {
+        let dx = self.bbox.width() / (self.width - 1) as f64;
+        let dy = self.bbox.height() / (self.height - 1) as f64;
+        [
+            self.bbox.xmin + col as f64 * dx,
+            self.bbox.ymax - row as f64 * dy,
+            self.get(col, row),
+        ]
+    }
+}
+
+/// Returns the part of the cell whose corners are `corners` (in order around it) where the
+/// values, linearly interpolated along its sides, are greater than or equal to `threshold`.
+fn cell_part(corners: &[[f64; 3]; 4], threshold: f64) -> Vec<[f64; 2]> {
+    let mut res: Vec<[f64; 2]> = Vec::with_capacity(8);
+    let mut push = |p: [f64; 2]| {
+        if res.last() != Some(&p) {
+            res.push(p);
+        }
+    };
+    for i in 0..4 {
+        let (a, b) = (&corners[i], &corners[(i + 1) % 4]);
+        if a[2] >= threshold {
+            push([a[0], a[1]]);
+        }
+        if (a[2] >= threshold) != (b[2] >= threshold) {
+            let r = (threshold - a[2]) / (b[2] - a[2]);
+            push([a[0] + (b[0] - a[0]) * r, a[1] + (b[1] - a[1]) * r]);
+        }
+    }
+    if res.len() > 1 && res.first() == res.last() {
+        res.pop();
+    }
+    res
+}
+
+/// Returns the (dissolved) area where the values of `grid` are greater than or equal to
+/// `threshold`.
+fn area_above(grid: &ValueGrid, threshold: f64) -> GResult<Geometry<'static>> {
+    let mut parts = Vec::new();
+    for row in 0..grid.height - 1 {
+        for col in 0..grid.width - 1 {
+            let corners = [
+                grid.point(col, row),
+                grid.point(col + 1, row),
+                grid.point(col + 1, row + 1),
+                grid.point(col, row + 1),
+            ];
+            if corners.iter().any(|c| c[2].is_nan()) {
+                continue;
+            }
+            let mut ring = cell_part(&corners, threshold);
+            if ring.len() < 3 {
+                continue;
+            }
+            ring.push(ring[0]);
+            let coords = ring.iter().map(|c| [c[0], c[1], 0.]).collect::<Vec<_>>();
+            if signed_ring_area(&coords) == 0. {
+                continue;
+            }
+            let ring = Geometry::create_linear_ring(CoordSeq::new_from_vec(&ring)?)?;
+            parts.push(Geometry::create_polygon(ring, Vec::new())?);
+        }
+    }
+    if parts.is_empty() {
+        return Geometry::create_empty_collection(GeometryTypes::MultiPolygon);
+    }
+    Geometry::create_multipolygon(parts)?.unary_union()
+}
+
+/// Computes the isobands of `grid`: for each of the `thresholds` (in increasing order), the
+/// area where the values are between it (included) and the next threshold (excluded, or without
+/// upper limit for the last one).
+///
+/// The values are linearly interpolated along the sides of the grid cells (marching squares),
+/// the saddle cells connecting their high corners. The polygons found in each cell are dissolved
+/// and cleaned by GEOS, and the cells with a missing (`NaN`) value are left out.
+///
+/// # Example
+///
+/// ```
+/// use geos::{Geom, Geometry};
+/// use geos::bbox::Rect;
+/// use geos::raster::{contours, ValueGrid};
+///
+/// let grid = ValueGrid::new(Rect::new(0., 0., 2., 2.), 3, 3,
+///                           vec![0., 0., 0.,
+///                                0., 4., 0.,
+///                                0., 0., 0.])
+///     .expect("ValueGrid::new failed");
+/// let bands = contours(&grid, &[1., 2.]).expect("contours failed");
+///
+/// // The values are at least 2 in a diamond around the center.
+/// let diamond = Geometry::new_from_wkt("POLYGON((1 0.5, 1.5 1, 1 1.5, 0.5 1, 1 0.5))")
+///                        .expect("Invalid geometry");
+/// assert_eq!(bands[1].0, 2.);
+/// assert_eq!(bands[1].1.equals(&diamond), Ok(true));
+/// // And at least 1 in a larger one, around the first band.
+/// assert_eq!(bands[0].1.area(), Ok(1.125 - 0.5));
+/// ```
+pub fn contours(grid: &ValueGrid, thresholds: &[f64]) -> GResult<Vec<(f64, Geometry<'static>)>> {
+    if thresholds.iter().any(|t| !t.is_finite()) || thresholds.windows(2).any(|w| w[0] >= w[1]) {
+        return Err(Error::InvalidInput(
+            "the thresholds must be finite and strictly increasing".to_owned(),
+        ));
+    }
+    let areas = thresholds
+        .iter()
+        .map(|&t| area_above(grid, t))
+        .collect::<GResult<Vec<_>>>()?;
+    let mut res = Vec::with_capacity(thresholds.len());
+    for (i, area) in areas.iter().enumerate() {
+        let band = match areas.get(i + 1) {
+            Some(above) => area.difference(above)?,
+            None => Geom::clone(area),
+        };
+        res.push((thresholds[i], band.topology_preserve_simplify(0.)?));
+    }
+    Ok(res)
+}
@@ -0,0 +1,197 @@
+//! Regularization of the shapes digitized by hand or extracted from imagery, like building
+//! footprints.
+//!
+//! Available using the `v3_6_0` feature.
+
+use crate::{Geom, Geometry, GeometryTypes};
+use error::{Error, GResult};
+use functions::{coords_from_seq, map_coord_seqs, seq_from_coords};
+
+#[derive(Clone, Copy, PartialEq)]
+enum Axis {
+    X,
+    Y,
+}
+
+/// Returns the axis the edge `a`-`b` is aligned with, within `tolerance` (in degrees).
+fn edge_axis(a: &[f64; 3], b: &[f64; 3], tolerance: f64) -> Option<Axis> {
+    let (dx, dy) = (b[0] - a[0], b[1] - a[1]);
+    if dx == 0. && dy == 0. {
+        return None;
+    }
+    let angle = dy.atan2(dx).to_degrees().rem_euclid(180.);
+    if angle <= tolerance || angle >= 180. - tolerance {
+        Some(Axis::X)
+    } else if (angle - 90.).abs() <= tolerance {
+        Some(Axis::Y)
+    } else {
+        None
+    }
+}
+
+/// Squares `coords` (already rotated so that the dominant axes are X and Y): the consecutive
+/// edges aligned with the same axis are moved onto a single line, at the length-weighted mean of
+/// their positions, and their vertices are moved onto those lines.
+fn square(coords: &[[f64; 3]], closed: bool, tolerance: f64) -> Vec<[f64; 3]> {
+    // The closing vertex of a ring is added back at the end.
+    let nb_vertices = if closed {
+        coords.len().saturating_sub(1)
+    } else {
+        coords.len()
+    };
+    let nb_edges = if closed {
+        nb_vertices
+    } else {
+        nb_vertices.saturating_sub(1)
+    };
+    if nb_edges == 0 {
+        return coords.to_vec();
+    }
+    let vertex = |i: usize| &coords[i % nb_vertices];
+    let axes = (0..nb_edges)
+        .map(|i| edge_axis(vertex(i), vertex(i + 1), tolerance))
+        .collect::<Vec<_>>();
+
+    // Groups the consecutive edges aligned with the same axis. A run of a ring can wrap around
+    // its first vertex, so the runs start at the first edge following a change of axis.
+    let start = if closed {
+        (0..nb_edges)
+            .find(|&i| axes[i] != axes[(i + nb_edges - 1) % nb_edges])
+            .unwrap_or(0)
+    } else {
+        0
+    };
+    let mut runs = vec![0; nb_edges];
+    // (sum of the weighted positions, sum of the weights) of each run.
+    let mut sums: Vec<(f64, f64)> = Vec::new();
+    for k in 0..nb_edges {
+        let i = (start + k) % nb_edges;
+        let axis = match axes[i] {
+            Some(axis) => axis,
+            None => continue,
+        };
+        let previous = (i + nb_edges - 1) % nb_edges;
+        if k == 0 || axes[previous] != Some(axis) {
+            sums.push((0., 0.));
+        }
+        let (a, b) = (vertex(i), vertex(i + 1));
+        let length = (b[0] - a[0]).hypot(b[1] - a[1]);
+        let position = match axis {
+            Axis::X => (a[1] + b[1]) / 2.,
+            Axis::Y => (a[0] + b[0]) / 2.,
+        };
+        let run = sums.len() - 1;
+        sums[run].0 += position * length;
+        sums[run].1 += length;
+        runs[i] = run;
+    }
+
+    let mut res = Vec::with_capacity(coords.len());
+    for i in 0..nb_vertices {
+        let mut c = *vertex(i);
+        let previous = if closed || i > 0 {
+            Some((i + nb_edges - 1) % nb_edges)
+        } else {
+            None
+        };
+        let next = if i < nb_edges { Some(i) } else { None };
+        for e in previous.into_iter().chain(next) {
+            let (sum, weight) = sums.get(runs[e]).cloned().unwrap_or((0., 0.));
+            match axes[e] {
+                Some(Axis::X) => c[1] = sum / weight,
+                Some(Axis::Y) => c[0] = sum / weight,
+                None => {}
+            }
+        }
+        res.push(c);
+    }
+    if closed {
+        res.push(res[0]);
+    }
+    res
+}
+
+fn rotate(c: &[f64; 3], center: &[f64; 2], (sin, cos): (f64, f64)) -> [f64; 3] {
+    let (x, y) = (c[0] - center[0], c[1] - center[1]);
+    [
+        center[0] + x * cos - y * sin,
+        center[1] + x * sin + y * cos,
+        c[2],
+    ]
+}
+
+/// Squares the near-right angles of `geom` (typically a building footprint) and aligns its
+/// edges with its dominant axes.
+///
+/// The dominant axes are the sides of the [oriented bounding
+/// box](../trait.Geom.html#tymethod.oriented_bbox). The edges within `angle_tolerance` degrees
+/// (from `0` to `45` excluded) of one of them are made parallel to it, the consecutive edges
+/// aligned with the same axis being merged onto a single line, and the other edges are left
+/// alone (apart from their vertices shared with a squared edge). Every line string and ring of
+/// `geom` is squared, and its SRID is kept.
+///
+/// An error is returned if `geom` is valid but the squared geometry isn't, which can happen
+/// with a large tolerance.
+///
+/// # Example
+///
+/// ```
+/// use geos::{Geom, Geometry};
+/// use geos::regularize::orthogonalize;
+///
+/// let footprint = Geometry::new_from_wkt("POLYGON((0 0, 10 0.2, 10.1 5, 0.1 5.1, 0 0))")
+///                          .expect("Invalid geometry");
+/// let squared = orthogonalize(&footprint, 10.).expect("orthogonalize failed");
+///
+/// // All the angles are now right angles.
+/// let area = squared.area().expect("area failed");
+/// let rectangle = squared.minimum_rotated_rectangle().expect("minimum_rotated_rectangle failed");
+/// assert!((area - rectangle.area().unwrap()).abs() < 1e-9);
+/// assert!((area - footprint.area().unwrap()).abs() < 0.5);
+///
+/// assert!(orthogonalize(&footprint, 45.).is_err());
+///
+/// // Empty parts are left alone.
+/// let geom = Geometry::new_from_wkt("GEOMETRYCOLLECTION(POINT(0 0), LINEARRING EMPTY)")
+///                     .expect("Invalid geometry");
+/// assert!(orthogonalize(&geom, 10.).is_ok());
+/// ```
+pub fn orthogonalize<'a, G: Geom<'a>>(geom: &G, angle_tolerance: f64) -> GResult<Geometry<'a>> {
+    if !(0. ..45.).contains(&angle_tolerance) {
+        return Err(Error::InvalidInput(format!(
+            "the angle tolerance must be between 0 and 45 degrees, got {}",
+            angle_tolerance
+        )));
+    }
+    if geom.is_empty()? {
+        return Ok(Geom::clone(geom));
+    }
+    let (_, angle) = geom.oriented_bbox()?;
+    let angle = angle.to_radians();
+    let centroid = geom.get_centroid()?;
+    let center = [centroid.get_x()?, centroid.get_y()?];
+
+    let mut res = map_coord_seqs(geom, &mut |seq, geom_type| {
+        let closed = match geom_type {
+            GeometryTypes::LinearRing => true,
+            GeometryTypes::LineString => false,
+            _ => return Ok(seq.clone()),
+        };
+        let coords = coords_from_seq(seq)?
+            .iter()
+            .map(|c| rotate(c, &center, (-angle).sin_cos()))
+            .collect::<Vec<_>>();
+        let coords = square(&coords, closed, angle_tolerance)
+            .iter()
+            .map(|c| rotate(c, &center, angle.sin_cos()))
+            .collect::<Vec<_>>();
+        seq_from_coords(&coords, seq.dimensions()?)
+    })?;
+    if geom.is_valid() && !res.is_valid() {
+        return Err(Error::GenericError(
+            "orthogonalize created an invalid geometry".to_owned(),
+        ));
+    }
+    res.set_srid(geom.get_srid()?);
+    Ok(res)
+}
@@ -0,0 +1,416 @@
+//! [S2](https://s2geometry.io) cells, and the cells covering a geometry.
+//!
+//! Positions are longitude/latitude in degrees (EPSG:4326). The cells are converted to polygons
+//! to be tested against the geometries: their edges, which are geodesics, are approximated by a
+//! few straight segments. The polygons of the cells crossing the antimeridian are repeated on
+//! both of its sides, but geometries crossing it (with longitudes outside of `[-180, 180]`)
+//! aren't supported.
+//!
+//! This module is only available with the `s2` feature. H3 cells aren't supported.
+
+use crate::{CoordSeq, GResult, Geom, Geometry};
+use error::Error;
+
+/// Deepest level of the hierarchy, leaf cells being about 1cm wide.
+pub const MAX_LEVEL: u8 = 30;
+
+const FACE_SHIFT: u32 = 61;
+const MAX_SIZE: f64 = (1u64 << MAX_LEVEL) as f64;
+/// Number of points (including the first corner) used for each edge of a cell polygon.
+const EDGE_POINTS: usize = 8;
+
+const SWAP_MASK: usize = 1;
+const INVERT_MASK: usize = 2;
+/// Position along the Hilbert curve of the `(i << 1) | j` sub-cell, for each orientation.
+const IJ_TO_POS: [[u64; 4]; 4] = [[0, 1, 3, 2], [0, 3, 1, 2], [2, 3, 1, 0], [2, 1, 3, 0]];
+/// Inverse of `IJ_TO_POS`.
+const POS_TO_IJ: [[u32; 4]; 4] = [[0, 1, 3, 2], [0, 2, 3, 1], [3, 2, 0, 1], [3, 1, 0, 2]];
+const POS_TO_ORIENTATION: [usize; 4] = [SWAP_MASK, 0, 0, SWAP_MASK | INVERT_MASK];
+
+fn check_level(level: u8) -> GResult<()> {
+    if level > MAX_LEVEL {
+        return Err(Error::GenericError(format!(
+            "S2 level must be between 0 and {}, got {}",
+            MAX_LEVEL, level
+        )));
+    }
+    Ok(())
+}
+
+fn lsb_for_level(level: u8) -> u64 {
+    1 << (2 * u32::from(MAX_LEVEL - level))
+}
+
+/// Quadratic projection from the `[0, 1]` cell space to the `[-1, 1]` face space.
+fn st_to_uv(s: f64) -> f64 {
+    if s >= 0.5 {
+        (4. * s * s - 1.) / 3.
+    } else {
+        (1. - 4. * (1. - s) * (1. - s)) / 3.
+    }
+}
+
+fn uv_to_st(u: f64) -> f64 {
+    if u >= 0. {
+        0.5 * (1. + 3. * u).sqrt()
+    } else {
+        1. - 0.5 * (1. - 3. * u).sqrt()
+    }
+}
+
+fn face_uv_to_xyz(face: u8, u: f64, v: f64) -> [f64; 3] {
+    match face {
+        0 => [1., u, v],
+        1 => [-u, 1., v],
+        2 => [-u, -v, 1.],
+        3 => [-1., -v, -u],
+        4 => [v, -1., -u],
+        _ => [v, u, -1.],
+    }
+}
+
+fn xyz_to_face_uv(p: [f64; 3]) -> (u8, f64, f64) {
+    let [x, y, z] = p;
+    let axis = if x.abs() >= y.abs() && x.abs() >= z.abs() {
+        0
+    } else if y.abs() >= z.abs() {
+        1
+    } else {
+        2
+    };
+    let face = if p[axis] < 0. { axis + 3 } else { axis } as u8;
+    let (u, v) = match face {
+        0 => (y / x, z / x),
+        1 => (-x / y, z / y),
+        2 => (-x / z, -y / z),
+        3 => (z / x, y / x),
+        4 => (z / y, -x / y),
+        _ => (-y / z, -x / z),
+    };
+    (face, u, v)
+}
+
+fn xyz_to_lon_lat(p: [f64; 3]) -> (f64, f64) {
+    let [x, y, z] = p;
+    (
+        y.atan2(x).to_degrees(),
+        z.atan2((x * x + y * y).sqrt()).to_degrees(),
+    )
+}
+
+fn st_to_ij(s: f64) -> u32 {
+    (s * MAX_SIZE).floor().clamp(0., MAX_SIZE - 1.) as u32
+}
+
+/// Identifier of an S2 cell, at any level.
+///
+/// # Example
+///
+/// ```
+/// use geos::s2::CellId;
+///
+/// let leaf = CellId::from_lon_lat(0., 0., 30).expect("from_lon_lat failed");
+/// assert_eq!(leaf, CellId(0x1000_0000_0000_0001));
+/// assert_eq!(leaf.level(), 30);
+///
+/// let cell = leaf.parent(1).expect("parent failed");
+/// assert_eq!(cell.to_token(), "14");
+/// assert_eq!(CellId::from_token("14"), Ok(cell));
+/// assert_eq!(cell.children().len(), 4);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CellId(pub u64);
+
+impl CellId {
+    /// Returns the cell of the given `level` containing the `(lon, lat)` position.
+    pub fn from_lon_lat(lon: f64, lat: f64, level: u8) -> GResult<CellId> {
+        check_level(level)?;
+        if !(-180. ..=180.).contains(&lon) || !(-90. ..=90.).contains(&lat) {
+            return Err(Error::GenericError(format!(
+                "({}, {}) is not a valid longitude/latitude position",
+                lon, lat
+            )));
+        }
+        let (lon, lat) = (lon.to_radians(), lat.to_radians());
+        let p = [lat.cos() * lon.cos(), lat.cos() * lon.sin(), lat.sin()];
+        let (face, u, v) = xyz_to_face_uv(p);
+        let leaf = CellId::from_face_ij(face, st_to_ij(uv_to_st(u)), st_to_ij(uv_to_st(v)));
+        leaf.parent(level)
+    }
+
+    fn from_face_ij(face: u8, i: u32, j: u32) -> CellId {
+        let mut orientation = face as usize & SWAP_MASK;
+        let mut pos = 0u64;
+        for k in (0..u32::from(MAX_LEVEL)).rev() {
+            let ij = ((((i >> k) & 1) << 1) | ((j >> k) & 1)) as usize;
+            let p = IJ_TO_POS[orientation][ij];
+            pos = (pos << 2) | p;
+            orientation ^= POS_TO_ORIENTATION[p as usize];
+        }
+        CellId((u64::from(face) << FACE_SHIFT) | (pos << 1) | 1)
+    }
+
+    /// Parses the token of a cell, as returned by [`to_token`](#method.to_token).
+    pub fn from_token(token: &str) -> GResult<CellId> {
+        let id = if token.len() <= 16 {
+            u64::from_str_radix(token, 16)
+                .ok()
+                .map(|id| id << (4 * (16 - token.len())))
+        } else {
+            None
+        };
+        match id.map(CellId) {
+            Some(cell) if cell.is_valid() => Ok(cell),
+            _ => Err(Error::GenericError(format!(
+                "\"{}\" is not a valid S2 token",
+                token
+            ))),
+        }
+    }
+
+    /// Returns the compact hexadecimal representation of the cell id, without its trailing
+    /// zeros.
+    pub fn to_token(&self) -> String {
+        if self.0 == 0 {
+            return "X".to_owned();
+        }
+        let hex = format!("{:016x}", self.0);
+        hex.trim_end_matches('0').to_owned()
+    }
+
+    /// Returns `true` if the id is the one of an existing cell.
+    pub fn is_valid(&self) -> bool {
+        self.face() < 6 && self.lsb() & 0x1555_5555_5555_5555 != 0
+    }
+
+    /// Returns the face (between 0 and 5) of the cell.
+    pub fn face(&self) -> u8 {
+        (self.0 >> FACE_SHIFT) as u8
+    }
+
+    /// Returns the level of the cell, from 0 (a whole face) to [`MAX_LEVEL`].
+    pub fn level(&self) -> u8 {
+        MAX_LEVEL - (self.0.trailing_zeros() / 2) as u8
+    }
+
+    fn lsb(&self) -> u64 {
+        self.0 & self.0.wrapping_neg()
+    }
+
+    /// Returns the cell of the given (lower or equal) `level` containing this one.
+    pub fn parent(&self, level: u8) -> GResult<CellId> {
+        check_level(level)?;
+        if level > self.level() {
+            return Err(Error::GenericError(format!(
+                "a cell of level {} has no parent at level {}",
+                self.level(),
+                level
+            )));
+        }
+        let lsb = lsb_for_level(level);
+        Ok(CellId((self.0 & lsb.wrapping_neg()) | lsb))
+    }
+
+    /// Returns the four cells of the next level contained in this one, or nothing for a leaf
+    /// cell.
+    pub fn children(&self) -> Vec<CellId> {
+        let lsb = self.lsb();
+        if lsb == 1 {
+            return Vec::new();
+        }
+        let child_lsb = lsb >> 2;
+        (0..4)
+            .map(|k| CellId(self.0 - lsb + (2 * k + 1) * child_lsb))
+            .collect()
+    }
+
+    /// Returns `true` if `other` is this cell or one of its descendants.
+    pub fn contains(&self, other: &CellId) -> bool {
+        let lsb = self.lsb();
+        other.0 >= self.0 - (lsb - 1) && other.0 <= self.0 + (lsb - 1)
+    }
+
+    /// Returns the `(i, j)` coordinates of the lower corner of the cell on its face.
+    fn ij(&self) -> (u32, u32) {
+        let mut orientation = self.face() as usize & SWAP_MASK;
+        let (mut i, mut j) = (0, 0);
+        for k in 0..u32::from(self.level()) {
+            let p = ((self.0 >> (2 * (u32::from(MAX_LEVEL) - k) - 1)) & 3) as usize;
+            let ij = POS_TO_IJ[orientation][p];
+            i |= (ij >> 1) << (u32::from(MAX_LEVEL) - 1 - k);
+            j |= (ij & 1) << (u32::from(MAX_LEVEL) - 1 - k);
+            orientation ^= POS_TO_ORIENTATION[p];
+        }
+        (i, j)
+    }
+
+    /// Returns the polygon (or multi polygon, if it crosses the antimeridian) of the cell, in
+    /// longitude/latitude.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry};
+    /// use geos::s2::CellId;
+    ///
+    /// let cell = CellId::from_lon_lat(2.35, 48.85, 10).expect("from_lon_lat failed");
+    /// let polygon = cell.to_geometry().expect("to_geometry failed");
+    /// let point = Geometry::new_from_wkt("POINT(2.35 48.85)").expect("Invalid geometry");
+    /// assert_eq!(polygon.contains(&point), Ok(true));
+    /// ```
+    pub fn to_geometry(&self) -> GResult<Geometry<'static>> {
+        let (i, j) = self.ij();
+        let size = f64::from(1u32 << (MAX_LEVEL - self.level()));
+        let (s0, t0) = (f64::from(i) / MAX_SIZE, f64::from(j) / MAX_SIZE);
+        let (s1, t1) = (s0 + size / MAX_SIZE, t0 + size / MAX_SIZE);
+        let corners = [(s0, t0), (s1, t0), (s1, t1), (s0, t1), (s0, t0)];
+
+        let mut points = Vec::with_capacity(4 * EDGE_POINTS);
+        for edge in corners.windows(2) {
+            let ((sa, ta), (sb, tb)) = (edge[0], edge[1]);
+            for k in 0..EDGE_POINTS {
+                let f = k as f64 / EDGE_POINTS as f64;
+                let (s, t) = (sa + (sb - sa) * f, ta + (tb - ta) * f);
+                points.push(xyz_to_lon_lat(face_uv_to_xyz(
+                    self.face(),
+                    st_to_uv(s),
+                    st_to_uv(t),
+                )));
+            }
+        }
+        let ring = unwrap_ring(&points);
+
+        let (min_lon, max_lon) = ring.iter().fold((180., -180.), |(min, max), c| {
+            (f64::min(min, c[0]), f64::max(max, c[0]))
+        });
+        let mut polygons = vec![create_polygon(&ring, 0.)?];
+        if max_lon > 180. {
+            polygons.push(create_polygon(&ring, -360.)?);
+        }
+        if min_lon < -180. {
+            polygons.push(create_polygon(&ring, 360.)?);
+        }
+        if polygons.len() == 1 {
+            Ok(polygons.remove(0))
+        } else {
+            Geometry::create_multipolygon(polygons)
+        }
+    }
+}
+
+fn is_pole(lat: f64) -> bool {
+    lat.abs() > 90. - 1e-9
+}
+
+/// Makes the longitudes of the ring continuous (possibly going out of `[-180, 180]`) and
+/// replaces its poles, which have no longitude, by segments along the pole.
+fn unwrap_ring(points: &[(f64, f64)]) -> Vec<[f64; 2]> {
+    let mut lons: Vec<Option<f64>> = Vec::with_capacity(points.len());
+    let mut previous: Option<f64> = None;
+    for &(lon, lat) in points {
+        if is_pole(lat) {
+            lons.push(None);
+            continue;
+        }
+        let lon = match previous {
+            Some(prev) if lon - prev > 180. => lon - 360.,
+            Some(prev) if lon - prev < -180. => lon + 360.,
+            _ => lon,
+        };
+        previous = Some(lon);
+        lons.push(Some(lon));
+    }
+    let n = points.len();
+    let mut ring = Vec::with_capacity(n + 2);
+    for (k, &(_, lat)) in points.iter().enumerate() {
+        match lons[k] {
+            Some(lon) => ring.push([lon, lat]),
+            None => {
+                let lat = lat.signum() * 90.;
+                if let Some(lon) = lons[(k + n - 1) % n] {
+                    ring.push([lon, lat]);
+                }
+                if let Some(lon) = lons[(k + 1) % n] {
+                    ring.push([lon, lat]);
+                }
+            }
+        }
+    }
+    let first = ring[0];
+    ring.push(first);
+    ring
+}
+
+fn create_polygon(ring: &[[f64; 2]], shift: f64) -> GResult<Geometry<'static>> {
+    let coords = ring
+        .iter()
+        .map(|c| [c[0] + shift, c[1]])
+        .collect::<Vec<_>>();
+    let exterior = Geometry::create_linear_ring(CoordSeq::new_from_vec(&coords)?)?;
+    Geometry::create_polygon(exterior, vec![])
+}
+
+/// Returns the sorted cells of the given `level` intersecting `geom`.
+///
+/// The search starts from the cells of level 1 and only subdivides the ones intersecting `geom`.
+/// The cells fully contained in `geom` aren't tested further: all their descendants are added.
+///
+/// # Example
+///
+/// ```
+/// use geos::{Geom, Geometry};
+/// use geos::s2::{covering, CellId};
+///
+/// let point = Geometry::new_from_wkt("POINT(2.35 48.85)").expect("Invalid geometry");
+/// assert_eq!(covering(&point, 12),
+///            Ok(vec![CellId::from_lon_lat(2.35, 48.85, 12).expect("from_lon_lat failed")]));
+///
+/// let area = Geometry::new_from_wkt("POLYGON((2 48, 3 48, 3 49, 2 49, 2 48))")
+///                     .expect("Invalid geometry");
+/// let cells = covering(&area, 6).expect("covering failed");
+/// assert!(!cells.is_empty());
+/// for cell in &cells {
+///     assert_eq!(cell.to_geometry().and_then(|c| c.intersects(&area)), Ok(true));
+/// }
+/// assert!(cells.contains(&CellId::from_lon_lat(2.5, 48.5, 6).expect("from_lon_lat failed")));
+/// ```
+pub fn covering<'a, G: Geom<'a>>(geom: &G, level: u8) -> GResult<Vec<CellId>> {
+    check_level(level)?;
+    if geom.is_empty()? {
+        return Ok(Vec::new());
+    }
+    let prepared = geom.to_prepared_geom()?;
+    let search_level = level.max(1);
+    let mut res = Vec::new();
+    let mut stack = (0..6)
+        .flat_map(|face| CellId((face << FACE_SHIFT) | (1 << 60)).children())
+        .collect::<Vec<_>>();
+    while let Some(cell) = stack.pop() {
+        let polygon = cell.to_geometry()?;
+        if !prepared.intersects(&polygon)? {
+            continue;
+        }
+        if cell.level() == search_level {
+            res.push(cell);
+        } else if prepared.contains(&polygon)? {
+            let lsb = cell.lsb();
+            let child_lsb = lsb_for_level(search_level);
+            let mut id = cell.0 - lsb + child_lsb;
+            while id < cell.0 + lsb {
+                res.push(CellId(id));
+                id += 2 * child_lsb;
+            }
+        } else {
+            stack.extend(cell.children());
+        }
+    }
+    if level != search_level {
+        res = res
+            .iter()
+            .map(|cell| cell.parent(level))
+            .collect::<GResult<_>>()?;
+    }
+    res.sort_unstable();
+    res.dedup();
+    Ok(res)
+}
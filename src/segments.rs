@@ -0,0 +1,82 @@
+use crate::ContextHandle;
+use error::{Error, GResult};
+use geos_sys::*;
+
+/// Iterator over the segments of a `LineString` or a `LinearRing`, returned by
+/// [`Geom::segments`](trait.Geom.html#tymethod.segments).
+///
+/// It borrows the coordinate sequence of the geometry, and reads each coordinate only once, as
+/// the iteration goes.
+pub struct Segments<'a, 'g> {
+    context: &'g ContextHandle<'a>,
+    // Owned by the geometry, which is borrowed for `'g`.
+    seq: *const GEOSCoordSequence,
+    size: usize,
+    next: usize,
+    previous: GResult<(f64, f64)>,
+}
+
+impl<'a, 'g> Segments<'a, 'g> {
+    /// `seq` must be null (for an empty geometry) or the coordinate sequence of a geometry
+    /// borrowed for `'g`.
+    pub(crate) unsafe fn new(
+        context: &'g ContextHandle<'a>,
+        seq: *const GEOSCoordSequence,
+    ) -> GResult<Segments<'a, 'g>> {
+        let mut size = 0;
+        if !seq.is_null() && GEOSCoordSeq_getSize_r(context.as_raw(), seq, &mut size) == 0 {
+            return Err(Error::Operation {
+                op: "segments",
+                message: context.get_last_error().unwrap_or_default(),
+            });
+        }
+        let mut segments = Segments {
+            context,
+            seq,
+            size: size as _,
+            next: 1,
+            previous: Ok((0., 0.)),
+        };
+        if segments.size > 1 {
+            segments.previous = segments.read(0);
+        }
+        Ok(segments)
+    }
+
+    fn read(&self, index: usize) -> GResult<(f64, f64)> {
+        let (mut x, mut y) = (0., 0.);
+        let ok = unsafe {
+            GEOSCoordSeq_getX_r(self.context.as_raw(), self.seq, index as _, &mut x) != 0
+                && GEOSCoordSeq_getY_r(self.context.as_raw(), self.seq, index as _, &mut y) != 0
+        };
+        if ok {
+            Ok((x, y))
+        } else {
+            Err(Error::Operation {
+                op: "segments",
+                message: self.context.get_last_error().unwrap_or_default(),
+            })
+        }
+    }
+}
+
+impl<'a, 'g> Iterator for Segments<'a, 'g> {
+    type Item = GResult<((f64, f64), (f64, f64))>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next >= self.size {
+            return None;
+        }
+        let current = self.read(self.next);
+        let previous = std::mem::replace(&mut self.previous, current.clone());
+        self.next += 1;
+        Some(previous.and_then(|previous| current.map(|current| (previous, current))))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.size.saturating_sub(self.next);
+        (len, Some(len))
+    }
+}
+
+impl<'a, 'g> ExactSizeIterator for Segments<'a, 'g> {}
@@ -0,0 +1,85 @@
+//! Shape descriptors, measuring how compact, elongated, rectangular or convex polygons are.
+//!
+//! Available using the `v3_6_0` feature.
+
+use crate::{Geom, GeometryTypes};
+use error::{Error, GResult};
+use functions::coords_from_seq;
+use std::f64::consts::PI;
+
+/// Descriptors of a polygonal geometry, all of them between `0` and `1`. Returned by
+/// [`descriptors`](fn.descriptors.html).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShapeDescriptors {
+    /// Polsby-Popper compactness: `4π · area / perimeter²`, `1` for a disc.
+    pub compactness: f64,
+    /// `1 - width / length` of the minimum rotated rectangle, `0` for a square-ish shape.
+    pub elongation: f64,
+    /// Ratio between the area and the one of the minimum rotated rectangle, `1` for a rectangle.
+    pub rectangularity: f64,
+    /// Ratio between the area and the one of the convex hull (also called solidity), `1` for a
+    /// convex shape.
+    pub convexity: f64,
+}
+
+/// Computes the [`ShapeDescriptors`](struct.ShapeDescriptors.html) of a polygonal geometry with
+/// a non-zero area.
+///
+/// # Example
+///
+/// ```
+/// use geos::Geometry;
+/// use geos::shape::descriptors;
+///
+/// let rectangle = Geometry::new_from_wkt("POLYGON((0 0, 4 0, 4 1, 0 1, 0 0))")
+///                          .expect("Invalid geometry");
+/// let d = descriptors(&rectangle).expect("descriptors failed");
+/// assert!((d.compactness - 4. * std::f64::consts::PI * 4. / 100.).abs() < 1e-9);
+/// assert!((d.elongation - 0.75).abs() < 1e-9);
+/// assert!((d.rectangularity - 1.).abs() < 1e-9);
+/// assert!((d.convexity - 1.).abs() < 1e-9);
+///
+/// let l_shape = Geometry::new_from_wkt("POLYGON((0 0, 2 0, 2 1, 1 1, 1 2, 0 2, 0 0))")
+///                        .expect("Invalid geometry");
+/// let d = descriptors(&l_shape).expect("descriptors failed");
+/// assert!((d.convexity - 3. / 3.5).abs() < 1e-9);
+///
+/// let line = Geometry::new_from_wkt("LINESTRING(0 0, 1 1)").expect("Invalid geometry");
+/// assert!(descriptors(&line).is_err());
+/// ```
+pub fn descriptors<'a, G: Geom<'a>>(geom: &G) -> GResult<ShapeDescriptors> {
+    match geom.geometry_type() {
+        GeometryTypes::Polygon | GeometryTypes::MultiPolygon => {}
+        geom_type => {
+            return Err(Error::GenericError(format!(
+                "shape descriptors need a polygonal geometry, got a {:?}",
+                geom_type
+            )))
+        }
+    }
+    let area = geom.area()?;
+    if area <= 0. {
+        return Err(Error::GenericError(
+            "shape descriptors need a non-zero area".to_owned(),
+        ));
+    }
+
+    let rectangle = geom.minimum_rotated_rectangle()?;
+    let corners = coords_from_seq(&rectangle.get_exterior_ring()?.get_coord_seq()?)?;
+    let side =
+        |i: usize| (corners[i + 1][0] - corners[i][0]).hypot(corners[i + 1][1] - corners[i][1]);
+    let (a, b) = (side(0), side(1));
+    let elongation = if a.max(b) > 0. {
+        1. - a.min(b) / a.max(b)
+    } else {
+        0.
+    };
+
+    let perimeter = geom.length()?;
+    Ok(ShapeDescriptors {
+        compactness: 4. * PI * area / (perimeter * perimeter),
+        elongation,
+        rectangularity: area / rectangle.area()?,
+        convexity: area / geom.convex_hull()?.area()?,
+    })
+}
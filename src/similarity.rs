@@ -0,0 +1,178 @@
+//! Normalized similarity scores between line geometries, mostly useful for map-matching and
+//! conflation.
+
+use crate::Geom;
+use error::{Error, GResult};
+
+/// Scores how similar two lines are, from `0` (nothing in common) to `1` (identical).
+///
+/// The score is a weighted average of:
+///
+/// * a Hausdorff distance score,
+/// * a Frechet distance score (only available using the `v3_7_0` feature),
+/// * the ratio between the shortest and the longest length.
+///
+/// A distance `d` is turned into a score with `1 / (1 + d / distance_scale)`, so a distance equal
+/// to the scale gives `0.5`.
+///
+/// # Example
+///
+/// ```
+/// use geos::Geometry;
+/// use geos::similarity::LineSimilarity;
+///
+/// let reference = Geometry::new_from_wkt("LINESTRING (0 0, 10 0)").expect("Invalid geometry");
+/// let close = Geometry::new_from_wkt("LINESTRING (0 1, 10 1)").expect("Invalid geometry");
+/// let far = Geometry::new_from_wkt("LINESTRING (0 5, 10 5)").expect("Invalid geometry");
+///
+/// let similarity = LineSimilarity::builder()
+///                                 .distance_scale(2.)
+///                                 .length_weight(0.)
+///                                 .build()
+///                                 .expect("invalid configuration");
+///
+/// assert_eq!(similarity.score(&reference, &reference), Ok(1.));
+/// let close_score = similarity.score(&reference, &close).expect("score failed");
+/// let far_score = similarity.score(&reference, &far).expect("score failed");
+/// assert!(close_score > far_score);
+/// assert!((close_score - 2. / 3.).abs() < 1e-9);
+///
+/// assert!(LineSimilarity::builder().length_weight(f64::NAN).build().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineSimilarity {
+    hausdorff_weight: f64,
+    frechet_weight: f64,
+    length_weight: f64,
+    distance_scale: f64,
+    densify_fraction: Option<f64>,
+}
+
+impl LineSimilarity {
+    /// Returns a builder with the default configuration: all the measures have a weight of `1`,
+    /// the distance scale is `1` and no densification is done.
+    pub fn builder() -> LineSimilarityBuilder {
+        LineSimilarityBuilder {
+            inner: LineSimilarity {
+                hausdorff_weight: 1.,
+                frechet_weight: if cfg!(any(feature = "v3_7_0", feature = "dox")) {
+                    1.
+                } else {
+                    0.
+                },
+                length_weight: 1.,
+                distance_scale: 1.,
+                densify_fraction: None,
+            },
+        }
+    }
+
+    fn distance_score(&self, distance: f64) -> f64 {
+        1. / (1. + distance / self.distance_scale)
+    }
+
+    /// Returns the similarity score between `a` and `b`, in the `[0, 1]` range.
+    pub fn score<'a, 'b, A: Geom<'a>, B: Geom<'b>>(&self, a: &A, b: &B) -> GResult<f64> {
+        let mut score = 0.;
+
+        if self.hausdorff_weight > 0. {
+            let distance = match self.densify_fraction {
+                Some(fraction) => a.hausdorff_distance_densify(b, fraction)?,
+                None => a.hausdorff_distance(b)?,
+            };
+            score += self.hausdorff_weight * self.distance_score(distance);
+        }
+        #[cfg(any(feature = "v3_7_0", feature = "dox"))]
+        {
+            if self.frechet_weight > 0. {
+                let distance = match self.densify_fraction {
+                    Some(fraction) => a.frechet_distance_densify(b, fraction)?,
+                    None => a.frechet_distance(b)?,
+                };
+                score += self.frechet_weight * self.distance_score(distance);
+            }
+        }
+        if self.length_weight > 0. {
+            let (len_a, len_b) = (a.length()?, b.length()?);
+            let ratio = if len_a.max(len_b) > 0. {
+                len_a.min(len_b) / len_a.max(len_b)
+            } else {
+                1.
+            };
+            score += self.length_weight * ratio;
+        }
+        Ok(score / (self.hausdorff_weight + self.frechet_weight + self.length_weight))
+    }
+}
+
+/// Builder for [`LineSimilarity`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineSimilarityBuilder {
+    inner: LineSimilarity,
+}
+
+impl LineSimilarityBuilder {
+    /// Sets the weight of the Hausdorff distance score.
+    pub fn hausdorff_weight(mut self, weight: f64) -> LineSimilarityBuilder {
+        self.inner.hausdorff_weight = weight;
+        self
+    }
+
+    /// Sets the weight of the Frechet distance score.
+    ///
+    /// Available using the `v3_7_0` feature.
+    #[cfg(any(feature = "v3_7_0", feature = "dox"))]
+    pub fn frechet_weight(mut self, weight: f64) -> LineSimilarityBuilder {
+        self.inner.frechet_weight = weight;
+        self
+    }
+
+    /// Sets the weight of the length ratio.
+    pub fn length_weight(mut self, weight: f64) -> LineSimilarityBuilder {
+        self.inner.length_weight = weight;
+        self
+    }
+
+    /// Sets the distance giving a distance score of `0.5`. It should be of the order of the
+    /// tolerated matching error, in the units of the geometries.
+    pub fn distance_scale(mut self, scale: f64) -> LineSimilarityBuilder {
+        self.inner.distance_scale = scale;
+        self
+    }
+
+    /// Densifies the lines with the given fraction before computing the distances (see
+    /// [`Geom::hausdorff_distance_densify`]).
+    pub fn densify_fraction(mut self, fraction: f64) -> LineSimilarityBuilder {
+        self.inner.densify_fraction = Some(fraction);
+        self
+    }
+
+    /// Checks the configuration and returns the corresponding [`LineSimilarity`].
+    pub fn build(self) -> GResult<LineSimilarity> {
+        let s = self.inner;
+        let weights = [s.hausdorff_weight, s.frechet_weight, s.length_weight];
+        if weights.iter().any(|w| !w.is_finite() || *w < 0.) {
+            return Err(Error::GenericError(
+                "weights must be finite and can't be negative".to_owned(),
+            ));
+        }
+        if s.hausdorff_weight + s.frechet_weight + s.length_weight <= 0. {
+            return Err(Error::GenericError(
+                "at least one weight must be positive".to_owned(),
+            ));
+        }
+        if s.distance_scale.is_nan() || s.distance_scale <= 0. {
+            return Err(Error::GenericError(
+                "distance scale must be positive".to_owned(),
+            ));
+        }
+        if let Some(fraction) = s.densify_fraction {
+            if fraction.is_nan() || fraction <= 0. || fraction > 1. {
+                return Err(Error::GenericError(
+                    "densify fraction must be in the (0, 1] range".to_owned(),
+                ));
+            }
+        }
+        Ok(s)
+    }
+}
@@ -23,12 +23,12 @@ pub struct STRtree<'a, I> {
 
 impl<'a, I> STRtree<'a, I> {
     pub fn with_capacity(node_capacity: usize) -> GResult<STRtree<'a, I>> {
-        match ContextHandle::init_e(Some("STRtree::with_capacity")) {
+        match ContextHandle::init_default("STRtree::with_capacity") {
             Ok(context_handle) => unsafe {
                 let ptr = GEOSSTRtree_create_r(context_handle.as_raw(), node_capacity);
                 Ok(STRtree {
                     ptr: PtrWrap(ptr),
-                    context: Arc::new(context_handle),
+                    context: context_handle,
                     item_type: PhantomData,
                 })
             },
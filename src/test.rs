@@ -1,7 +1,9 @@
 #[cfg(test)]
 mod test {
-    use crate::{Geom, Geometry, PreparedGeometry};
+    use crate::{wkb_byte_order, ByteOrder, Geom, Geometry, PreparedGeometry, WKBReader};
     use enums::GeometryTypes;
+    use std::fs;
+    use std::path::PathBuf;
 
     #[test]
     fn test_relationship() {
@@ -179,6 +181,159 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_read_mixed_endian_wkb() {
+        // GEOMETRYCOLLECTION (POINT (1 2), LINESTRING (0 0, 3 4)), with a big endian collection
+        // and line string, and a little endian point.
+        let mut wkb = vec![0u8];
+        wkb.extend_from_slice(&7u32.to_be_bytes());
+        wkb.extend_from_slice(&2u32.to_be_bytes());
+        wkb.push(1);
+        wkb.extend_from_slice(&1u32.to_le_bytes());
+        for v in &[1f64, 2.] {
+            wkb.extend_from_slice(&v.to_le_bytes());
+        }
+        wkb.push(0);
+        wkb.extend_from_slice(&2u32.to_be_bytes());
+        wkb.extend_from_slice(&2u32.to_be_bytes());
+        for v in &[0f64, 0., 3., 4.] {
+            wkb.extend_from_slice(&v.to_be_bytes());
+        }
+
+        assert_eq!(wkb_byte_order(&wkb), Ok(ByteOrder::BigEndian));
+        let expected = "GEOMETRYCOLLECTION (POINT (1 2), LINESTRING (0 0, 3 4))";
+        let mut reader = WKBReader::new().unwrap();
+        assert_eq!(
+            reader.read_wkb(&wkb).unwrap().to_wkt_precision(0),
+            Ok(expected.to_owned()),
+        );
+        assert_eq!(
+            Geometry::new_from_wkb(&wkb).unwrap().to_wkt_precision(0),
+            Ok(expected.to_owned()),
+        );
+        // An unknown geometry type in the nested line string header.
+        wkb[34] = 42;
+        assert!(reader.read_wkb(&wkb).is_err());
+    }
+
+    #[test]
+    fn test_only_reentrant_geos_calls() {
+        // The non `_r` functions use the global GEOS context, ignoring the handlers set on ours.
+        let allowed = [
+            // It has no reentrant variant, and only returns a static string.
+            "GEOSversion",
+            // GEOS only provides a process-wide interrupt mechanism, used by `Deadline`.
+            "GEOS_interruptRequest",
+            "GEOS_interruptRegisterCallback",
+        ];
+        let mut dirs = vec![PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/src"))];
+        let mut nb_files = 0;
+        while let Some(dir) = dirs.pop() {
+            for entry in fs::read_dir(&dir).unwrap() {
+                let path = entry.unwrap().path();
+                if path.is_dir() {
+                    dirs.push(path);
+                    continue;
+                }
+                if path.extension().and_then(|e| e.to_str()) != Some("rs") {
+                    continue;
+                }
+                nb_files += 1;
+                let source = fs::read_to_string(&path).unwrap();
+                for (pos, _) in source.match_indices("GEOS") {
+                    let name = source[pos..]
+                        .split(|c: char| !c.is_alphanumeric() && c != '_')
+                        .next()
+                        .unwrap();
+                    let is_call = source[pos + name.len()..].starts_with('(');
+                    assert!(
+                        !is_call || name.ends_with("_r") || allowed.contains(&name),
+                        "{} calls the non reentrant {}",
+                        path.display(),
+                        name
+                    );
+                }
+            }
+        }
+        assert!(nb_files > 10, "the sources weren't found");
+    }
+
+    #[test]
+    fn test_is_valid_cached_reset() {
+        // A self-intersecting "bow tie".
+        let mut geom = Geometry::new_from_wkt("POLYGON((0 0, 2 2, 2 0, 0 2, 0 0))").unwrap();
+        assert!(!geom.is_valid_cached());
+        // Swapping the two right corners turns it into a square.
+        geom.edit_coords(|coord| {
+            if coord == [2., 2.] {
+                coord[1] = 0.;
+            } else if coord == [2., 0.] {
+                coord[1] = 2.;
+            }
+        })
+        .unwrap();
+        assert!(geom.is_valid());
+        assert!(geom.is_valid_cached());
+    }
+
+    #[test]
+    fn test_geom_macro() {
+        let line = geom!("LINESTRING(0 0, {} 0)", 3);
+        assert_eq!(line.length(), Ok(3.));
+        assert!(line.equals(&geom!("LINESTRING(0 0, 3 0)")).unwrap());
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid WKT")]
+    fn test_geom_macro_invalid() {
+        geom!("LINESTRING(0 0,");
+    }
+
+    #[test]
+    fn test_empty_check() {
+        use crate::{ContextHandle, EmptyCheck, Error};
+        use std::sync::Arc;
+
+        let square = "POLYGON((0 0, 1 0, 1 1, 0 1, 0 0))";
+        // By default, GEOS handles the empty geometries.
+        let empty = Geometry::new_from_wkt("POLYGON EMPTY").unwrap();
+        let other = Geometry::new_from_wkt(square).unwrap();
+        assert!(empty.buffer(1., 8).unwrap().is_empty().unwrap());
+        assert!(other.intersection(&empty).unwrap().is_empty().unwrap());
+        assert!(empty.convex_hull().unwrap().is_empty().unwrap());
+        assert!(empty.get_centroid().unwrap().is_empty().unwrap());
+        assert!(empty.unary_union().unwrap().is_empty().unwrap());
+
+        let context = ContextHandle::builder()
+            .empty_check(EmptyCheck::Error)
+            .build()
+            .unwrap();
+        assert_eq!(context.get_empty_check(), EmptyCheck::Error);
+        let context = Arc::new(context);
+        let empty =
+            Geometry::new_from_wkt_with_context("POLYGON EMPTY", Arc::clone(&context)).unwrap();
+        let other = Geometry::new_from_wkt_with_context(square, context).unwrap();
+        let is_empty_error = |res: Result<Geometry, Error>, op: &str| {
+            assert_eq!(res.err(), Some(Error::EmptyGeometry(op.to_owned())));
+        };
+        is_empty_error(empty.buffer(1., 8), "buffer");
+        is_empty_error(empty.envelope(), "envelope");
+        is_empty_error(empty.boundary(), "boundary");
+        is_empty_error(empty.simplify(1.), "simplify");
+        is_empty_error(other.union(&empty), "union");
+        is_empty_error(other.difference(&empty), "difference");
+        is_empty_error(empty.sym_difference(&other), "sym_difference");
+        assert_eq!(
+            other.distance(&empty),
+            Err(Error::EmptyGeometry("distance".to_owned()))
+        );
+        // Only the operations on empty geometries fail.
+        assert_eq!(other.buffer(0., 8).unwrap().area(), Ok(1.));
+        // The check of the context of the geometry the operation is called on is used.
+        let default_context = Geometry::new_from_wkt(square).unwrap();
+        assert!(default_context.intersection(&empty).is_ok());
+    }
+
     fn assert_almost_eq(a: f64, b: f64) {
         let f: f64 = a / b;
         assert!(f < 1.0001);
@@ -0,0 +1,38 @@
+//! Helpers to compare geometries in tests.
+
+use crate::{GResult, Geom, Geometry};
+
+/// Returns `true` if the two WKT strings describe the same geometry once their X and Y values are
+/// rounded to `decimal_places` decimals.
+///
+/// Both geometries are canonicalized (see [`Geom::canonicalize`]) before being compared vertex by
+/// vertex, so the order of the parts, the orientation of the rings and their starting point don't
+/// matter, but the number of vertices does. Z values are ignored. It's meant to compare the
+/// output of different GEOS versions, which may differ in the last decimals. An error is
+/// returned if `decimal_places` is greater than 15.
+///
+/// # Example
+///
+/// ```
+/// use geos::testing::wkt_approx_eq;
+///
+/// assert_eq!(wkt_approx_eq("MULTIPOINT (0.3333333 1, 2 3)",
+///                          "MULTIPOINT ((2 3), (0.33333333333 1))",
+///                          6),
+///            Ok(true));
+/// assert_eq!(wkt_approx_eq("POLYGON ((0 0, 1 0, 1 1, 0 0))",
+///                          "POLYGON ((1 1, 0 0, 1.0000001 0, 1 1))",
+///                          3),
+///            Ok(true));
+/// assert_eq!(wkt_approx_eq("POINT (0.33 1)", "POINT (0.34 1)", 2), Ok(false));
+/// assert!(wkt_approx_eq("POINT (0.33 1)", "POINT (0.34", 2).is_err());
+/// assert!(wkt_approx_eq("POINT (0.33 1)", "POINT (0.33 1)", 16).is_err());
+/// ```
+pub fn wkt_approx_eq(a_wkt: &str, b_wkt: &str, decimal_places: u32) -> GResult<bool> {
+    let a = Geometry::new_from_wkt(a_wkt)?.canonicalize(decimal_places)?;
+    let b = Geometry::new_from_wkt(b_wkt)?.canonicalize(decimal_places)?;
+    if a.geometry_type() != b.geometry_type() || a.is_empty()? != b.is_empty()? {
+        return Ok(false);
+    }
+    a.equals_exact(&b, 0.)
+}
@@ -0,0 +1,135 @@
+use crate::{Geom, Geometry};
+use error::GResult;
+use functions::{coords_from_seq, map_coord_seqs, seq_from_coords, visit_coord_seqs};
+use libc::c_int;
+use std::hash::{Hash, Hasher};
+
+/// Wrapper comparing geometries up to a tolerance, so they can be used as keys in a `HashSet`
+/// or a `HashMap`.
+///
+/// Two wrapped geometries are considered equal when they have the same structure and when, once
+/// normalized, their coordinates snap to the same cells of a grid whose size is the tolerance
+/// (given to [`new`](#method.new), which snaps the geometry once and for all). Both sides are
+/// expected to use the same tolerance. A tolerance lower or equal to `0` means exact equality.
+///
+/// Note that, as with any grid snapping, two coordinates closer than the tolerance can still
+/// end up in two different cells.
+///
+/// This isn't a `ToleranceEq(geom, tolerance)` tuple struct with public fields: snapping can
+/// fail, and `PartialEq` and `Hash` can't report an error, so the snapped key is computed once
+/// in [`new`](#method.new). Keeping the fields private also makes sure the geometry can't be
+/// modified after the key was computed, which would break the `HashSet` it's stored in.
+///
+/// # Example
+///
+/// ```
+/// use geos::{Geometry, ToleranceEq};
+/// use std::collections::HashSet;
+///
+/// let a = Geometry::new_from_wkt("POLYGON((0 0, 1 0, 1 1, 0 0))").expect("Invalid geometry");
+/// let b = Geometry::new_from_wkt("POLYGON((1.0001 0, 1 1, 0 0, 1.0001 0))")
+///                  .expect("Invalid geometry");
+/// let c = Geometry::new_from_wkt("POLYGON((0 0, 2 0, 2 2, 0 0))").expect("Invalid geometry");
+///
+/// let mut set = HashSet::new();
+/// assert!(set.insert(ToleranceEq::new(a, 0.01).expect("snapping failed")));
+/// assert!(!set.insert(ToleranceEq::new(b, 0.01).expect("snapping failed")));
+/// assert!(set.insert(ToleranceEq::new(c, 0.01).expect("snapping failed")));
+/// assert_eq!(set.len(), 2);
+/// ```
+pub struct ToleranceEq<'a> {
+    geom: Geometry<'a>,
+    tolerance: f64,
+    key: Vec<i64>,
+}
+
+fn cell(value: f64, tolerance: f64) -> i64 {
+    if tolerance > 0. {
+        (value / tolerance).round() as i64
+    } else {
+        // Adding 0 turns -0 into 0.
+        (value + 0.).to_bits() as i64
+    }
+}
+
+fn snap(value: f64, tolerance: f64) -> f64 {
+    if tolerance > 0. {
+        (value / tolerance).round() * tolerance
+    } else {
+        value
+    }
+}
+
+/// Returns the snapped coordinates of the normalized geometry, along with its structure.
+fn key(geom: &Geometry, tolerance: f64) -> GResult<Vec<i64>> {
+    let mut snapped = map_coord_seqs(geom, &mut |seq, _| {
+        let mut coords = coords_from_seq(seq)?;
+        for coord in coords.iter_mut() {
+            for value in coord.iter_mut() {
+                *value = snap(*value, tolerance);
+            }
+        }
+        seq_from_coords(&coords, seq.dimensions()?)
+    })?;
+    snapped.normalize()?;
+
+    let root_type: c_int = snapped.geometry_type().into();
+    let mut key = vec![root_type as i64, snapped.get_num_geometries()? as i64];
+    visit_coord_seqs(&snapped, &mut |seq, geom_type| {
+        let coords = coords_from_seq(seq)?;
+        let geom_type: c_int = geom_type.into();
+        key.push(geom_type as i64);
+        key.push(coords.len() as i64);
+        for coord in coords {
+            key.push(cell(coord[0], tolerance));
+            key.push(cell(coord[1], tolerance));
+            if !coord[2].is_nan() {
+                key.push(cell(coord[2], tolerance));
+            }
+        }
+        Ok(())
+    })?;
+    Ok(key)
+}
+
+impl<'a> ToleranceEq<'a> {
+    /// Wraps `geom`, snapping it once to the grid of the given `tolerance`. An error is returned
+    /// if it can't be snapped (like a curved geometry).
+    pub fn new(geom: Geometry<'a>, tolerance: f64) -> GResult<ToleranceEq<'a>> {
+        let key = key(&geom, tolerance)?;
+        Ok(ToleranceEq {
+            geom,
+            tolerance,
+            key,
+        })
+    }
+
+    /// Returns the wrapped geometry (not snapped).
+    pub fn geometry(&self) -> &Geometry<'a> {
+        &self.geom
+    }
+
+    /// Returns the tolerance.
+    pub fn tolerance(&self) -> f64 {
+        self.tolerance
+    }
+
+    /// Returns the wrapped geometry.
+    pub fn into_inner(self) -> Geometry<'a> {
+        self.geom
+    }
+}
+
+impl<'a, 'b> PartialEq<ToleranceEq<'b>> for ToleranceEq<'a> {
+    fn eq(&self, other: &ToleranceEq<'b>) -> bool {
+        self.key == other.key
+    }
+}
+
+impl<'a> Eq for ToleranceEq<'a> {}
+
+impl<'a> Hash for ToleranceEq<'a> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key.hash(state);
+    }
+}
@@ -0,0 +1,127 @@
+use crate::{GResult, Geom, GeometryTypes};
+use functions::{coords_from_seq, unknown_type, unsupported_curve};
+
+/// Callbacks called by [`Geom::walk`](trait.Geom.html#tymethod.walk) during a depth-first
+/// traversal of a geometry. All of them do nothing by default and returning an error stops the
+/// traversal.
+///
+/// The calls for a polygon with one hole are: `enter_polygon(2)`, `enter_ring(0)`,
+/// `coordinates(LinearRing, ..)`, `leave_ring(0)`, `enter_ring(1)`, `coordinates(LinearRing,
+/// ..)`, `leave_ring(1)`, `leave_polygon()`.
+///
+/// # Example
+///
+/// ```
+/// use geos::{GResult, Geom, Geometry, GeometryTypes, GeometryVisitor};
+///
+/// /// Counts the holes and the points of a geometry.
+/// #[derive(Default)]
+/// struct Counter {
+///     holes: usize,
+///     points: usize,
+/// }
+///
+/// impl GeometryVisitor for Counter {
+///     fn enter_ring(&mut self, index: usize) -> GResult<()> {
+///         if index > 0 {
+///             self.holes += 1;
+///         }
+///         Ok(())
+///     }
+///
+///     fn coordinates(&mut self, _: GeometryTypes, coords: &[[f64; 3]]) -> GResult<()> {
+///         self.points += coords.len();
+///         Ok(())
+///     }
+/// }
+///
+/// let geom = Geometry::new_from_wkt("GEOMETRYCOLLECTION(POINT(0 0),
+///                                    POLYGON((0 0, 9 0, 9 9, 0 0), (5 1, 8 1, 8 4, 5 1)))")
+///                     .expect("Invalid geometry");
+/// let mut counter = Counter::default();
+/// geom.walk(&mut counter).expect("walk failed");
+/// assert_eq!(counter.holes, 1);
+/// assert_eq!(counter.points, 9);
+/// ```
+pub trait GeometryVisitor {
+    /// Called before the parts of a multi geometry or a geometry collection.
+    fn enter_collection(&mut self, _geom_type: GeometryTypes, _nb_parts: usize) -> GResult<()> {
+        Ok(())
+    }
+
+    /// Called after the parts of a multi geometry or a geometry collection.
+    fn leave_collection(&mut self, _geom_type: GeometryTypes) -> GResult<()> {
+        Ok(())
+    }
+
+    /// Called before the rings of a polygon (0 if it's empty, 1 for its exterior ring plus its
+    /// number of interior rings otherwise).
+    fn enter_polygon(&mut self, _nb_rings: usize) -> GResult<()> {
+        Ok(())
+    }
+
+    /// Called after the rings of a polygon.
+    fn leave_polygon(&mut self) -> GResult<()> {
+        Ok(())
+    }
+
+    /// Called before the coordinates of a polygon ring, `index` being 0 for the exterior ring.
+    fn enter_ring(&mut self, _index: usize) -> GResult<()> {
+        Ok(())
+    }
+
+    /// Called after the coordinates of a polygon ring.
+    fn leave_ring(&mut self, _index: usize) -> GResult<()> {
+        Ok(())
+    }
+
+    /// Called with the coordinates of each point, line string and linear ring (the rings of
+    /// polygons included). The Z values of 2D geometries are NaN.
+    fn coordinates(&mut self, _geom_type: GeometryTypes, _coords: &[[f64; 3]]) -> GResult<()> {
+        Ok(())
+    }
+}
+
+pub(crate) fn walk<'a, G: Geom<'a>, V: GeometryVisitor>(geom: &G, visitor: &mut V) -> GResult<()> {
+    let geom_type = geom.geometry_type();
+    match geom_type {
+        GeometryTypes::Point | GeometryTypes::LineString | GeometryTypes::LinearRing => {
+            let coords = if geom.is_empty()? {
+                Vec::new()
+            } else {
+                coords_from_seq(&geom.get_coord_seq()?)?
+            };
+            visitor.coordinates(geom_type, &coords)
+        }
+        GeometryTypes::Polygon => {
+            if geom.is_empty()? {
+                visitor.enter_polygon(0)?;
+                return visitor.leave_polygon();
+            }
+            let nb_interiors = geom.get_num_interior_rings()?;
+            visitor.enter_polygon(nb_interiors + 1)?;
+            visitor.enter_ring(0)?;
+            walk(&geom.get_exterior_ring()?, visitor)?;
+            visitor.leave_ring(0)?;
+            for n in 0..nb_interiors {
+                visitor.enter_ring(n + 1)?;
+                walk(&geom.get_interior_ring_n(n as _)?, visitor)?;
+                visitor.leave_ring(n + 1)?;
+            }
+            visitor.leave_polygon()
+        }
+        GeometryTypes::MultiPoint
+        | GeometryTypes::MultiLineString
+        | GeometryTypes::MultiPolygon
+        | GeometryTypes::GeometryCollection => {
+            let nb_parts = geom.get_num_geometries()?;
+            visitor.enter_collection(geom_type, nb_parts)?;
+            for n in 0..nb_parts {
+                walk(&geom.get_geometry_n(n)?, visitor)?;
+            }
+            visitor.leave_collection(geom_type)
+        }
+        geom_type if geom_type.is_curved() => unsupported_curve(geom_type),
+        _ => unknown_type(geom_type),
+    }
+}
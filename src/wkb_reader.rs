@@ -0,0 +1,221 @@
+use crate::{
+    AsRaw, AsRawMut, ContextHandle, ContextHandling, ContextInteractions, GResult, Geometry,
+};
+use context_handle::PtrWrap;
+use error::Error;
+use geos_sys::*;
+use std::sync::Arc;
+
+/// The `WKBReader` type is used to read `HEX` or `WKB` formatted input into [`Geometry`].
+///
+/// The byte order is read from the header of each (sub-)geometry, so both big endian (XDR) and
+/// little endian (NDR) inputs are accepted without any configuration. Use
+/// [`wkb_byte_order`](fn.wkb_byte_order.html) if you need to know which one a buffer uses.
+///
+/// # Example
+///
+/// ```
+/// use geos::{Geom, WKBReader};
+///
+/// let mut reader = WKBReader::new().expect("Failed to create WKBReader");
+///
+/// // POINT (1 2) in big endian.
+/// let xdr = [0u8, 0, 0, 0, 1, 63, 240, 0, 0, 0, 0, 0, 0, 64, 0, 0, 0, 0, 0, 0, 0];
+/// // POINT (1 2) in little endian.
+/// let ndr = [1u8, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 240, 63, 0, 0, 0, 0, 0, 0, 0, 64];
+///
+/// let geom1 = reader.read_wkb(&xdr).expect("failed to read XDR WKB");
+/// let geom2 = reader.read_wkb(&ndr).expect("failed to read NDR WKB");
+/// assert_eq!(geom1.to_wkt_precision(1).unwrap(), "POINT (1.0 2.0)");
+/// assert_eq!(geom1.equals(&geom2), Ok(true));
+/// ```
+pub struct WKBReader<'a> {
+    ptr: PtrWrap<*mut GEOSWKBReader>,
+    context: Arc<ContextHandle<'a>>,
+}
+
+impl<'a> WKBReader<'a> {
+    /// Creates a new `WKBReader` instance.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, Geometry, WKBReader};
+    ///
+    /// let point_geom = Geometry::new_from_wkt("POINT (2.5 2.5)").expect("Invalid geometry");
+    /// let mut reader = WKBReader::new().expect("Failed to create WKBReader");
+    ///
+    /// let wkb = point_geom.to_wkb().expect("conversion to WKB failed");
+    /// assert_eq!(reader.read_wkb(wkb.as_ref()).unwrap().to_wkt_precision(1).unwrap(),
+    ///            "POINT (2.5 2.5)");
+    /// ```
+    pub fn new() -> GResult<WKBReader<'a>> {
+        match ContextHandle::init_default("WKBReader::new") {
+            Ok(context_handle) => Self::new_with_context(context_handle),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Creates a new `WKBReader` instance with a given context.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{ContextHandling, Geom, Geometry, WKBReader};
+    ///
+    /// let point_geom = Geometry::new_from_wkt("POINT (2.5 2.5)").expect("Invalid geometry");
+    /// let mut reader = WKBReader::new_with_context(point_geom.clone_context())
+    ///                            .expect("Failed to create WKBReader");
+    ///
+    /// let wkb = point_geom.to_wkb().expect("conversion to WKB failed");
+    /// assert_eq!(reader.read_wkb(wkb.as_ref()).unwrap().to_wkt_precision(1).unwrap(),
+    ///            "POINT (2.5 2.5)");
+    /// ```
+    pub fn new_with_context(context: Arc<ContextHandle<'a>>) -> GResult<WKBReader<'a>> {
+        unsafe {
+            let ptr = GEOSWKBReader_create_r(context.as_raw());
+            WKBReader::new_from_raw(ptr, context, "new_with_context")
+        }
+    }
+
+    pub(crate) unsafe fn new_from_raw(
+        ptr: *mut GEOSWKBReader,
+        context: Arc<ContextHandle<'a>>,
+        caller: &str,
+    ) -> GResult<WKBReader<'a>> {
+        if ptr.is_null() {
+            let extra = if let Some(x) = context.get_last_error() {
+                format!("\nLast error: {}", x)
+            } else {
+                String::new()
+            };
+            return Err(Error::NoConstructionFromNullPtr(format!(
+                "WKBReader::{}{}",
+                caller, extra
+            )));
+        }
+        Ok(WKBReader {
+            ptr: PtrWrap(ptr),
+            context,
+        })
+    }
+
+    /// Reads a geometry from the given WKB buffer. The created geometry shares the context of
+    /// the reader.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, WKBReader};
+    ///
+    /// let mut reader = WKBReader::new().expect("Failed to create WKBReader");
+    /// let wkb = [1u8, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 4, 64, 0, 0, 0, 0, 0, 0, 4, 64];
+    /// let geom = reader.read_wkb(&wkb).expect("failed to read WKB");
+    /// assert_eq!(geom.to_wkt_precision(1).unwrap(), "POINT (2.5 2.5)");
+    ///
+    /// // Truncated input is an error, not a crash.
+    /// assert!(reader.read_wkb(&wkb[..10]).is_err());
+    /// ```
+    pub fn read_wkb(&mut self, wkb: &[u8]) -> GResult<Geometry<'a>> {
+        unsafe {
+            let ptr = GEOSWKBReader_read_r(
+                self.get_raw_context(),
+                self.as_raw_mut(),
+                wkb.as_ptr(),
+                wkb.len(),
+            );
+            Geometry::new_from_parser(ptr, self.clone_context(), "WKB", wkb)
+        }
+    }
+
+    /// Reads a geometry from the given HEX buffer. The created geometry shares the context of
+    /// the reader.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, WKBReader};
+    ///
+    /// let mut reader = WKBReader::new().expect("Failed to create WKBReader");
+    /// let geom = reader.read_hex(b"00000000013FF00000000000004000000000000000")
+    ///                  .expect("failed to read HEX");
+    /// assert_eq!(geom.to_wkt_precision(1).unwrap(), "POINT (1.0 2.0)");
+    /// ```
+    pub fn read_hex(&mut self, hex: &[u8]) -> GResult<Geometry<'a>> {
+        unsafe {
+            let ptr = GEOSWKBReader_readHEX_r(
+                self.get_raw_context(),
+                self.as_raw_mut(),
+                hex.as_ptr(),
+                hex.len(),
+            );
+            Geometry::new_from_parser(ptr, self.clone_context(), "HEX", hex)
+        }
+    }
+}
+
+unsafe impl<'a> Send for WKBReader<'a> {}
+unsafe impl<'a> Sync for WKBReader<'a> {}
+
+impl<'a> Drop for WKBReader<'a> {
+    fn drop(&mut self) {
+        unsafe { GEOSWKBReader_destroy_r(self.get_raw_context(), self.as_raw_mut()) };
+    }
+}
+
+impl<'a> ContextInteractions<'a> for WKBReader<'a> {
+    /// Set the context handle to the `WKBReader`.
+    ///
+    /// ```
+    /// use geos::{ContextInteractions, ContextHandle, WKBReader};
+    ///
+    /// let context_handle = ContextHandle::init().expect("invalid init");
+    /// let mut reader = WKBReader::new().expect("failed to create WKB reader");
+    /// context_handle.set_notice_message_handler(Some(Box::new(|s| println!("new message: {}", s))));
+    /// reader.set_context_handle(context_handle);
+    /// ```
+    fn set_context_handle(&mut self, context: ContextHandle<'a>) {
+        self.context = Arc::new(context);
+    }
+
+    /// Get the context handle of the `WKBReader`.
+    ///
+    /// ```
+    /// use geos::{ContextInteractions, WKBReader};
+    ///
+    /// let reader = WKBReader::new().expect("failed to create WKB reader");
+    /// let context = reader.get_context_handle();
+    /// context.set_notice_message_handler(Some(Box::new(|s| println!("new message: {}", s))));
+    /// ```
+    fn get_context_handle(&self) -> &ContextHandle<'a> {
+        &self.context
+    }
+}
+
+impl<'a> AsRaw for WKBReader<'a> {
+    type RawType = GEOSWKBReader;
+
+    fn as_raw(&self) -> *const Self::RawType {
+        *self.ptr
+    }
+}
+
+impl<'a> AsRawMut for WKBReader<'a> {
+    type RawType = GEOSWKBReader;
+
+    unsafe fn as_raw_mut_override(&self) -> *mut Self::RawType {
+        *self.ptr
+    }
+}
+
+impl<'a> ContextHandling for WKBReader<'a> {
+    type Context = Arc<ContextHandle<'a>>;
+
+    fn get_raw_context(&self) -> GEOSContextHandle_t {
+        self.context.as_raw()
+    }
+
+    fn clone_context(&self) -> Arc<ContextHandle<'a>> {
+        Arc::clone(&self.context)
+    }
+}
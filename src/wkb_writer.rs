@@ -49,8 +49,8 @@ impl<'a> WKBWriter<'a> {
     ///            "POINT (2.5 2.5)");
     /// ```
     pub fn new() -> GResult<WKBWriter<'a>> {
-        match ContextHandle::init_e(Some("WKBWriter::new")) {
-            Ok(context_handle) => Self::new_with_context(Arc::new(context_handle)),
+        match ContextHandle::init_default("WKBWriter::new") {
+            Ok(context_handle) => Self::new_with_context(context_handle),
             Err(e) => Err(e),
         }
     }
@@ -0,0 +1,176 @@
+use crate::{
+    AsRaw, AsRawMut, ContextHandle, ContextHandling, ContextInteractions, GResult, Geometry,
+};
+use context_handle::PtrWrap;
+use error::Error;
+use geos_sys::*;
+use std::ffi::CString;
+use std::sync::Arc;
+
+/// The `WKTReader` type is used to read `WKT` formatted input into [`Geometry`].
+///
+/// Unlike [`Geometry::new_from_wkt`], which creates a new reader (and a new context) on each call,
+/// a `WKTReader` can be reused for many inputs.
+///
+/// # Example
+///
+/// ```
+/// use geos::{Geom, WKTReader};
+///
+/// let mut reader = WKTReader::new().expect("Failed to create WKTReader");
+///
+/// let geom = reader.read_wkt("POINT (2.5 2.5)").expect("failed to read WKT");
+/// assert_eq!(geom.to_wkt_precision(1).unwrap(), "POINT (2.5 2.5)");
+/// assert!(reader.read_wkt("POINT (2.5").is_err());
+/// ```
+pub struct WKTReader<'a> {
+    ptr: PtrWrap<*mut GEOSWKTReader>,
+    context: Arc<ContextHandle<'a>>,
+}
+
+impl<'a> WKTReader<'a> {
+    /// Creates a new `WKTReader` instance.
+    pub fn new() -> GResult<WKTReader<'a>> {
+        match ContextHandle::init_default("WKTReader::new") {
+            Ok(context_handle) => Self::new_with_context(context_handle),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Creates a new `WKTReader` instance with a given context.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{ContextHandling, Geom, Geometry, WKTReader};
+    ///
+    /// let point_geom = Geometry::new_from_wkt("POINT (2.5 2.5)").expect("Invalid geometry");
+    /// let mut reader = WKTReader::new_with_context(point_geom.clone_context())
+    ///                            .expect("Failed to create WKTReader");
+    ///
+    /// let geom = reader.read_wkt("POINT (2.5 2.5)").expect("failed to read WKT");
+    /// assert_eq!(geom.equals(&point_geom), Ok(true));
+    /// ```
+    pub fn new_with_context(context: Arc<ContextHandle<'a>>) -> GResult<WKTReader<'a>> {
+        unsafe {
+            let ptr = GEOSWKTReader_create_r(context.as_raw());
+            WKTReader::new_from_raw(ptr, context, "new_with_context")
+        }
+    }
+
+    pub(crate) unsafe fn new_from_raw(
+        ptr: *mut GEOSWKTReader,
+        context: Arc<ContextHandle<'a>>,
+        caller: &str,
+    ) -> GResult<WKTReader<'a>> {
+        if ptr.is_null() {
+            let extra = if let Some(x) = context.get_last_error() {
+                format!("\nLast error: {}", x)
+            } else {
+                String::new()
+            };
+            return Err(Error::NoConstructionFromNullPtr(format!(
+                "WKTReader::{}{}",
+                caller, extra
+            )));
+        }
+        Ok(WKTReader {
+            ptr: PtrWrap(ptr),
+            context,
+        })
+    }
+
+    /// Reads a geometry from the given WKT string. The created geometry shares the context of
+    /// the reader.
+    ///
+    /// Both the `POINT Z (1 2 3)` and the old `POINT (1 2 3)` styles are accepted for 3D
+    /// geometries.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geom, WKTReader};
+    ///
+    /// let mut reader = WKTReader::new().expect("Failed to create WKTReader");
+    /// let new_style = reader.read_wkt("LINESTRING Z (0 0 1, 1 1 2)").expect("failed to read WKT");
+    /// let old_style = reader.read_wkt("LINESTRING (0 0 1, 1 1 2)").expect("failed to read WKT");
+    /// assert_eq!(new_style.has_z(), Ok(true));
+    /// assert_eq!(old_style.has_z(), Ok(true));
+    /// assert_eq!(new_style.to_wkt(), old_style.to_wkt());
+    /// ```
+    pub fn read_wkt(&mut self, wkt: &str) -> GResult<Geometry<'a>> {
+        let c_str = CString::new(wkt)
+            .map_err(|e| Error::InvalidInput(format!("Conversion to CString failed: {}", e)))?;
+        unsafe {
+            let ptr =
+                GEOSWKTReader_read_r(self.get_raw_context(), self.as_raw_mut(), c_str.as_ptr());
+            Geometry::new_from_parser(ptr, self.clone_context(), "WKT", wkt.as_bytes())
+        }
+    }
+}
+
+unsafe impl<'a> Send for WKTReader<'a> {}
+unsafe impl<'a> Sync for WKTReader<'a> {}
+
+impl<'a> Drop for WKTReader<'a> {
+    fn drop(&mut self) {
+        unsafe { GEOSWKTReader_destroy_r(self.get_raw_context(), self.as_raw_mut()) };
+    }
+}
+
+impl<'a> ContextInteractions<'a> for WKTReader<'a> {
+    /// Set the context handle to the `WKTReader`.
+    ///
+    /// ```
+    /// use geos::{ContextInteractions, ContextHandle, WKTReader};
+    ///
+    /// let context_handle = ContextHandle::init().expect("invalid init");
+    /// let mut reader = WKTReader::new().expect("failed to create WKT reader");
+    /// context_handle.set_notice_message_handler(Some(Box::new(|s| println!("new message: {}", s))));
+    /// reader.set_context_handle(context_handle);
+    /// ```
+    fn set_context_handle(&mut self, context: ContextHandle<'a>) {
+        self.context = Arc::new(context);
+    }
+
+    /// Get the context handle of the `WKTReader`.
+    ///
+    /// ```
+    /// use geos::{ContextInteractions, WKTReader};
+    ///
+    /// let reader = WKTReader::new().expect("failed to create WKT reader");
+    /// let context = reader.get_context_handle();
+    /// context.set_notice_message_handler(Some(Box::new(|s| println!("new message: {}", s))));
+    /// ```
+    fn get_context_handle(&self) -> &ContextHandle<'a> {
+        &self.context
+    }
+}
+
+impl<'a> AsRaw for WKTReader<'a> {
+    type RawType = GEOSWKTReader;
+
+    fn as_raw(&self) -> *const Self::RawType {
+        *self.ptr
+    }
+}
+
+impl<'a> AsRawMut for WKTReader<'a> {
+    type RawType = GEOSWKTReader;
+
+    unsafe fn as_raw_mut_override(&self) -> *mut Self::RawType {
+        *self.ptr
+    }
+}
+
+impl<'a> ContextHandling for WKTReader<'a> {
+    type Context = Arc<ContextHandle<'a>>;
+
+    fn get_raw_context(&self) -> GEOSContextHandle_t {
+        self.context.as_raw()
+    }
+
+    fn clone_context(&self) -> Arc<ContextHandle<'a>> {
+        Arc::clone(&self.context)
+    }
+}
@@ -24,6 +24,7 @@ use std::sync::Arc;
 pub struct WKTWriter<'a> {
     ptr: PtrWrap<*mut GEOSWKTWriter>,
     context: Arc<ContextHandle<'a>>,
+    max_coordinates: Option<usize>,
 }
 
 impl<'a> WKTWriter<'a> {
@@ -40,8 +41,8 @@ impl<'a> WKTWriter<'a> {
     /// assert_eq!(writer.write(&point_geom).unwrap(), "POINT (2.5000000000000000 2.5000000000000000)");
     /// ```
     pub fn new() -> GResult<WKTWriter<'a>> {
-        match ContextHandle::init_e(Some("WKTWriter::new")) {
-            Ok(context_handle) => Self::new_with_context(Arc::new(context_handle)),
+        match ContextHandle::init_default("WKTWriter::new") {
+            Ok(context_handle) => Self::new_with_context(context_handle),
             Err(e) => Err(e),
         }
     }
@@ -85,6 +86,7 @@ impl<'a> WKTWriter<'a> {
         Ok(WKTWriter {
             ptr: PtrWrap(ptr),
             context,
+            max_coordinates: None,
         })
     }
 
@@ -104,10 +106,46 @@ impl<'a> WKTWriter<'a> {
         unsafe {
             let ptr =
                 GEOSWKTWriter_write_r(self.get_raw_context(), self.as_raw_mut(), geometry.as_raw());
-            managed_string(ptr, self.get_context_handle(), "WKTWriter::write")
+            let wkt = managed_string(ptr, self.get_context_handle(), "WKTWriter::write")?;
+            Ok(match self.max_coordinates {
+                Some(max_coordinates) => elide_coordinates(wkt, max_coordinates),
+                None => wkt,
+            })
         }
     }
 
+    /// Limits the number of coordinates written by [`WKTWriter::write`]: if a geometry has more
+    /// than `max_coordinates`, only the first and last ones are kept, the others being replaced
+    /// by `...` (along with the parentheses between them, so the output isn't valid WKT anymore).
+    /// Passing [`None`] removes the limit, which is the default.
+    ///
+    /// It is meant to log huge geometries without flooding the logs.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use geos::{Geometry, WKTWriter};
+    ///
+    /// let mut writer = WKTWriter::new().expect("Failed to create WKTWriter");
+    /// writer.set_trim(true);
+    /// writer.set_max_coordinates(Some(4));
+    ///
+    /// let line = Geometry::new_from_wkt("LINESTRING(0 0, 1 1, 2 2, 3 3, 4 4, 5 5)")
+    ///                     .expect("Invalid geometry");
+    /// assert_eq!(writer.write(&line).unwrap(), "LINESTRING (0 0, 1 1, ..., 4 4, 5 5)");
+    ///
+    /// let polygons = Geometry::new_from_wkt("MULTIPOLYGON(((0 0, 1 0, 1 1, 0 0)),
+    ///                                                     ((5 5, 6 5, 6 6, 5 5)))")
+    ///                         .expect("Invalid geometry");
+    /// assert_eq!(writer.write(&polygons).unwrap(), "MULTIPOLYGON (((0 0, 1 0, ..., 6 6, 5 5)))");
+    ///
+    /// let point = Geometry::new_from_wkt("POINT(1 2)").expect("Invalid geometry");
+    /// assert_eq!(writer.write(&point).unwrap(), "POINT (1 2)");
+    /// ```
+    pub fn set_max_coordinates(&mut self, max_coordinates: Option<usize>) {
+        self.max_coordinates = max_coordinates;
+    }
+
     /// Sets the `precision` to be used when calling [`WKTWriter::write`]. Often, what users
     /// actually want is the [`WKTWriter::set_trim`] method instead.
     ///
@@ -136,7 +174,9 @@ impl<'a> WKTWriter<'a> {
     }
 
     /// Sets the number of dimensions to be used when calling [`WKTWriter::write`]. By default, it
-    /// is 2.
+    /// is 2. With 3 dimensions, geometries having Z values are written with the `Z` suffix (see
+    /// [`set_old_3d`](#method.set_old_3d) for the old style), the others are still written in 2D.
+    /// Measures (M) aren't supported by GEOS, so `ZM` is never written.
     ///
     /// # Example
     ///
@@ -151,6 +191,9 @@ impl<'a> WKTWriter<'a> {
     ///
     /// writer.set_output_dimension(OutputDimension::ThreeD);
     /// assert_eq!(writer.write(&point_geom).unwrap(), "POINT Z (1.1 2.2 3.3)");
+    ///
+    /// let point_2d = Geometry::new_from_wkt("POINT (1.1 2.2)").expect("Invalid geometry");
+    /// assert_eq!(writer.write(&point_2d).unwrap(), "POINT (1.1 2.2)");
     /// ```
     pub fn set_output_dimension(&mut self, dimension: OutputDimension) {
         unsafe {
@@ -205,12 +248,15 @@ impl<'a> WKTWriter<'a> {
         unsafe { GEOSWKTWriter_setTrim_r(self.get_raw_context(), self.as_raw_mut(), trim as _) }
     }
 
-    /// Enables/disables old 3D/4D WKT style generation.
+    /// Enables/disables old 3D/4D WKT style generation: `POINT (1 2 3)` instead of
+    /// `POINT Z (1 2 3)`. Both styles are accepted by [`WKTReader`](struct.WKTReader.html) and
+    /// [`Geometry::new_from_wkt`](struct.Geometry.html#method.new_from_wkt). It only matters
+    /// when the output dimension is 3.
     ///
     /// # Example
     ///
     /// ```
-    /// use geos::{Geometry, OutputDimension, WKTWriter};
+    /// use geos::{Geom, Geometry, OutputDimension, WKTReader, WKTWriter};
     ///
     /// let point_geom = Geometry::new_from_wkt("POINT (2.5 2.5 2.5)").expect("Invalid geometry");
     /// let mut writer = WKTWriter::new().expect("Failed to create WKTWriter");
@@ -220,15 +266,71 @@ impl<'a> WKTWriter<'a> {
     ///
     /// assert_eq!(writer.write(&point_geom).unwrap(), "POINT Z (2.5 2.5 2.5)");
     ///
-    /// writer.set_old_3D(true);
-    /// assert_eq!(writer.write(&point_geom).unwrap(), "POINT (2.5 2.5 2.5)");
+    /// writer.set_old_3d(true);
+    /// let old_wkt = writer.write(&point_geom).unwrap();
+    /// assert_eq!(old_wkt, "POINT (2.5 2.5 2.5)");
+    ///
+    /// let mut reader = WKTReader::new().expect("Failed to create WKTReader");
+    /// let geom = reader.read_wkt(&old_wkt).expect("failed to read WKT");
+    /// assert_eq!(geom.has_z(), Ok(true));
+    /// writer.set_old_3d(false);
+    /// assert_eq!(writer.write(&geom).unwrap(), "POINT Z (2.5 2.5 2.5)");
     /// ```
+    pub fn set_old_3d(&mut self, use_old_3d: bool) {
+        unsafe {
+            GEOSWKTWriter_setOld3D_r(self.get_raw_context(), self.as_raw_mut(), use_old_3d as _)
+        }
+    }
+
+    /// Same as [`set_old_3d`](#method.set_old_3d).
+    #[deprecated(note = "use set_old_3d")]
     #[allow(non_snake_case)]
     pub fn set_old_3D(&mut self, use_old_3D: bool) {
-        unsafe {
-            GEOSWKTWriter_setOld3D_r(self.get_raw_context(), self.as_raw_mut(), use_old_3D as _)
+        self.set_old_3d(use_old_3D)
+    }
+}
+
+/// Replaces the coordinates of `wkt` after the first `max_coordinates / 2` (rounded up) and
+/// before the last `max_coordinates / 2` with `...`.
+fn elide_coordinates(wkt: String, max_coordinates: usize) -> String {
+    // The (start, end) positions of the coordinates: the pieces between the delimiters starting
+    // like a number (the geometry types and `EMPTY` are upper case).
+    let mut coords = Vec::new();
+    let mut piece_start = 0;
+    for (pos, c) in wkt.char_indices().chain(Some((wkt.len(), ')'))) {
+        if c != '(' && c != ')' && c != ',' {
+            continue;
         }
+        let piece = &wkt[piece_start..pos];
+        let trimmed = piece.trim_start();
+        if let Some(first) = trimmed.chars().next() {
+            if first.is_ascii_digit() || "-+.ni".contains(first) {
+                let start = piece_start + piece.len() - trimmed.len();
+                coords.push((start, start + trimmed.trim_end().len()));
+            }
+        }
+        piece_start = pos + 1;
+    }
+    if coords.len() <= max_coordinates {
+        return wkt;
     }
+    let head = max_coordinates.div_ceil(2);
+    let tail = max_coordinates / 2;
+    let (cut_start, before) = match head {
+        0 => (coords[0].0, ""),
+        _ => (coords[head - 1].1, ", "),
+    };
+    let (cut_end, after) = match tail {
+        0 => (coords[coords.len() - 1].1, ""),
+        _ => (coords[coords.len() - tail].0, ", "),
+    };
+    format!(
+        "{}{}...{}{}",
+        &wkt[..cut_start],
+        before,
+        after,
+        &wkt[cut_end..]
+    )
 }
 
 unsafe impl<'a> Send for WKTWriter<'a> {}
@@ -296,3 +398,27 @@ impl<'a> ContextHandling for WKTWriter<'a> {
         Arc::clone(&self.context)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::elide_coordinates;
+
+    #[test]
+    fn elide() {
+        let wkt = "POLYGON ((0 0, 1 0, 1 1, 0 1, 0 0), (0.2 0.2, 0.2 0.4, 0.4 0.2, 0.2 0.2))";
+        assert_eq!(elide_coordinates(wkt.to_owned(), 9), wkt);
+        assert_eq!(
+            elide_coordinates(wkt.to_owned(), 3),
+            "POLYGON ((0 0, 1 0, ..., 0.2 0.2))"
+        );
+        assert_eq!(elide_coordinates(wkt.to_owned(), 1), "POLYGON ((0 0, ...))");
+        assert_eq!(elide_coordinates(wkt.to_owned(), 0), "POLYGON ((...))");
+        assert_eq!(
+            elide_coordinates(
+                "GEOMETRYCOLLECTION (POINT EMPTY, POINT (1 2))".to_owned(),
+                0
+            ),
+            "GEOMETRYCOLLECTION (POINT EMPTY, POINT (...))"
+        );
+    }
+}